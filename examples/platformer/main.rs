@@ -26,6 +26,10 @@ fn main() {
                 load_level_neighbors: true,
             },
             set_clear_color: SetClearColor::FromLevelBackground,
+            int_grid_merging: IntGridMerging::Enabled {
+                values: vec![1, 3],
+                outlines: false,
+            },
             ..Default::default()
         })
         .add_systems(Startup, systems::setup)
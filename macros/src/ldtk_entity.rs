@@ -6,6 +6,7 @@ static WORLDLY_ATTRIBUTE_NAME: &str = "worldly";
 static GRID_COORDS_ATTRIBUTE_NAME: &str = "grid_coords";
 static LDTK_ENTITY_ATTRIBUTE_NAME: &str = "ldtk_entity";
 static FROM_ENTITY_INSTANCE_ATTRIBUTE_NAME: &str = "from_entity_instance";
+static LDTK_FIELD_ATTRIBUTE_NAME: &str = "ldtk_field";
 static WITH_ATTRIBUTE_NAME: &str = "with";
 
 pub fn expand_ldtk_entity_derive(ast: syn::DeriveInput) -> proc_macro::TokenStream {
@@ -88,6 +89,20 @@ pub fn expand_ldtk_entity_derive(ast: syn::DeriveInput) -> proc_macro::TokenStre
             continue;
         }
 
+        let ldtk_field = field
+            .attrs
+            .iter()
+            .find(|a| *a.path.get_ident().as_ref().unwrap() == LDTK_FIELD_ATTRIBUTE_NAME);
+        if let Some(attribute) = ldtk_field {
+            field_constructions.push(expand_ldtk_field_attribute(
+                attribute,
+                field_name,
+                field_type,
+                quote! { entity_instance },
+            ));
+            continue;
+        }
+
         let with = field
             .attrs
             .iter()
@@ -340,6 +355,54 @@ fn expand_from_entity_instance_attribute(
     }
 }
 
+/// Expands `#[ldtk_field...]`, reading the LDtk field from the `source` expression.
+pub fn expand_ldtk_field_attribute(
+    attribute: &syn::Attribute,
+    field_name: &syn::Ident,
+    field_type: &syn::Type,
+    source: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let identifier = match attribute.parse_meta() {
+        Ok(syn::Meta::Path(_)) => field_name.to_string(),
+        Ok(syn::Meta::List(syn::MetaList { nested, .. })) if nested.len() == 1 => {
+            match nested.first().unwrap() {
+                syn::NestedMeta::Lit(syn::Lit::Str(identifier)) => identifier.value(),
+                other => {
+                    let error = syn::Error::new_spanned(
+                        other,
+                        "Expected the LDtk field identifier as a string literal, like #[ldtk_field(\"Health\")]",
+                    )
+                    .to_compile_error();
+                    return quote! { #field_name: #error, };
+                }
+            }
+        }
+        _ => {
+            let error = syn::Error::new_spanned(
+                attribute,
+                "#[ldtk_field...] attribute should take the form #[ldtk_field(\"FieldIdentifier\")] or #[ldtk_field]",
+            )
+            .to_compile_error();
+            return quote! { #field_name: #error, };
+        }
+    };
+
+    // Spanned to the field type, so unsupported types are reported there
+    let conversion = quote::quote_spanned! {syn::spanned::Spanned::span(field_type)=>
+        (&&&&&&LdtkFieldConversion::<#field_type>(std::marker::PhantomData))
+            .convert_ldtk_field(#identifier, value)
+    };
+
+    quote! {
+        #field_name: bevy_ecs_ldtk::app::ldtk_field::ldtk_field_or_default(#source, #identifier, |value| {
+            #[allow(unused_imports)]
+            use bevy_ecs_ldtk::app::ldtk_field::*;
+
+            #conversion
+        }),
+    }
+}
+
 fn expand_with_attribute(
     attribute: &syn::Attribute,
     field_name: &syn::Ident,
@@ -0,0 +1,146 @@
+use quote::quote;
+use std::path::PathBuf;
+
+pub fn expand_ldtk_enums(path: syn::LitStr) -> proc_macro::TokenStream {
+    match ldtk_enums(&path) {
+        Ok(gen) => gen.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn ldtk_enums(path: &syn::LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| syn::Error::new(path.span(), "CARGO_MANIFEST_DIR is not set"))?;
+    let full_path = PathBuf::from(manifest_dir).join(path.value());
+
+    let contents = std::fs::read_to_string(&full_path).map_err(|e| {
+        syn::Error::new(
+            path.span(),
+            format!("could not read {}: {e}", full_path.display()),
+        )
+    })?;
+    let project: serde_json::Value = serde_json::from_str(&contents).map_err(|e| {
+        syn::Error::new(
+            path.span(),
+            format!("could not parse {} as JSON: {e}", full_path.display()),
+        )
+    })?;
+
+    let defs = &project["defs"];
+    let enum_definitions = ["enums", "externalEnums"]
+        .iter()
+        .filter_map(|key| defs[key].as_array())
+        .flatten();
+
+    let mut enums = Vec::new();
+    for enum_definition in enum_definitions {
+        enums.push(expand_enum_definition(path, enum_definition)?);
+    }
+
+    // Including the project makes cargo rebuild the caller whenever the project changes.
+    let full_path = full_path.to_string_lossy();
+
+    Ok(quote! {
+        const _: &[u8] = include_bytes!(#full_path);
+
+        #(#enums)*
+    })
+}
+
+fn expand_enum_definition(
+    path: &syn::LitStr,
+    enum_definition: &serde_json::Value,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let identifier = enum_definition["identifier"]
+        .as_str()
+        .ok_or_else(|| syn::Error::new(path.span(), "enum definition has no identifier"))?;
+    let enum_name = ident(path, identifier)?;
+
+    let value_identifiers = enum_definition["values"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|value| {
+            value["id"].as_str().ok_or_else(|| {
+                syn::Error::new(
+                    path.span(),
+                    format!("a value of the {identifier} enum has no id"),
+                )
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let variants = value_identifiers
+        .iter()
+        .map(|value| ident(path, value))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let doc = format!(
+        " The `{identifier}` enum of `{}`, generated by `ldtk_enums!`.",
+        path.value()
+    );
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[allow(non_camel_case_types)]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum #enum_name {
+            #(#variants,)*
+        }
+
+        impl bevy_ecs_ldtk::ldtk::ldtk_enum::LdtkEnum for #enum_name {
+            const IDENTIFIER: &'static str = #identifier;
+            const VALUES: &'static [Self] = &[#(Self::#variants,)*];
+
+            fn as_str(&self) -> &'static str {
+                match *self {
+                    #(Self::#variants => #value_identifiers,)*
+                }
+            }
+        }
+
+        impl std::str::FromStr for #enum_name {
+            type Err = bevy_ecs_ldtk::ldtk::ldtk_enum::LdtkEnumError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                <Self as bevy_ecs_ldtk::ldtk::ldtk_enum::LdtkEnum>::from_identifier(s)
+            }
+        }
+
+        impl std::fmt::Display for #enum_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(bevy_ecs_ldtk::ldtk::ldtk_enum::LdtkEnum::as_str(self))
+            }
+        }
+
+        impl std::convert::TryFrom<&bevy_ecs_ldtk::ldtk::FieldValue> for #enum_name {
+            type Error = bevy_ecs_ldtk::ldtk::ldtk_enum::LdtkEnumError;
+
+            fn try_from(value: &bevy_ecs_ldtk::ldtk::FieldValue) -> Result<Self, Self::Error> {
+                <Self as bevy_ecs_ldtk::ldtk::ldtk_enum::LdtkEnum>::from_field_value(value)
+            }
+        }
+    })
+}
+
+/// Converts an LDtk identifier into a Rust identifier, using a raw identifier for keywords.
+fn ident(path: &syn::LitStr, identifier: &str) -> syn::Result<syn::Ident> {
+    if let Ok(ident) = syn::parse_str::<syn::Ident>(identifier) {
+        return Ok(ident);
+    }
+
+    let is_identifier = identifier
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && identifier.chars().all(|c| c.is_alphanumeric() || c == '_');
+    let can_be_raw = !matches!(identifier, "_" | "crate" | "self" | "Self" | "super");
+
+    if is_identifier && can_be_raw {
+        Ok(syn::Ident::new_raw(identifier, path.span()))
+    } else {
+        Err(syn::Error::new(
+            path.span(),
+            format!("{identifier} is not a valid Rust identifier"),
+        ))
+    }
+}
@@ -0,0 +1,125 @@
+use quote::{format_ident, quote};
+
+static LDTK_ATTRIBUTE_NAME: &str = "ldtk";
+
+pub fn expand_ldtk_fields_derive(ast: syn::DeriveInput) -> proc_macro::TokenStream {
+    let struct_name = &ast.ident;
+
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => panic!("Expected a struct with named fields."),
+    };
+
+    let mut field_names = Vec::new();
+    let mut field_variables = Vec::new();
+    let mut field_extractions = Vec::new();
+    for field in fields {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_type = &field.ty;
+        let field_variable = format_ident!("__{}", field_name);
+
+        let mut identifier = field_name.to_string();
+        let mut default = false;
+
+        for attribute in field
+            .attrs
+            .iter()
+            .filter(|a| a.path.is_ident(LDTK_ATTRIBUTE_NAME))
+        {
+            let (rename, is_default) = parse_ldtk_attribute(attribute);
+            identifier = rename.unwrap_or(identifier);
+            default |= is_default;
+        }
+
+        let extraction = quote! {
+            bevy_ecs_ldtk::ldtk::ldtk_fields::LdtkFields::get_typed_field::<#field_type>(value, #identifier)
+        };
+
+        field_extractions.push(if default {
+            quote! {
+                match #extraction {
+                    Err(bevy_ecs_ldtk::ldtk::ldtk_fields::LdtkFieldsError::FieldNotFound { .. })
+                    | Err(bevy_ecs_ldtk::ldtk::ldtk_fields::LdtkFieldsError::UnexpectedNull { .. }) => {
+                        Ok(<#field_type as std::default::Default>::default())
+                    }
+                    result => result,
+                }
+            }
+        } else {
+            extraction
+        });
+        field_names.push(field_name);
+        field_variables.push(field_variable);
+    }
+
+    let construction = if fields.is_empty() {
+        quote! { Ok(Self {}) }
+    } else {
+        quote! {
+            #(let #field_variables = #field_extractions;)*
+
+            match (#(#field_variables,)*) {
+                (#(Ok(#field_variables),)*) => Ok(Self {
+                    #(#field_names: #field_variables,)*
+                }),
+                (#(#field_variables,)*) => Err(bevy_ecs_ldtk::ldtk::ldtk_fields::LdtkFieldsErrors(
+                    [#(#field_variables.err(),)*].into_iter().flatten().collect(),
+                )),
+            }
+        }
+    };
+
+    let generics = &ast.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let gen = quote! {
+        impl #impl_generics std::convert::TryFrom<&bevy_ecs_ldtk::prelude::EntityInstance> for #struct_name #ty_generics #where_clause {
+            type Error = bevy_ecs_ldtk::ldtk::ldtk_fields::LdtkFieldsErrors;
+
+            fn try_from(value: &bevy_ecs_ldtk::prelude::EntityInstance) -> Result<Self, Self::Error> {
+                #construction
+            }
+        }
+
+        impl #impl_generics std::convert::TryFrom<&bevy_ecs_ldtk::ldtk::Level> for #struct_name #ty_generics #where_clause {
+            type Error = bevy_ecs_ldtk::ldtk::ldtk_fields::LdtkFieldsErrors;
+
+            fn try_from(value: &bevy_ecs_ldtk::ldtk::Level) -> Result<Self, Self::Error> {
+                #construction
+            }
+        }
+    };
+    gen.into()
+}
+
+/// Parses `#[ldtk(rename = "...", default)]` into the rename and whether `default` is present.
+fn parse_ldtk_attribute(attribute: &syn::Attribute) -> (Option<String>, bool) {
+    let nested = match attribute
+        .parse_meta()
+        .expect("Cannot parse #[ldtk...] attribute")
+    {
+        syn::Meta::List(syn::MetaList { nested, .. }) => nested,
+        _ => panic!("#[ldtk...] attribute should take the form #[ldtk(rename = \"...\", default)]"),
+    };
+
+    let mut rename = None;
+    let mut default = false;
+    for meta in nested {
+        match meta {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                path,
+                lit: syn::Lit::Str(identifier),
+                ..
+            })) if path.is_ident("rename") => rename = Some(identifier.value()),
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("default") => {
+                default = true
+            }
+            _ => panic!("Expected rename = \"...\" or default as arguments of #[ldtk(...)]"),
+        }
+    }
+
+    (rename, default)
+}
@@ -2,6 +2,7 @@ use quote::quote;
 
 static LDTK_INT_CELL_ATTRIBUTE_NAME: &str = "ldtk_int_cell";
 static FROM_INT_GRID_CELL_ATTRIBUTE_NAME: &str = "from_int_grid_cell";
+static FROM_INT_GRID_CELL_CONTEXT_ATTRIBUTE_NAME: &str = "from_int_grid_cell_context";
 static WITH_ATTRIBUTE_NAME: &str = "with";
 
 pub fn expand_ldtk_int_cell_derive(ast: syn::DeriveInput) -> proc_macro::TokenStream {
@@ -42,6 +43,16 @@ pub fn expand_ldtk_int_cell_derive(ast: syn::DeriveInput) -> proc_macro::TokenSt
             continue;
         }
 
+        let from_int_grid_cell_context = field.attrs.iter().find(|a| {
+            *a.path.get_ident().as_ref().unwrap() == FROM_INT_GRID_CELL_CONTEXT_ATTRIBUTE_NAME
+        });
+        if let Some(attribute) = from_int_grid_cell_context {
+            field_constructions.push(expand_from_int_grid_cell_context_attribute(
+                attribute, field_name, field_type,
+            ));
+            continue;
+        }
+
         let with = field
             .attrs
             .iter()
@@ -66,6 +77,7 @@ pub fn expand_ldtk_int_cell_derive(ast: syn::DeriveInput) -> proc_macro::TokenSt
             fn bundle_int_cell(
                 int_grid_cell: bevy_ecs_ldtk::prelude::IntGridCell,
                 layer_instance: &bevy_ecs_ldtk::prelude::LayerInstance,
+                context: &bevy_ecs_ldtk::prelude::IntGridCellContext,
             ) -> Self {
                 Self {
                     #(#field_constructions)*
@@ -88,7 +100,7 @@ fn expand_ldtk_int_cell_attribute(
     {
         syn::Meta::Path(_) => {
             quote! {
-                #field_name: <#field_type as bevy_ecs_ldtk::prelude::LdtkIntCell>::bundle_int_cell(int_grid_cell, layer_instance),
+                #field_name: <#field_type as bevy_ecs_ldtk::prelude::LdtkIntCell>::bundle_int_cell(int_grid_cell, layer_instance, context),
             }
         }
         _ => panic!("#[ldtk_int_cell] attribute should take the form #[ldtk_int_cell]"),
@@ -115,6 +127,26 @@ fn expand_from_int_grid_cell_attribute(
     }
 }
 
+fn expand_from_int_grid_cell_context_attribute(
+    attribute: &syn::Attribute,
+    field_name: &syn::Ident,
+    field_type: &syn::Type,
+) -> proc_macro2::TokenStream {
+    match attribute
+        .parse_meta()
+        .expect("Cannot parse #[from_int_grid_cell_context] attribute")
+    {
+        syn::Meta::Path(_) => {
+            quote! {
+                #field_name: <#field_type as From<&bevy_ecs_ldtk::prelude::IntGridCellContext>>::from(context),
+            }
+        }
+        _ => {
+            panic!("#[from_int_grid_cell_context] attribute should take the form #[from_int_grid_cell_context]")
+        }
+    }
+}
+
 fn expand_with_attribute(
     attribute: &syn::Attribute,
     field_name: &syn::Ident,
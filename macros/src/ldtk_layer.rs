@@ -0,0 +1,175 @@
+use quote::quote;
+
+static LDTK_LAYER_ATTRIBUTE_NAME: &str = "ldtk_layer";
+static FROM_LAYER_INSTANCE_ATTRIBUTE_NAME: &str = "from_layer_instance";
+static FROM_LAYER_DEFINITION_ATTRIBUTE_NAME: &str = "from_layer_definition";
+static WITH_ATTRIBUTE_NAME: &str = "with";
+
+pub fn expand_ldtk_layer_derive(ast: syn::DeriveInput) -> proc_macro::TokenStream {
+    let struct_name = &ast.ident;
+
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => panic!("Expected a struct with named fields."),
+    };
+
+    let mut field_constructions = Vec::new();
+    for field in fields {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_type = &field.ty;
+
+        let ldtk_layer = field
+            .attrs
+            .iter()
+            .find(|a| *a.path.get_ident().as_ref().unwrap() == LDTK_LAYER_ATTRIBUTE_NAME);
+        if let Some(attribute) = ldtk_layer {
+            field_constructions.push(expand_ldtk_layer_attribute(
+                attribute, field_name, field_type,
+            ));
+            continue;
+        }
+
+        let from_layer_instance = field
+            .attrs
+            .iter()
+            .find(|a| *a.path.get_ident().as_ref().unwrap() == FROM_LAYER_INSTANCE_ATTRIBUTE_NAME);
+        if let Some(attribute) = from_layer_instance {
+            field_constructions.push(expand_from_layer_instance_attribute(
+                attribute, field_name, field_type,
+            ));
+            continue;
+        }
+
+        let from_layer_definition = field.attrs.iter().find(|a| {
+            *a.path.get_ident().as_ref().unwrap() == FROM_LAYER_DEFINITION_ATTRIBUTE_NAME
+        });
+        if let Some(attribute) = from_layer_definition {
+            field_constructions.push(expand_from_layer_definition_attribute(
+                attribute, field_name, field_type,
+            ));
+            continue;
+        }
+
+        let with = field
+            .attrs
+            .iter()
+            .find(|a| *a.path.get_ident().as_ref().unwrap() == WITH_ATTRIBUTE_NAME);
+        if let Some(attribute) = with {
+            field_constructions.push(expand_with_attribute(attribute, field_name, field_type));
+            continue;
+        }
+    }
+
+    let generics = &ast.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let struct_update = if field_constructions.len() < fields.len() {
+        quote! { ..<Self as std::default::Default>::default() }
+    } else {
+        quote! {}
+    };
+
+    let gen = quote! {
+        impl #impl_generics bevy_ecs_ldtk::prelude::LdtkLayer for #struct_name #ty_generics #where_clause {
+            fn bundle_layer(
+                layer_instance: &bevy_ecs_ldtk::prelude::LayerInstance,
+                layer_definition: &bevy_ecs_ldtk::ldtk::LayerDefinition,
+                asset_server: &bevy::prelude::AssetServer,
+            ) -> Self {
+                Self {
+                    #(#field_constructions)*
+                    #struct_update
+                }
+            }
+        }
+    };
+    gen.into()
+}
+
+fn expand_ldtk_layer_attribute(
+    attribute: &syn::Attribute,
+    field_name: &syn::Ident,
+    field_type: &syn::Type,
+) -> proc_macro2::TokenStream {
+    match attribute
+        .parse_meta()
+        .expect("Cannot parse #[ldtk_layer] attribute")
+    {
+        syn::Meta::Path(_) => {
+            quote! {
+                #field_name: <#field_type as bevy_ecs_ldtk::prelude::LdtkLayer>::bundle_layer(layer_instance, layer_definition, asset_server),
+            }
+        }
+        _ => panic!("#[ldtk_layer] attribute should take the form #[ldtk_layer]"),
+    }
+}
+
+fn expand_from_layer_instance_attribute(
+    attribute: &syn::Attribute,
+    field_name: &syn::Ident,
+    field_type: &syn::Type,
+) -> proc_macro2::TokenStream {
+    match attribute
+        .parse_meta()
+        .expect("Cannot parse #[from_layer_instance] attribute")
+    {
+        syn::Meta::Path(_) => {
+            quote! {
+                #field_name: <#field_type as From<&bevy_ecs_ldtk::prelude::LayerInstance>>::from(layer_instance),
+            }
+        }
+        _ => {
+            panic!("#[from_layer_instance] attribute should take the form #[from_layer_instance]")
+        }
+    }
+}
+
+fn expand_from_layer_definition_attribute(
+    attribute: &syn::Attribute,
+    field_name: &syn::Ident,
+    field_type: &syn::Type,
+) -> proc_macro2::TokenStream {
+    match attribute
+        .parse_meta()
+        .expect("Cannot parse #[from_layer_definition] attribute")
+    {
+        syn::Meta::Path(_) => {
+            quote! {
+                #field_name: <#field_type as From<&bevy_ecs_ldtk::ldtk::LayerDefinition>>::from(layer_definition),
+            }
+        }
+        _ => {
+            panic!(
+                "#[from_layer_definition] attribute should take the form #[from_layer_definition]"
+            )
+        }
+    }
+}
+
+fn expand_with_attribute(
+    attribute: &syn::Attribute,
+    field_name: &syn::Ident,
+    _: &syn::Type,
+) -> proc_macro2::TokenStream {
+    match attribute
+        .parse_meta()
+        .expect("Cannot parse #[with...] attribute")
+    {
+        syn::Meta::List(syn::MetaList { nested, .. }) if nested.len() == 1 => {
+            match nested.first().unwrap() {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) => {
+                    quote! {
+                        #field_name: #path(layer_instance, layer_definition),
+                    }
+                }
+                _ => panic!("Expected function as the only argument of #[with(...)]"),
+            }
+        }
+        _ => {
+            panic!("#[with...] attribute should take the form #[with(function_name)]")
+        }
+    }
+}
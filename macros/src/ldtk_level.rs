@@ -0,0 +1,158 @@
+use quote::quote;
+
+use crate::ldtk_entity::expand_ldtk_field_attribute;
+
+static LDTK_LEVEL_ATTRIBUTE_NAME: &str = "ldtk_level";
+static FROM_LEVEL_ATTRIBUTE_NAME: &str = "from_level";
+static LDTK_FIELD_ATTRIBUTE_NAME: &str = "ldtk_field";
+static WITH_ATTRIBUTE_NAME: &str = "with";
+
+pub fn expand_ldtk_level_derive(ast: syn::DeriveInput) -> proc_macro::TokenStream {
+    let struct_name = &ast.ident;
+
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => panic!("Expected a struct with named fields."),
+    };
+
+    let mut field_constructions = Vec::new();
+    for field in fields {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_type = &field.ty;
+
+        let ldtk_level = field
+            .attrs
+            .iter()
+            .find(|a| *a.path.get_ident().as_ref().unwrap() == LDTK_LEVEL_ATTRIBUTE_NAME);
+        if let Some(attribute) = ldtk_level {
+            field_constructions.push(expand_ldtk_level_attribute(
+                attribute, field_name, field_type,
+            ));
+            continue;
+        }
+
+        let from_level = field
+            .attrs
+            .iter()
+            .find(|a| *a.path.get_ident().as_ref().unwrap() == FROM_LEVEL_ATTRIBUTE_NAME);
+        if let Some(attribute) = from_level {
+            field_constructions.push(expand_from_level_attribute(
+                attribute, field_name, field_type,
+            ));
+            continue;
+        }
+
+        let ldtk_field = field
+            .attrs
+            .iter()
+            .find(|a| *a.path.get_ident().as_ref().unwrap() == LDTK_FIELD_ATTRIBUTE_NAME);
+        if let Some(attribute) = ldtk_field {
+            field_constructions.push(expand_ldtk_field_attribute(
+                attribute,
+                field_name,
+                field_type,
+                quote! { level },
+            ));
+            continue;
+        }
+
+        let with = field
+            .attrs
+            .iter()
+            .find(|a| *a.path.get_ident().as_ref().unwrap() == WITH_ATTRIBUTE_NAME);
+        if let Some(attribute) = with {
+            field_constructions.push(expand_with_attribute(attribute, field_name, field_type));
+            continue;
+        }
+    }
+
+    let generics = &ast.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let struct_update = if field_constructions.len() < fields.len() {
+        quote! { ..<Self as std::default::Default>::default() }
+    } else {
+        quote! {}
+    };
+
+    let gen = quote! {
+        impl #impl_generics bevy_ecs_ldtk::prelude::LdtkLevel for #struct_name #ty_generics #where_clause {
+            fn bundle_level(
+                level: &bevy_ecs_ldtk::ldtk::loaded_level::LoadedLevel,
+                asset_server: &bevy::prelude::AssetServer,
+            ) -> Self {
+                Self {
+                    #(#field_constructions)*
+                    #struct_update
+                }
+            }
+        }
+    };
+    gen.into()
+}
+
+fn expand_ldtk_level_attribute(
+    attribute: &syn::Attribute,
+    field_name: &syn::Ident,
+    field_type: &syn::Type,
+) -> proc_macro2::TokenStream {
+    match attribute
+        .parse_meta()
+        .expect("Cannot parse #[ldtk_level] attribute")
+    {
+        syn::Meta::Path(_) => {
+            quote! {
+                #field_name: <#field_type as bevy_ecs_ldtk::prelude::LdtkLevel>::bundle_level(level, asset_server),
+            }
+        }
+        _ => panic!("#[ldtk_level] attribute should take the form #[ldtk_level]"),
+    }
+}
+
+fn expand_from_level_attribute(
+    attribute: &syn::Attribute,
+    field_name: &syn::Ident,
+    field_type: &syn::Type,
+) -> proc_macro2::TokenStream {
+    match attribute
+        .parse_meta()
+        .expect("Cannot parse #[from_level] attribute")
+    {
+        syn::Meta::Path(_) => {
+            quote! {
+                #field_name: <#field_type as From<&bevy_ecs_ldtk::ldtk::loaded_level::LoadedLevel>>::from(level),
+            }
+        }
+        _ => {
+            panic!("#[from_level] attribute should take the form #[from_level]")
+        }
+    }
+}
+
+fn expand_with_attribute(
+    attribute: &syn::Attribute,
+    field_name: &syn::Ident,
+    _: &syn::Type,
+) -> proc_macro2::TokenStream {
+    match attribute
+        .parse_meta()
+        .expect("Cannot parse #[with...] attribute")
+    {
+        syn::Meta::List(syn::MetaList { nested, .. }) if nested.len() == 1 => {
+            match nested.first().unwrap() {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) => {
+                    quote! {
+                        #field_name: #path(level),
+                    }
+                }
+                _ => panic!("Expected function as the only argument of #[with(...)]"),
+            }
+        }
+        _ => {
+            panic!("#[with...] attribute should take the form #[with(function_name)]")
+        }
+    }
+}
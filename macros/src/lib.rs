@@ -1,7 +1,11 @@
 use proc_macro::TokenStream;
 
 mod ldtk_entity;
+mod ldtk_enums;
+mod ldtk_fields;
 mod ldtk_int_cell;
+mod ldtk_layer;
+mod ldtk_level;
 
 #[proc_macro_derive(
     LdtkEntity,
@@ -12,6 +16,7 @@ mod ldtk_int_cell;
         grid_coords,
         ldtk_entity,
         from_entity_instance,
+        ldtk_field,
         with
     )
 )]
@@ -21,9 +26,43 @@ pub fn ldtk_entity_derive(input: TokenStream) -> TokenStream {
     ldtk_entity::expand_ldtk_entity_derive(ast)
 }
 
-#[proc_macro_derive(LdtkIntCell, attributes(ldtk_int_cell, from_int_grid_cell, with))]
+#[proc_macro_derive(
+    LdtkIntCell,
+    attributes(ldtk_int_cell, from_int_grid_cell, from_int_grid_cell_context, with)
+)]
 pub fn ldtk_int_cell_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).unwrap();
 
     ldtk_int_cell::expand_ldtk_int_cell_derive(ast)
 }
+
+#[proc_macro_derive(
+    LdtkLayer,
+    attributes(ldtk_layer, from_layer_instance, from_layer_definition, with)
+)]
+pub fn ldtk_layer_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+
+    ldtk_layer::expand_ldtk_layer_derive(ast)
+}
+
+#[proc_macro_derive(LdtkLevel, attributes(ldtk_level, from_level, ldtk_field, with))]
+pub fn ldtk_level_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+
+    ldtk_level::expand_ldtk_level_derive(ast)
+}
+
+#[proc_macro_derive(LdtkFields, attributes(ldtk))]
+pub fn ldtk_fields_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+
+    ldtk_fields::expand_ldtk_fields_derive(ast)
+}
+
+#[proc_macro]
+pub fn ldtk_enums(input: TokenStream) -> TokenStream {
+    let path = syn::parse_macro_input!(input as syn::LitStr);
+
+    ldtk_enums::expand_ldtk_enums(path)
+}
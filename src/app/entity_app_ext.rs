@@ -1,17 +1,39 @@
 //! Provides [LdtkEntityAppExt] for registering bundles to spawn for given LDtk Entity identifiers.
-use crate::app::ldtk_entity::*;
+use crate::app::{ldtk_entity::*, ldtk_entity_system::*};
 use bevy::prelude::*;
 
 /// [Bundle]: bevy::prelude::Bundle
 /// [App]: bevy::prelude::App
 ///
 /// Provides functions to register [Bundle]s to bevy's [App] for particular LDtk layer and entity
-/// identifiers.
+/// identifiers, entity definition uids, or entity tags.
 ///
 /// After being registered, entities will be spawned with these [Bundle]s when some entity in LDtk
 /// meets the criteria you specify.
 ///
+/// ## Registration priority
+/// Each LDtk entity is spawned with the bundle of at most one of these registrations, picked in
+/// order of most to least specific:
+/// 1. [LdtkEntityAppExt::register_ldtk_entity_for_def_uid]
+/// 2. [LdtkEntityAppExt::register_ldtk_entity_for_layer]
+/// 3. [LdtkEntityAppExt::register_ldtk_entity]
+/// 4. [LdtkEntityAppExt::register_default_ldtk_entity_for_layer]
+/// 5. [LdtkEntityAppExt::register_default_ldtk_entity]
+///
+/// If none of them match, the entity is spawned with an [EntityInstance] component.
+///
+/// Additive registrations compose with these instead, and are inserted after the bundle above:
+/// 1. The bundles of every [LdtkEntityAppExt::add_ldtk_entity_bundle_for_layer_optional]
+///    registration matching the entity, in the order they were registered.
+/// 2. The bundles of every [LdtkEntityAppExt::register_ldtk_entity_for_tag] registration matching
+///    the entity, in the order of the entity's tags.
+///
+/// Since later bundles overwrite components of earlier ones, additive bundles should generally
+/// not share components with each other or with the main bundle.
+///
 /// Not intended for custom implementations on your own types.
+///
+/// [EntityInstance]: crate::ldtk::EntityInstance
 pub trait LdtkEntityAppExt {
     /// Used internally by all the other LDtk entity registration functions.
     ///
@@ -99,6 +121,286 @@ pub trait LdtkEntityAppExt {
     fn register_default_ldtk_entity<B: LdtkEntity + Bundle>(&mut self) -> &mut Self {
         self.register_ldtk_entity_for_layer_optional::<B>(None, None)
     }
+
+    /// Registers [LdtkEntity] types to be spawned for entities of the given entity definition
+    /// uid in an LDtk file.
+    ///
+    /// Unlike identifiers, uids don't change when an entity definition is renamed in LDtk.
+    /// This registration takes priority over all identifier-based registrations.
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_ldtk::prelude::*;
+    ///
+    /// fn main() {
+    ///     App::empty()
+    ///         .add_plugins(LdtkPlugin)
+    ///         .register_ldtk_entity_for_def_uid::<MyBundle>(42)
+    ///         // add other systems, plugins, resources...
+    ///         .run();
+    /// }
+    ///
+    /// # #[derive(Component, Default)]
+    /// # struct ComponentA;
+    /// #[derive(Bundle, LdtkEntity, Default)]
+    /// pub struct MyBundle {
+    ///     a: ComponentA,
+    /// }
+    /// ```
+    fn register_ldtk_entity_for_def_uid<B: LdtkEntity + Bundle>(
+        &mut self,
+        def_uid: i32,
+    ) -> &mut Self;
+
+    /// Registers [LdtkEntity] types to be inserted on entities with the given tag in an LDtk
+    /// file.
+    ///
+    /// Tag registrations compose with each other and with the other registrations: an entity
+    /// receives the bundles of all tag registrations matching any of its tags, in addition to its
+    /// main bundle.
+    /// Registering multiple bundles for the same tag inserts all of them, in the order they were
+    /// registered.
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_ldtk::prelude::*;
+    ///
+    /// fn main() {
+    ///     App::empty()
+    ///         .add_plugins(LdtkPlugin)
+    ///         .register_ldtk_entity::<GoblinBundle>("Goblin")
+    ///         .register_ldtk_entity_for_tag::<EnemyBundle>("Enemy")
+    ///         // add other systems, plugins, resources...
+    ///         .run();
+    /// }
+    ///
+    /// # #[derive(Component, Default)]
+    /// # struct Goblin;
+    /// # #[derive(Component, Default)]
+    /// # struct Enemy;
+    /// # #[derive(Component, Default)]
+    /// # struct Health;
+    /// #[derive(Bundle, LdtkEntity, Default)]
+    /// pub struct GoblinBundle {
+    ///     goblin: Goblin,
+    ///     #[sprite_sheet_bundle]
+    ///     sprite_sheet: SpriteSheetBundle,
+    /// }
+    ///
+    /// #[derive(Bundle, LdtkEntity, Default)]
+    /// pub struct EnemyBundle {
+    ///     enemy: Enemy,
+    ///     health: Health,
+    /// }
+    /// ```
+    fn register_ldtk_entity_for_tag<B: LdtkEntity + Bundle>(&mut self, tag: &str) -> &mut Self;
+
+    /// Used internally by all the other additive LDtk entity registration functions.
+    ///
+    /// Similar to [LdtkEntityAppExt::add_ldtk_entity_bundle_for_layer], except it provides the
+    /// same defaulting functionality as
+    /// [LdtkEntityAppExt::register_ldtk_entity_for_layer_optional].
+    /// Unlike that function, every additive registration matching an entity is used, regardless
+    /// of how specific it is.
+    fn add_ldtk_entity_bundle_for_layer_optional<B: LdtkEntity + Bundle>(
+        &mut self,
+        layer_identifier: Option<String>,
+        entity_identifier: Option<String>,
+    ) -> &mut Self;
+
+    /// Adds an [LdtkEntity] type to be inserted on entities with the given Entity identifier and
+    /// layer identifier in an LDtk file, alongside any other bundles registered for them.
+    ///
+    /// Unlike [LdtkEntityAppExt::register_ldtk_entity_for_layer], which picks a single bundle for
+    /// each entity, all matching additive registrations are inserted, in the order they were
+    /// added.
+    /// This allows separate plugins to attach their own bundles to the same entities.
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_ldtk::prelude::*;
+    ///
+    /// fn main() {
+    ///     App::empty()
+    ///         .add_plugins(LdtkPlugin)
+    ///         .add_plugins((AiPlugin, AudioPlugin))
+    ///         // add other systems, plugins, resources...
+    ///         .run();
+    /// }
+    ///
+    /// # #[derive(Component, Default)]
+    /// # struct Brain;
+    /// # #[derive(Component, Default)]
+    /// # struct Footsteps;
+    /// #[derive(Bundle, LdtkEntity, Default)]
+    /// pub struct AiBundle {
+    ///     brain: Brain,
+    /// }
+    ///
+    /// struct AiPlugin;
+    ///
+    /// impl Plugin for AiPlugin {
+    ///     fn build(&self, app: &mut App) {
+    ///         app.add_ldtk_entity_bundle_for_layer::<AiBundle>("Entities", "Enemy");
+    ///     }
+    /// }
+    ///
+    /// #[derive(Bundle, LdtkEntity, Default)]
+    /// pub struct AudioBundle {
+    ///     footsteps: Footsteps,
+    /// }
+    ///
+    /// struct AudioPlugin;
+    ///
+    /// impl Plugin for AudioPlugin {
+    ///     fn build(&self, app: &mut App) {
+    ///         app.add_ldtk_entity_bundle_for_layer::<AudioBundle>("Entities", "Enemy");
+    ///     }
+    /// }
+    /// ```
+    fn add_ldtk_entity_bundle_for_layer<B: LdtkEntity + Bundle>(
+        &mut self,
+        layer_identifier: &str,
+        entity_identifier: &str,
+    ) -> &mut Self {
+        self.add_ldtk_entity_bundle_for_layer_optional::<B>(
+            Some(layer_identifier.to_string()),
+            Some(entity_identifier.to_string()),
+        )
+    }
+
+    /// Similar to [LdtkEntityAppExt::add_ldtk_entity_bundle_for_layer], except it applies the
+    /// registration to all layers.
+    fn add_ldtk_entity_bundle<B: LdtkEntity + Bundle>(
+        &mut self,
+        entity_identifier: &str,
+    ) -> &mut Self {
+        self.add_ldtk_entity_bundle_for_layer_optional::<B>(
+            None,
+            Some(entity_identifier.to_string()),
+        )
+    }
+
+    /// Similar to [LdtkEntityAppExt::add_ldtk_entity_bundle_for_layer], except it applies the
+    /// registration to all entities on the given layer.
+    fn add_default_ldtk_entity_bundle_for_layer<B: LdtkEntity + Bundle>(
+        &mut self,
+        layer_identifier: &str,
+    ) -> &mut Self {
+        self.add_ldtk_entity_bundle_for_layer_optional::<B>(
+            Some(layer_identifier.to_string()),
+            None,
+        )
+    }
+
+    /// Similar to [LdtkEntityAppExt::add_ldtk_entity_bundle_for_layer], except it applies the
+    /// registration to any entity and any layer.
+    fn add_default_ldtk_entity_bundle<B: LdtkEntity + Bundle>(&mut self) -> &mut Self {
+        self.add_ldtk_entity_bundle_for_layer_optional::<B>(None, None)
+    }
+
+    /// Used internally by all the other LDtk entity system registration functions.
+    ///
+    /// Similar to [LdtkEntityAppExt::register_ldtk_entity_system_for_layer], except it provides
+    /// the same defaulting functionality as
+    /// [LdtkEntityAppExt::register_ldtk_entity_for_layer_optional].
+    /// Entity systems are matched separately from bundles, so an entity may use a bundle and
+    /// systems from registrations of different specificity.
+    /// Only the systems of the most specific matching registration are run.
+    fn register_ldtk_entity_system_for_layer_optional<M>(
+        &mut self,
+        layer_identifier: Option<String>,
+        entity_identifier: Option<String>,
+        system: impl IntoSystem<LdtkEntitySpawnContext, (), M> + 'static,
+    ) -> &mut Self;
+
+    /// Registers a system to run on every entity spawned for a given Entity identifier and layer
+    /// identifier in an LDtk file.
+    ///
+    /// The system receives an [LdtkEntitySpawnContext] describing the new entity, and can use any
+    /// other [SystemParam](bevy::ecs::system::SystemParam)s, like resources, queries, and
+    /// [Commands].
+    /// It runs while the plugin spawns levels, after the entity's bundle has been inserted, so the
+    /// entity is complete on the frame it spawns.
+    /// Multiple systems can be registered for the same identifiers, and they run in the order they
+    /// were registered.
+    ///
+    /// This is useful when creating an entity requires more than the arguments of
+    /// [LdtkEntity::bundle_entity].
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_ldtk::{app::LdtkEntitySpawnContext, prelude::*};
+    ///
+    /// fn main() {
+    ///     App::empty()
+    ///         .add_plugins(LdtkPlugin)
+    ///         .register_ldtk_entity_system_for_layer("Entities", "Player", setup_player)
+    ///         // add other systems, plugins, resources...
+    ///         .run();
+    /// }
+    ///
+    /// #[derive(Resource)]
+    /// struct PlayerSettings {
+    ///     speed: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Speed(f32);
+    ///
+    /// fn setup_player(
+    ///     In(context): In<LdtkEntitySpawnContext>,
+    ///     mut commands: Commands,
+    ///     settings: Res<PlayerSettings>,
+    /// ) {
+    ///     commands.entity(context.entity).insert(Speed(settings.speed));
+    /// }
+    /// ```
+    fn register_ldtk_entity_system_for_layer<M>(
+        &mut self,
+        layer_identifier: &str,
+        entity_identifier: &str,
+        system: impl IntoSystem<LdtkEntitySpawnContext, (), M> + 'static,
+    ) -> &mut Self {
+        self.register_ldtk_entity_system_for_layer_optional(
+            Some(layer_identifier.to_string()),
+            Some(entity_identifier.to_string()),
+            system,
+        )
+    }
+
+    /// Similar to [LdtkEntityAppExt::register_ldtk_entity_system_for_layer], except it applies
+    /// the registration to all layers.
+    fn register_ldtk_entity_system<M>(
+        &mut self,
+        entity_identifier: &str,
+        system: impl IntoSystem<LdtkEntitySpawnContext, (), M> + 'static,
+    ) -> &mut Self {
+        self.register_ldtk_entity_system_for_layer_optional(
+            None,
+            Some(entity_identifier.to_string()),
+            system,
+        )
+    }
+
+    /// Similar to [LdtkEntityAppExt::register_ldtk_entity_system_for_layer], except it applies
+    /// the registration to all entities on the given layer.
+    fn register_default_ldtk_entity_system_for_layer<M>(
+        &mut self,
+        layer_identifier: &str,
+        system: impl IntoSystem<LdtkEntitySpawnContext, (), M> + 'static,
+    ) -> &mut Self {
+        self.register_ldtk_entity_system_for_layer_optional(
+            Some(layer_identifier.to_string()),
+            None,
+            system,
+        )
+    }
+
+    /// Similar to [LdtkEntityAppExt::register_ldtk_entity_system_for_layer], except it applies
+    /// the registration to any entity and any layer.
+    fn register_default_ldtk_entity_system<M>(
+        &mut self,
+        system: impl IntoSystem<LdtkEntitySpawnContext, (), M> + 'static,
+    ) -> &mut Self {
+        self.register_ldtk_entity_system_for_layer_optional(None, None, system)
+    }
 }
 
 impl LdtkEntityAppExt for App {
@@ -108,17 +410,65 @@ impl LdtkEntityAppExt for App {
         entity_identifier: Option<String>,
     ) -> &mut Self {
         let new_entry = Box::new(PhantomLdtkEntity::<B>::new());
-        match self.world.get_non_send_resource_mut::<LdtkEntityMap>() {
-            Some(mut entries) => {
-                entries.insert((layer_identifier, entity_identifier), new_entry);
-            }
-            None => {
-                let mut bundle_map = LdtkEntityMap::new();
-                bundle_map.insert((layer_identifier, entity_identifier), new_entry);
-                self.world
-                    .insert_non_send_resource::<LdtkEntityMap>(bundle_map);
-            }
-        }
+        self.world
+            .get_resource_or_insert_with(LdtkEntityMap::default)
+            .identifiers
+            .insert((layer_identifier, entity_identifier), new_entry);
+        self
+    }
+
+    fn register_ldtk_entity_for_def_uid<B: LdtkEntity + Bundle>(
+        &mut self,
+        def_uid: i32,
+    ) -> &mut Self {
+        let new_entry = Box::new(PhantomLdtkEntity::<B>::new());
+        self.world
+            .get_resource_or_insert_with(LdtkEntityMap::default)
+            .def_uids
+            .insert(def_uid, new_entry);
+        self
+    }
+
+    fn add_ldtk_entity_bundle_for_layer_optional<B: LdtkEntity + Bundle>(
+        &mut self,
+        layer_identifier: Option<String>,
+        entity_identifier: Option<String>,
+    ) -> &mut Self {
+        let new_entry = AdditiveLdtkEntity {
+            layer_identifier,
+            entity_identifier,
+            ldtk_entity: Box::new(PhantomLdtkEntity::<B>::new()),
+        };
+        self.world
+            .get_resource_or_insert_with(LdtkEntityMap::default)
+            .additive
+            .push(new_entry);
+        self
+    }
+
+    fn register_ldtk_entity_for_tag<B: LdtkEntity + Bundle>(&mut self, tag: &str) -> &mut Self {
+        let new_entry = Box::new(PhantomLdtkEntity::<B>::new());
+        self.world
+            .get_resource_or_insert_with(LdtkEntityMap::default)
+            .tags
+            .entry(tag.to_string())
+            .or_default()
+            .push(new_entry);
+        self
+    }
+
+    fn register_ldtk_entity_system_for_layer_optional<M>(
+        &mut self,
+        layer_identifier: Option<String>,
+        entity_identifier: Option<String>,
+        system: impl IntoSystem<LdtkEntitySpawnContext, (), M> + 'static,
+    ) -> &mut Self {
+        let system_id = self.world.register_system(system);
+        self.world
+            .get_resource_or_insert_with(LdtkEntitySystemMap::default)
+            .entry((layer_identifier, entity_identifier))
+            .or_default()
+            .push(system_id);
         self
     }
 }
@@ -164,7 +514,7 @@ mod tests {
             .register_default_ldtk_entity_for_layer::<LdtkEntityBundle>("default_entity_for_layer")
             .register_default_ldtk_entity::<LdtkEntityBundle>();
 
-        let ldtk_entity_map = app.world.get_non_send_resource::<LdtkEntityMap>().unwrap();
+        let ldtk_entity_map = &app.world.resource::<LdtkEntityMap>().identifiers;
 
         assert!(ldtk_entity_map.contains_key(&(
             Some("layer".to_string()),
@@ -177,4 +527,115 @@ mod tests {
 
         assert!(ldtk_entity_map.contains_key(&(None, None)));
     }
+
+    #[test]
+    fn test_ldtk_entity_def_uid_and_tag_registrations() {
+        let mut app = App::new();
+        app.register_ldtk_entity_for_def_uid::<LdtkEntityBundle>(7)
+            .register_ldtk_entity_for_tag::<LdtkEntityBundle>("tag")
+            .register_ldtk_entity_for_tag::<LdtkEntityBundle>("tag")
+            .register_ldtk_entity::<LdtkEntityBundle>("entity");
+
+        let ldtk_entity_map = app.world.resource::<LdtkEntityMap>();
+
+        assert!(ldtk_entity_map.def_uids.contains_key(&7));
+        assert_eq!(ldtk_entity_map.tags.get("tag").map(Vec::len), Some(2));
+
+        let tagged = EntityInstance {
+            identifier: "other".to_string(),
+            def_uid: 3,
+            tags: vec!["tag".to_string(), "untagged".to_string()],
+            ..default()
+        };
+        assert!(ldtk_entity_map.get("layer", &tagged).is_none());
+        assert_eq!(ldtk_entity_map.get_tagged(&tagged).count(), 2);
+
+        let by_uid = EntityInstance {
+            identifier: "entity".to_string(),
+            def_uid: 7,
+            ..default()
+        };
+        assert!(std::ptr::addr_eq(
+            ldtk_entity_map.get("layer", &by_uid).unwrap(),
+            ldtk_entity_map.def_uids[&7].as_ref(),
+        ));
+        assert_eq!(ldtk_entity_map.get_tagged(&by_uid).count(), 0);
+    }
+
+    #[test]
+    fn test_additive_ldtk_entity_registrations() {
+        let mut app = App::new();
+        app.register_ldtk_entity::<LdtkEntityBundle>("entity")
+            .add_ldtk_entity_bundle_for_layer::<LdtkEntityBundle>("layer", "entity")
+            .add_ldtk_entity_bundle::<LdtkEntityBundle>("entity")
+            .add_ldtk_entity_bundle::<LdtkEntityBundle>("other_entity")
+            .add_default_ldtk_entity_bundle_for_layer::<LdtkEntityBundle>("other_layer")
+            .add_default_ldtk_entity_bundle::<LdtkEntityBundle>();
+
+        let ldtk_entity_map = app.world.resource::<LdtkEntityMap>();
+
+        assert_eq!(ldtk_entity_map.additive.len(), 5);
+        assert!(ldtk_entity_map
+            .identifiers
+            .contains_key(&(None, Some("entity".to_string()))));
+
+        let entity_instance = EntityInstance {
+            identifier: "entity".to_string(),
+            ..default()
+        };
+
+        let matching: Vec<_> = ldtk_entity_map
+            .get_additive("layer", &entity_instance)
+            .collect();
+        assert_eq!(matching.len(), 3);
+        for (matched, expected) in matching.into_iter().zip([0, 1, 4]) {
+            assert!(std::ptr::addr_eq(
+                matched,
+                ldtk_entity_map.additive[expected].ldtk_entity.as_ref()
+            ));
+        }
+
+        assert_eq!(
+            ldtk_entity_map
+                .get_additive("other_layer", &entity_instance)
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_ldtk_entity_system_registrations() {
+        fn system(_: In<LdtkEntitySpawnContext>) {}
+
+        let mut app = App::new();
+        app.register_ldtk_entity_system_for_layer("layer", "entity_for_layer", system)
+            .register_ldtk_entity_system("entity", system)
+            .register_ldtk_entity_system("entity", system)
+            .register_default_ldtk_entity_system_for_layer("default_entity_for_layer", system)
+            .register_default_ldtk_entity_system(system);
+
+        let ldtk_entity_system_map = app.world.resource::<LdtkEntitySystemMap>();
+
+        assert_eq!(
+            ldtk_entity_system_map
+                .get(&(
+                    Some("layer".to_string()),
+                    Some("entity_for_layer".to_string())
+                ))
+                .map(Vec::len),
+            Some(1)
+        );
+
+        assert_eq!(
+            ldtk_entity_system_map
+                .get(&(None, Some("entity".to_string())))
+                .map(Vec::len),
+            Some(2)
+        );
+
+        assert!(ldtk_entity_system_map
+            .contains_key(&(Some("default_entity_for_layer".to_string()), None)));
+
+        assert!(ldtk_entity_system_map.contains_key(&(None, None)));
+    }
 }
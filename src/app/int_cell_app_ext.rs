@@ -23,10 +23,14 @@ pub trait LdtkIntCellAppExt {
     /// This defaulting functionality means that a particular LDtk IntGrid tile may match multiple
     /// registrations.
     /// In these cases, registrations are prioritized in order of most to least specific:
-    /// 1. `layer_identifier` and `value` are specified
-    /// 2. Just `value` is specified
-    /// 3. Just `layer_identifier` is specified
-    /// 4. Neither `value` nor `layer_identifier` are specified
+    /// 1. `layer_identifier` and the value's identifier are registered with
+    ///    [LdtkIntCellAppExt::register_ldtk_int_cell_by_identifier]
+    /// 2. `layer_identifier` and `value` are specified
+    /// 3. `layer_identifier` and the value's group are registered with
+    ///    [LdtkIntCellAppExt::register_ldtk_int_cell_for_group]
+    /// 4. Just `value` is specified
+    /// 5. Just `layer_identifier` is specified
+    /// 6. Neither `value` nor `layer_identifier` are specified
     fn register_ldtk_int_cell_for_layer_optional<B: LdtkIntCell + Bundle>(
         &mut self,
         layer_identifier: Option<String>,
@@ -76,6 +80,47 @@ pub trait LdtkIntCellAppExt {
         )
     }
 
+    /// Registers [LdtkIntCell] types to be inserted for the IntGrid value with the given
+    /// identifier on a layer.
+    ///
+    /// Unlike [LdtkIntCellAppExt::register_ldtk_int_cell_for_layer], this registration keeps
+    /// working if the value is renumbered in the editor.
+    /// When a project is loaded, a warning is logged if it has no such layer or value.
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_ldtk::prelude::*;
+    ///
+    /// fn main() {
+    ///     App::empty()
+    ///         .add_plugins(LdtkPlugin)
+    ///         .register_ldtk_int_cell_by_identifier::<Lava>("Terrain", "lava")
+    ///         // add other systems, plugins, resources...
+    ///         .run();
+    /// }
+    ///
+    /// # #[derive(Component, Default)]
+    /// # struct Damage;
+    /// #[derive(Bundle, LdtkIntCell, Default)]
+    /// pub struct Lava {
+    ///     damage: Damage,
+    /// }
+    /// ```
+    fn register_ldtk_int_cell_by_identifier<B: LdtkIntCell + Bundle>(
+        &mut self,
+        layer_identifier: &str,
+        value_identifier: &str,
+    ) -> &mut Self;
+
+    /// Registers [LdtkIntCell] types to be inserted for every IntGrid value in the group with the
+    /// given identifier on a layer.
+    ///
+    /// When a project is loaded, a warning is logged if it has no such layer or group.
+    fn register_ldtk_int_cell_for_group<B: LdtkIntCell + Bundle>(
+        &mut self,
+        layer_identifier: &str,
+        group_identifier: &str,
+    ) -> &mut Self;
+
     /// Similar to [LdtkIntCellAppExt::register_ldtk_int_cell_for_layer], except it applies the
     /// registration to all layers.
     fn register_ldtk_int_cell<B: LdtkIntCell + Bundle>(&mut self, value: i32) -> &mut Self {
@@ -108,17 +153,42 @@ impl LdtkIntCellAppExt for App {
         value: Option<i32>,
     ) -> &mut Self {
         let new_entry = Box::new(PhantomLdtkIntCell::<B>::new());
-        match self.world.get_non_send_resource_mut::<LdtkIntCellMap>() {
-            Some(mut entries) => {
-                entries.insert((layer_identifier, value), new_entry);
-            }
-            None => {
-                let mut bundle_map = LdtkIntCellMap::new();
-                bundle_map.insert((layer_identifier, value), new_entry);
-                self.world
-                    .insert_non_send_resource::<LdtkIntCellMap>(bundle_map);
-            }
-        }
+        self.world
+            .get_resource_or_insert_with(LdtkIntCellMap::default)
+            .values
+            .insert((layer_identifier, value), new_entry);
+        self
+    }
+
+    fn register_ldtk_int_cell_by_identifier<B: LdtkIntCell + Bundle>(
+        &mut self,
+        layer_identifier: &str,
+        value_identifier: &str,
+    ) -> &mut Self {
+        let new_entry = Box::new(PhantomLdtkIntCell::<B>::new());
+        self.world
+            .get_resource_or_insert_with(LdtkIntCellMap::default)
+            .value_identifiers
+            .insert(
+                (layer_identifier.to_string(), value_identifier.to_string()),
+                new_entry,
+            );
+        self
+    }
+
+    fn register_ldtk_int_cell_for_group<B: LdtkIntCell + Bundle>(
+        &mut self,
+        layer_identifier: &str,
+        group_identifier: &str,
+    ) -> &mut Self {
+        let new_entry = Box::new(PhantomLdtkIntCell::<B>::new());
+        self.world
+            .get_resource_or_insert_with(LdtkIntCellMap::default)
+            .groups
+            .insert(
+                (layer_identifier.to_string(), group_identifier.to_string()),
+                new_entry,
+            );
         self
     }
 }
@@ -126,7 +196,13 @@ impl LdtkIntCellAppExt for App {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{components::IntGridCell, ldtk::LayerInstance};
+    use crate::{
+        components::IntGridCell,
+        ldtk::{
+            Definitions, IntGridValueDefinition, IntGridValueGroupDefinition, LayerDefinition,
+            LayerInstance,
+        },
+    };
 
     #[derive(Default, Component, Debug)]
     struct ComponentA;
@@ -141,7 +217,11 @@ mod tests {
     }
 
     impl LdtkIntCell for LdtkIntCellBundle {
-        fn bundle_int_cell(_: IntGridCell, _: &LayerInstance) -> LdtkIntCellBundle {
+        fn bundle_int_cell(
+            _: IntGridCell,
+            _: &LayerInstance,
+            _: &IntGridCellContext,
+        ) -> LdtkIntCellBundle {
             LdtkIntCellBundle::default()
         }
     }
@@ -156,7 +236,7 @@ mod tests {
             )
             .register_default_ldtk_int_cell::<LdtkIntCellBundle>();
 
-        let ldtk_int_cell_map = app.world.get_non_send_resource::<LdtkIntCellMap>().unwrap();
+        let ldtk_int_cell_map = &app.world.resource::<LdtkIntCellMap>().values;
 
         assert!(ldtk_int_cell_map.contains_key(&(Some("layer".to_string()), Some(1))));
 
@@ -168,4 +248,102 @@ mod tests {
 
         assert!(ldtk_int_cell_map.contains_key(&(None, None)));
     }
+
+    #[test]
+    fn test_ldtk_int_cell_identifier_and_group_registrations() {
+        let mut app = App::new();
+        app.register_ldtk_int_cell_by_identifier::<LdtkIntCellBundle>("Terrain", "lava")
+            .register_ldtk_int_cell_for_group::<LdtkIntCellBundle>("Terrain", "Hazards")
+            .register_ldtk_int_cell_for_layer::<LdtkIntCellBundle>("Terrain", 1)
+            .register_ldtk_int_cell_for_layer::<LdtkIntCellBundle>("Terrain", 2)
+            .register_ldtk_int_cell_by_identifier::<LdtkIntCellBundle>("Terrain", "ice")
+            .register_ldtk_int_cell_for_group::<LdtkIntCellBundle>("Missing", "Hazards");
+
+        let ldtk_int_cell_map = app.world.resource::<LdtkIntCellMap>();
+
+        let layer_definition = LayerDefinition {
+            identifier: "Terrain".to_string(),
+            int_grid_values: vec![
+                IntGridValueDefinition {
+                    value: 1,
+                    identifier: Some("lava".to_string()),
+                    group_uid: 1,
+                    ..default()
+                },
+                IntGridValueDefinition {
+                    value: 2,
+                    identifier: Some("acid".to_string()),
+                    group_uid: 1,
+                    ..default()
+                },
+                IntGridValueDefinition {
+                    value: 3,
+                    identifier: Some("spikes".to_string()),
+                    group_uid: 1,
+                    ..default()
+                },
+            ],
+            int_grid_values_groups: vec![IntGridValueGroupDefinition {
+                uid: 1,
+                identifier: Some("Hazards".to_string()),
+                ..default()
+            }],
+            ..default()
+        };
+        let registrations = ldtk_int_cell_map.for_layer(&layer_definition);
+
+        let by_identifier =
+            &ldtk_int_cell_map.value_identifiers[&("Terrain".to_string(), "lava".to_string())];
+        let by_value = &ldtk_int_cell_map.values[&(Some("Terrain".to_string()), Some(2))];
+        let by_group = &ldtk_int_cell_map.groups[&("Terrain".to_string(), "Hazards".to_string())];
+
+        assert!(std::ptr::addr_eq(
+            registrations.get(1).unwrap(),
+            by_identifier.as_ref(),
+        ));
+        assert!(std::ptr::addr_eq(
+            registrations.get(2).unwrap(),
+            by_value.as_ref(),
+        ));
+        assert!(std::ptr::addr_eq(
+            registrations.get(3).unwrap(),
+            by_group.as_ref(),
+        ));
+        assert!(registrations.get(4).is_none());
+
+        let other_layer = LayerDefinition {
+            identifier: "Other".to_string(),
+            ..layer_definition.clone()
+        };
+        assert!(ldtk_int_cell_map.for_layer(&other_layer).get(3).is_none());
+
+        // values without a specific registration fall back to the layer's default
+        app.register_default_ldtk_int_cell_for_layer::<LdtkIntCellBundle>("Terrain");
+        let ldtk_int_cell_map = app.world.resource::<LdtkIntCellMap>();
+        let by_default = &ldtk_int_cell_map.values[&(Some("Terrain".to_string()), None)];
+        assert!(std::ptr::addr_eq(
+            ldtk_int_cell_map
+                .for_layer(&layer_definition)
+                .get(4)
+                .unwrap(),
+            by_default.as_ref(),
+        ));
+
+        let defs = Definitions {
+            layers: vec![layer_definition.clone()],
+            ..default()
+        };
+        assert_eq!(
+            ldtk_int_cell_map.unresolved_registrations(&defs),
+            vec![
+                UnresolvedIntCellRegistration::UnknownLayer {
+                    layer_identifier: "Missing".to_string(),
+                },
+                UnresolvedIntCellRegistration::UnknownValueIdentifier {
+                    layer_identifier: "Terrain".to_string(),
+                    value_identifier: "ice".to_string(),
+                },
+            ]
+        );
+    }
 }
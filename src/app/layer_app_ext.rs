@@ -0,0 +1,146 @@
+//! Provides [LdtkLayerAppExt] for registering bundles to insert on layer entities.
+use crate::app::ldtk_layer::*;
+use bevy::prelude::*;
+
+/// [Bundle]: bevy::prelude::Bundle
+/// [App]: bevy::prelude::App
+///
+/// Provides functions to register [Bundle]s to bevy's [App] for particular LDtk layer
+/// identifiers.
+///
+/// After being registered, layer entities will be spawned with these [Bundle]s when their layer
+/// meets the criteria you specify.
+///
+/// Not intended for custom implementations on your own types.
+pub trait LdtkLayerAppExt {
+    /// Used internally by all the other LDtk layer registration functions.
+    ///
+    /// Similar to [LdtkLayerAppExt::register_ldtk_layer], except setting `layer_identifier` to
+    /// [None] will make the registration apply to any layer.
+    ///
+    /// A layer matching both kinds of registration only gets the one for its identifier.
+    /// Registering twice for the same identifier replaces the first registration.
+    fn register_ldtk_layer_optional<B: LdtkLayer + Bundle>(
+        &mut self,
+        layer_identifier: Option<String>,
+    ) -> &mut Self;
+
+    /// Registers [LdtkLayer] types to be inserted on layers with the given identifier in an LDtk
+    /// file.
+    ///
+    /// This example lets the plugin know that it should insert a Foreground bundle on every
+    /// "Foreground" layer.
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_ldtk::prelude::*;
+    ///
+    /// fn main() {
+    ///     App::empty()
+    ///         .add_plugins(LdtkPlugin)
+    ///         .register_ldtk_layer::<Foreground>("Foreground")
+    ///         // add other systems, plugins, resources...
+    ///         .run();
+    /// }
+    ///
+    /// # #[derive(Component, Default)]
+    /// # struct Occluder;
+    /// # #[derive(Component, Default)]
+    /// # struct Collider;
+    /// #[derive(Bundle, LdtkLayer, Default)]
+    /// pub struct Foreground {
+    ///     occluder: Occluder,
+    ///     collider: Collider,
+    /// }
+    /// ```
+    ///
+    /// You can find more details on the `#[derive(LdtkLayer)]` macro at [LdtkLayer].
+    fn register_ldtk_layer<B: LdtkLayer + Bundle>(&mut self, layer_identifier: &str) -> &mut Self {
+        self.register_ldtk_layer_optional::<B>(Some(layer_identifier.to_string()))
+    }
+
+    /// Similar to [LdtkLayerAppExt::register_ldtk_layer], except it applies the registration to
+    /// all layers.
+    fn register_default_ldtk_layer<B: LdtkLayer + Bundle>(&mut self) -> &mut Self {
+        self.register_ldtk_layer_optional::<B>(None)
+    }
+}
+
+impl LdtkLayerAppExt for App {
+    fn register_ldtk_layer_optional<B: LdtkLayer + Bundle>(
+        &mut self,
+        layer_identifier: Option<String>,
+    ) -> &mut Self {
+        let new_entry = Box::new(PhantomLdtkLayer::<B>::new());
+        self.world
+            .get_resource_or_insert_with(LdtkLayerMap::default)
+            .insert(layer_identifier, new_entry);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldtk::{LayerDefinition, LayerInstance};
+
+    #[derive(Default, Component, Debug)]
+    struct ComponentA;
+
+    #[derive(Default, Bundle)]
+    struct LdtkLayerBundleA {
+        a: ComponentA,
+    }
+
+    impl LdtkLayer for LdtkLayerBundleA {
+        fn bundle_layer(
+            _: &LayerInstance,
+            _: &LayerDefinition,
+            _: &AssetServer,
+        ) -> LdtkLayerBundleA {
+            LdtkLayerBundleA::default()
+        }
+    }
+
+    #[derive(Default, Component, Debug)]
+    struct ComponentB;
+
+    #[derive(Default, Bundle)]
+    struct LdtkLayerBundleB {
+        b: ComponentB,
+    }
+
+    impl LdtkLayer for LdtkLayerBundleB {
+        fn bundle_layer(
+            _: &LayerInstance,
+            _: &LayerDefinition,
+            _: &AssetServer,
+        ) -> LdtkLayerBundleB {
+            LdtkLayerBundleB::default()
+        }
+    }
+
+    #[test]
+    fn test_ldtk_layer_registrations() {
+        let mut app = App::new();
+        app.register_ldtk_layer::<LdtkLayerBundleA>("Foreground")
+            .register_default_ldtk_layer::<LdtkLayerBundleB>();
+
+        let ldtk_layer_map = app.world.resource::<LdtkLayerMap>();
+
+        let for_identifier = ldtk_layer_map.get(&Some("Foreground".to_string())).unwrap();
+        let for_any = ldtk_layer_map.get(&None).unwrap();
+
+        assert!(std::ptr::addr_eq(
+            ldtk_layer_map.get_for_layer("Foreground").unwrap(),
+            for_identifier.as_ref(),
+        ));
+        assert!(std::ptr::addr_eq(
+            ldtk_layer_map.get_for_layer("Background").unwrap(),
+            for_any.as_ref(),
+        ));
+
+        assert!(LdtkLayerMap::default()
+            .get_for_layer("Foreground")
+            .is_none());
+    }
+}
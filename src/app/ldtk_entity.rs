@@ -248,6 +248,55 @@ use std::{collections::HashMap, marker::PhantomData};
 /// }
 /// ```
 ///
+/// ### `#[ldtk_field...]`
+/// Indicates that a component should be created from the value of one of the entity's fields.
+/// There are two forms for this attribute:
+/// - `#[ldtk_field("FieldIdentifier")]` will use the field with the given identifier.
+/// - `#[ldtk_field]` will use the field with the same name as the struct field.
+///
+/// The component type needs to implement one of the following conversions.
+/// If it implements more than one, the first in this list is used:
+/// - `TryFrom<&FieldValue>`, which is implemented by enums generated with `ldtk_enums!`.
+/// - `From<i32>`, for Int fields.
+/// - `From<f32>`, for Float fields.
+/// - `From<bool>`, for Bool fields.
+/// - `From<String>`, for String, Multilines, FilePath and Enum fields.
+/// - `From<IVec2>`, for Point fields.
+///
+/// If the field is missing, null, or has the wrong type, a warning is logged and the component
+/// is created with its [Default] implementation instead.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// #[derive(Component, Default)]
+/// struct Health(i32);
+///
+/// impl From<i32> for Health {
+///     fn from(value: i32) -> Self {
+///         Health(value)
+///     }
+/// }
+///
+/// #[derive(Component, Default)]
+/// struct Name(String);
+///
+/// impl From<String> for Name {
+///     fn from(value: String) -> Self {
+///         Name(value)
+///     }
+/// }
+///
+/// #[derive(Bundle, LdtkEntity, Default)]
+/// pub struct EnemyBundle {
+///     #[ldtk_field("HP")]
+///     health: Health,
+///     #[ldtk_field]
+///     name: Name,
+///     #[sprite_sheet_bundle]
+///     sprite_sheet: SpriteSheetBundle,
+/// }
+/// ```
+///
 /// ### `#[with(...)]`
 ///
 /// Indicates that this component or bundle should be initialized with the given
@@ -390,7 +439,7 @@ impl<B: LdtkEntity + Bundle> PhantomLdtkEntity<B> {
     }
 }
 
-pub trait PhantomLdtkEntityTrait {
+pub trait PhantomLdtkEntityTrait: Send + Sync {
     #[allow(clippy::too_many_arguments)]
     fn evaluate<'a, 'b>(
         &self,
@@ -426,5 +475,89 @@ impl<B: LdtkEntity + Bundle> PhantomLdtkEntityTrait for PhantomLdtkEntity<B> {
     }
 }
 
-/// Used by [LdtkEntityAppExt](super::LdtkEntityAppExt) to associate Ldtk entity identifiers with [LdtkEntity]s.
-pub type LdtkEntityMap = HashMap<(Option<String>, Option<String>), Box<dyn PhantomLdtkEntityTrait>>;
+/// Used by [LdtkEntityAppExt](super::LdtkEntityAppExt) to associate Ldtk entities with
+/// [LdtkEntity]s.
+///
+/// See [LdtkEntityAppExt](super::LdtkEntityAppExt#registration-priority) for how registrations
+/// are matched to entities.
+#[derive(Default, Resource)]
+pub struct LdtkEntityMap {
+    /// Registrations by optional layer identifier and optional entity identifier.
+    pub identifiers: HashMap<(Option<String>, Option<String>), Box<dyn PhantomLdtkEntityTrait>>,
+    /// Registrations by entity definition uid.
+    pub def_uids: HashMap<i32, Box<dyn PhantomLdtkEntityTrait>>,
+    /// Registrations by entity tag, in the order they were registered.
+    pub tags: HashMap<String, Vec<Box<dyn PhantomLdtkEntityTrait>>>,
+    /// Additive registrations by optional layer identifier and optional entity identifier, in the
+    /// order they were registered.
+    pub additive: Vec<AdditiveLdtkEntity>,
+}
+
+/// An additive registration made with
+/// [LdtkEntityAppExt::add_ldtk_entity_bundle_for_layer_optional](super::LdtkEntityAppExt::add_ldtk_entity_bundle_for_layer_optional).
+pub struct AdditiveLdtkEntity {
+    /// The layer identifier to match, or [None] to match any layer.
+    pub layer_identifier: Option<String>,
+    /// The entity identifier to match, or [None] to match any entity.
+    pub entity_identifier: Option<String>,
+    /// The registered [LdtkEntity].
+    pub ldtk_entity: Box<dyn PhantomLdtkEntityTrait>,
+}
+
+impl AdditiveLdtkEntity {
+    /// Returns true if this registration applies to the given entity.
+    pub fn matches(&self, layer_identifier: &str, entity_instance: &EntityInstance) -> bool {
+        self.layer_identifier
+            .iter()
+            .all(|identifier| identifier == layer_identifier)
+            && self
+                .entity_identifier
+                .iter()
+                .all(|identifier| *identifier == entity_instance.identifier)
+    }
+}
+
+impl LdtkEntityMap {
+    /// Returns the most specific definition uid or identifier registration for the entity, if any.
+    pub fn get(
+        &self,
+        layer_identifier: &str,
+        entity_instance: &EntityInstance,
+    ) -> Option<&dyn PhantomLdtkEntityTrait> {
+        self.def_uids
+            .get(&entity_instance.def_uid)
+            .or_else(|| {
+                utils::try_each_optional_permutation(
+                    layer_identifier.to_string(),
+                    entity_instance.identifier.clone(),
+                    |x, y| self.identifiers.get(&(x, y)),
+                )
+            })
+            .map(AsRef::as_ref)
+    }
+
+    /// Returns every additive registration matching the entity, in the order they were registered.
+    pub fn get_additive<'a>(
+        &'a self,
+        layer_identifier: &'a str,
+        entity_instance: &'a EntityInstance,
+    ) -> impl Iterator<Item = &'a dyn PhantomLdtkEntityTrait> {
+        self.additive
+            .iter()
+            .filter(move |additive| additive.matches(layer_identifier, entity_instance))
+            .map(|additive| additive.ldtk_entity.as_ref())
+    }
+
+    /// Returns every tag registration for the entity, in the order of its tags.
+    pub fn get_tagged<'a>(
+        &'a self,
+        entity_instance: &'a EntityInstance,
+    ) -> impl Iterator<Item = &'a dyn PhantomLdtkEntityTrait> {
+        entity_instance
+            .tags
+            .iter()
+            .filter_map(|tag| self.tags.get(tag))
+            .flatten()
+            .map(AsRef::as_ref)
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{
+    components::{LayerMetadata, LevelIid},
+    ldtk::EntityInstance,
+};
+use bevy::{ecs::system::SystemId, prelude::*};
+use std::collections::HashMap;
+
+/// Input of systems registered with
+/// [LdtkEntityAppExt::register_ldtk_entity_system](super::LdtkEntityAppExt::register_ldtk_entity_system).
+///
+/// Describes an LDtk entity that has just been spawned.
+/// By the time the system runs, the entity already has its registered [LdtkEntity] bundle (or
+/// [EntityInstance]), its [SpatialBundle], and its place in the level's hierarchy.
+///
+/// [LdtkEntity]: super::LdtkEntity
+#[derive(Clone, Debug)]
+pub struct LdtkEntitySpawnContext {
+    /// The spawned entity.
+    pub entity: Entity,
+    /// The LDtk entity it was spawned from.
+    pub entity_instance: EntityInstance,
+    /// The tileset of the entity's editor visual, if it has one.
+    pub tileset: Option<Handle<Image>>,
+    /// The Entity layer the entity is a child of.
+    pub layer_entity: Entity,
+    /// The [LayerMetadata] of `layer_entity`.
+    pub layer_metadata: LayerMetadata,
+    /// The level the entity was spawned in.
+    pub level_entity: Entity,
+    /// The [LevelIid] of `level_entity`.
+    pub level_iid: LevelIid,
+}
+
+/// The id of a system registered with
+/// [LdtkEntityAppExt::register_ldtk_entity_system](super::LdtkEntityAppExt::register_ldtk_entity_system).
+pub type LdtkEntitySystemId = SystemId<LdtkEntitySpawnContext>;
+
+/// Used by [LdtkEntityAppExt](super::LdtkEntityAppExt) to associate Ldtk entity identifiers with
+/// systems that run on newly spawned entities.
+#[derive(Default, Resource, Deref, DerefMut)]
+pub struct LdtkEntitySystemMap(
+    pub HashMap<(Option<String>, Option<String>), Vec<LdtkEntitySystemId>>,
+);
@@ -0,0 +1,196 @@
+//! Conversions used by the `#[ldtk_field(...)]` attribute of `#[derive(LdtkEntity)]` and
+//! `#[derive(LdtkLevel)]`.
+//!
+//! The attribute accepts field types with any of several conversions from a [FieldValue].
+//! To pick one without knowing the type in the macro, each conversion is implemented for
+//! [LdtkFieldConversion] behind a different number of references, and the macro calls
+//! `convert_ldtk_field` on the most-referenced form.
+//! Method resolution then picks the conversion with the most references that the type supports,
+//! which is the order they are listed in the [LdtkEntity](super::LdtkEntity) docs.
+use crate::ldtk::{
+    ldtk_fields::{FromLdtkField, LdtkFields},
+    loaded_level::LoadedLevel,
+    EntityInstance, FieldValue,
+};
+use bevy::{log::warn, prelude::IVec2};
+use std::{fmt::Debug, marker::PhantomData};
+
+/// Selects a conversion into `T` for a field with `#[ldtk_field(...)]`.
+pub struct LdtkFieldConversion<T>(pub PhantomData<T>);
+
+pub trait ViaTryFromFieldValue<T> {
+    fn convert_ldtk_field(&self, identifier: &str, value: &FieldValue) -> Result<T, String>;
+}
+
+impl<T> ViaTryFromFieldValue<T> for &&&&&LdtkFieldConversion<T>
+where
+    T: for<'a> TryFrom<&'a FieldValue>,
+    for<'a> <T as TryFrom<&'a FieldValue>>::Error: Debug,
+{
+    fn convert_ldtk_field(&self, _: &str, value: &FieldValue) -> Result<T, String> {
+        T::try_from(value).map_err(|e| format!("{e:?}"))
+    }
+}
+
+/// Implements one `From<$source>` conversion level for [LdtkFieldConversion].
+macro_rules! impl_via_from {
+    ($trait_name:ident, $source:ty, $conversion:ty) => {
+        pub trait $trait_name<T> {
+            fn convert_ldtk_field(&self, identifier: &str, value: &FieldValue)
+                -> Result<T, String>;
+        }
+
+        impl<T: From<$source>> $trait_name<T> for $conversion {
+            fn convert_ldtk_field(
+                &self,
+                identifier: &str,
+                value: &FieldValue,
+            ) -> Result<T, String> {
+                <$source>::from_ldtk_field(identifier, value)
+                    .map(T::from)
+                    .map_err(|e| e.to_string())
+            }
+        }
+    };
+}
+
+impl_via_from!(ViaFromI32, i32, &&&&LdtkFieldConversion<T>);
+impl_via_from!(ViaFromF32, f32, &&&LdtkFieldConversion<T>);
+impl_via_from!(ViaFromBool, bool, &&LdtkFieldConversion<T>);
+impl_via_from!(ViaFromString, String, &LdtkFieldConversion<T>);
+impl_via_from!(ViaFromIVec2, IVec2, LdtkFieldConversion<T>);
+
+/// An LDtk object whose fields can be read with `#[ldtk_field(...)]`.
+pub trait LdtkFieldSource: LdtkFields {
+    /// Describes the object in warnings.
+    fn describe(&self) -> String;
+}
+
+impl LdtkFieldSource for EntityInstance {
+    fn describe(&self) -> String {
+        format!("{} entity {}", self.identifier, self.iid)
+    }
+}
+
+impl LdtkFieldSource for LoadedLevel<'_> {
+    fn describe(&self) -> String {
+        format!("level {}", self.identifier())
+    }
+}
+
+/// Finds the field with the given identifier and converts it, warning and falling back to the
+/// [Default] value if that fails.
+pub fn ldtk_field_or_default<T: Default>(
+    source: &impl LdtkFieldSource,
+    identifier: &str,
+    convert: impl FnOnce(&FieldValue) -> Result<T, String>,
+) -> T {
+    let result = source
+        .get_field(identifier)
+        .map_err(|e| e.to_string())
+        .and_then(convert);
+
+    result.unwrap_or_else(|e| {
+        warn!(
+            "could not create {} from {}: {e}",
+            std::any::type_name::<T>(),
+            source.describe(),
+        );
+        T::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldtk::FieldInstance;
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Health(i32);
+
+    impl From<i32> for Health {
+        fn from(value: i32) -> Self {
+            Health(value)
+        }
+    }
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Damage(i32);
+
+    impl TryFrom<&FieldValue> for Damage {
+        type Error = ();
+
+        fn try_from(value: &FieldValue) -> Result<Self, Self::Error> {
+            match value {
+                FieldValue::Int(Some(value)) => Ok(Damage(*value * 2)),
+                _ => Err(()),
+            }
+        }
+    }
+
+    // Prefers TryFrom<&FieldValue> over From<i32>
+    #[derive(Debug, Default, PartialEq)]
+    struct Both(i32);
+
+    impl From<i32> for Both {
+        fn from(value: i32) -> Self {
+            Both(value)
+        }
+    }
+
+    impl TryFrom<&FieldValue> for Both {
+        type Error = ();
+
+        fn try_from(_: &FieldValue) -> Result<Self, Self::Error> {
+            Ok(Both(-1))
+        }
+    }
+
+    fn entity_instance() -> EntityInstance {
+        EntityInstance {
+            field_instances: vec![
+                FieldInstance {
+                    identifier: "Health".to_string(),
+                    value: FieldValue::Int(Some(5)),
+                    field_instance_type: "Int".to_string(),
+                    tile: None,
+                    def_uid: 0,
+                    real_editor_values: Vec::new(),
+                },
+                FieldInstance {
+                    identifier: "Name".to_string(),
+                    value: FieldValue::String(Some("Bob".to_string())),
+                    field_instance_type: "String".to_string(),
+                    tile: None,
+                    def_uid: 0,
+                    real_editor_values: Vec::new(),
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    macro_rules! convert {
+        ($type:ty, $identifier:literal) => {
+            ldtk_field_or_default(&entity_instance(), $identifier, |value| {
+                (&&&&&&LdtkFieldConversion::<$type>(PhantomData))
+                    .convert_ldtk_field($identifier, value)
+            })
+        };
+    }
+
+    #[test]
+    fn conversions_are_selected_by_type() {
+        assert_eq!(convert!(Health, "Health"), Health(5));
+        assert_eq!(convert!(Damage, "Health"), Damage(10));
+        assert_eq!(convert!(Both, "Health"), Both(-1));
+        assert_eq!(convert!(String, "Name"), "Bob".to_string());
+    }
+
+    #[test]
+    fn failed_conversions_use_default() {
+        assert_eq!(convert!(Health, "Name"), Health(0));
+        assert_eq!(convert!(Damage, "Name"), Damage(0));
+        assert_eq!(convert!(Health, "Missing"), Health(0));
+    }
+}
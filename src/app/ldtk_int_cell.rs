@@ -1,9 +1,14 @@
 use crate::{
-    components::{IntGridCell, IntGridCellBundle},
-    ldtk::LayerInstance,
+    components::{GridCoords, IntGridCell, IntGridCellBundle},
+    ldtk::{
+        Definitions, IntGridValueDefinition, IntGridValueGroupDefinition, LayerDefinition,
+        LayerInstance,
+    },
+    utils::grid_coords_to_ldtk_grid_coords,
 };
 use bevy::{ecs::system::EntityCommands, prelude::*};
 use std::{collections::HashMap, marker::PhantomData};
+use thiserror::Error;
 
 /// [LdtkIntCellAppExt]: super::LdtkIntCellAppExt
 /// [Bundle]: bevy::prelude::Bundle
@@ -126,6 +131,39 @@ use std::{collections::HashMap, marker::PhantomData};
 /// }
 /// ```
 ///
+/// ### `#[from_int_grid_cell_context]`
+/// Indicates that a component or bundle that implements `From<&IntGridCellContext>` should be
+/// created using that conversion.
+/// This gives the component access to the cell's coordinates, value definition, and neighbours.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// #[derive(Component, Default)]
+/// enum WallShape {
+///     #[default]
+///     Interior,
+///     Edge,
+/// }
+///
+/// impl From<&IntGridCellContext<'_>> for WallShape {
+///     fn from(context: &IntGridCellContext) -> WallShape {
+///         let value = context.int_grid_cell().value;
+///
+///         if context.neighbours().all(|(_, neighbour)| neighbour == value) {
+///             WallShape::Interior
+///         } else {
+///             WallShape::Edge
+///         }
+///     }
+/// }
+///
+/// #[derive(Bundle, LdtkIntCell, Default)]
+/// pub struct Wall {
+///     #[from_int_grid_cell_context]
+///     shape: WallShape,
+/// }
+/// ```
+///
 /// ### `#[with(...)]`
 ///
 /// Indicates that this component or bundle should be initialized with the given
@@ -168,15 +206,126 @@ pub trait LdtkIntCell {
     /// So, any custom implementations of these components within this trait will be overwritten.
     /// Furthermore, a [bevy_ecs_tilemap::tiles::TileBundle] will be inserted **before** this bundle, so
     /// be careful not to overwrite the components provided by that bundle.
-    fn bundle_int_cell(int_grid_cell: IntGridCell, layer_instance: &LayerInstance) -> Self;
+    ///
+    /// The [IntGridCellContext] describes the cell's position, value definition, and neighbours.
+    fn bundle_int_cell(
+        int_grid_cell: IntGridCell,
+        layer_instance: &LayerInstance,
+        context: &IntGridCellContext,
+    ) -> Self;
 }
 
 impl LdtkIntCell for IntGridCellBundle {
-    fn bundle_int_cell(int_grid_cell: IntGridCell, _: &LayerInstance) -> Self {
+    fn bundle_int_cell(
+        int_grid_cell: IntGridCell,
+        _: &LayerInstance,
+        _: &IntGridCellContext,
+    ) -> Self {
         IntGridCellBundle { int_grid_cell }
     }
 }
 
+/// Offsets of the 8 cells surrounding an IntGrid cell, starting above it and going clockwise.
+const NEIGHBOUR_OFFSETS: [IVec2; 8] = [
+    IVec2::new(0, 1),
+    IVec2::new(1, 1),
+    IVec2::new(1, 0),
+    IVec2::new(1, -1),
+    IVec2::new(0, -1),
+    IVec2::new(-1, -1),
+    IVec2::new(-1, 0),
+    IVec2::new(-1, 1),
+];
+
+/// Describes an IntGrid cell being spawned, passed to [LdtkIntCell::bundle_int_cell].
+///
+/// Like [GridCoords], offsets and coordinates used by this type are y-up.
+#[derive(Copy, Clone, Debug)]
+pub struct IntGridCellContext<'a> {
+    /// The coordinates of the cell in its layer.
+    pub grid_coords: GridCoords,
+    /// The definition of the cell's value in the layer definition.
+    pub value_definition: Option<&'a IntGridValueDefinition>,
+    /// The group the cell's value belongs to, if it is grouped.
+    pub group: Option<&'a IntGridValueGroupDefinition>,
+    /// The layer the cell belongs to.
+    pub layer_instance: &'a LayerInstance,
+}
+
+impl<'a> IntGridCellContext<'a> {
+    /// Describes the cell at the given coordinates of an IntGrid layer.
+    pub fn new(
+        layer_instance: &'a LayerInstance,
+        layer_definition: &'a LayerDefinition,
+        grid_coords: GridCoords,
+    ) -> Self {
+        let mut context = IntGridCellContext {
+            grid_coords,
+            value_definition: None,
+            group: None,
+            layer_instance,
+        };
+
+        let value = context.value_at(grid_coords);
+
+        context.value_definition = layer_definition
+            .int_grid_values
+            .iter()
+            .find(|value_definition| Some(value_definition.value) == value);
+
+        context.group = context.value_definition.and_then(|value_definition| {
+            layer_definition
+                .int_grid_values_groups
+                .iter()
+                .find(|group| group.uid == value_definition.group_uid)
+        });
+
+        context
+    }
+
+    /// The [IntGridCell] of this cell.
+    pub fn int_grid_cell(&self) -> IntGridCell {
+        IntGridCell {
+            value: self.value_at(self.grid_coords).unwrap_or_default(),
+        }
+    }
+
+    /// Returns the IntGrid value at the given coordinates of this cell's layer, or [None] if they
+    /// are out of its bounds.
+    ///
+    /// Empty cells have a value of 0.
+    pub fn value_at(&self, grid_coords: GridCoords) -> Option<i32> {
+        let LayerInstance { c_wid, c_hei, .. } = *self.layer_instance;
+
+        if !(0..c_wid).contains(&grid_coords.x) || !(0..c_hei).contains(&grid_coords.y) {
+            return None;
+        }
+
+        let ldtk_grid_coords = grid_coords_to_ldtk_grid_coords(grid_coords, c_hei);
+        let index = ldtk_grid_coords.y * c_wid + ldtk_grid_coords.x;
+
+        self.layer_instance
+            .int_grid_csv
+            .get(index as usize)
+            .copied()
+    }
+
+    /// Returns the IntGrid value of the cell at the given offset from this one, or [None] if it
+    /// is out of the layer's bounds.
+    pub fn neighbour(&self, offset: IVec2) -> Option<i32> {
+        self.value_at(self.grid_coords + GridCoords::from(offset))
+    }
+
+    /// Iterates over the offsets and values of the (up to) 8 cells surrounding this one.
+    ///
+    /// Cells out of the layer's bounds are skipped.
+    pub fn neighbours(&self) -> impl Iterator<Item = (IVec2, i32)> + '_ {
+        NEIGHBOUR_OFFSETS
+            .into_iter()
+            .filter_map(|offset| Some((offset, self.neighbour(offset)?)))
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Hash)]
 pub struct PhantomLdtkIntCell<B: LdtkIntCell + Bundle> {
     ldtk_int_cell: PhantomData<B>,
@@ -190,12 +339,13 @@ impl<B: LdtkIntCell + Bundle> PhantomLdtkIntCell<B> {
     }
 }
 
-pub trait PhantomLdtkIntCellTrait {
+pub trait PhantomLdtkIntCellTrait: Send + Sync {
     fn evaluate<'a, 'b>(
         &self,
         entity_commands: &'b mut EntityCommands<'a>,
         int_grid_cell: IntGridCell,
         layer_instance: &LayerInstance,
+        context: &IntGridCellContext,
     ) -> &'b mut EntityCommands<'a>;
 }
 
@@ -205,10 +355,280 @@ impl<B: LdtkIntCell + Bundle> PhantomLdtkIntCellTrait for PhantomLdtkIntCell<B>
         entity_commands: &'b mut EntityCommands<'a>,
         int_grid_cell: IntGridCell,
         layer_instance: &LayerInstance,
+        context: &IntGridCellContext,
     ) -> &'b mut EntityCommands<'a> {
-        entity_commands.insert(B::bundle_int_cell(int_grid_cell, layer_instance))
+        entity_commands.insert(B::bundle_int_cell(int_grid_cell, layer_instance, context))
     }
 }
 
 /// Used by [LdtkIntCellAppExt](super::LdtkIntCellAppExt) to associate Ldtk IntGrid values with [LdtkIntCell]s.
-pub type LdtkIntCellMap = HashMap<(Option<String>, Option<i32>), Box<dyn PhantomLdtkIntCellTrait>>;
+///
+/// See [LdtkIntCellAppExt::register_ldtk_int_cell_for_layer_optional] for how a cell's
+/// registration is picked.
+///
+/// [LdtkIntCellAppExt::register_ldtk_int_cell_for_layer_optional]: super::LdtkIntCellAppExt::register_ldtk_int_cell_for_layer_optional
+#[derive(Default, Resource)]
+pub struct LdtkIntCellMap {
+    /// Registrations by layer identifier and IntGrid value.
+    pub values: HashMap<(Option<String>, Option<i32>), Box<dyn PhantomLdtkIntCellTrait>>,
+    /// Registrations by layer identifier and IntGrid value identifier.
+    pub value_identifiers: HashMap<(String, String), Box<dyn PhantomLdtkIntCellTrait>>,
+    /// Registrations by layer identifier and IntGrid value group identifier.
+    pub groups: HashMap<(String, String), Box<dyn PhantomLdtkIntCellTrait>>,
+}
+
+impl LdtkIntCellMap {
+    /// Resolves the most specific registration for every value of the given layer.
+    ///
+    /// Identifiers are only looked up here, so this is meant to be called once per layer, and the
+    /// result used for all of its cells.
+    pub fn for_layer(&self, layer_definition: &LayerDefinition) -> LayerIntCellRegistrations<'_> {
+        let layer_identifier = &layer_definition.identifier;
+
+        let default = self
+            .values
+            .get(&(Some(layer_identifier.clone()), None))
+            .or_else(|| self.values.get(&(None, None)))
+            .map(|ldtk_int_cell| ldtk_int_cell.as_ref());
+
+        let defined_values = layer_definition
+            .int_grid_values
+            .iter()
+            .map(|value_definition| value_definition.value);
+
+        let registered_values = self
+            .values
+            .keys()
+            .filter(|(layer, _)| layer.is_none() || layer.as_ref() == Some(layer_identifier))
+            .filter_map(|(_, value)| *value);
+
+        let values = defined_values
+            .chain(registered_values)
+            .filter_map(|value| {
+                let value_definition = layer_definition
+                    .int_grid_values
+                    .iter()
+                    .find(|value_definition| value_definition.value == value);
+
+                let group_identifier = value_definition.and_then(|value_definition| {
+                    layer_definition
+                        .int_grid_values_groups
+                        .iter()
+                        .find(|group| group.uid == value_definition.group_uid)?
+                        .identifier
+                        .clone()
+                });
+
+                value_definition
+                    .and_then(|value_definition| value_definition.identifier.clone())
+                    .and_then(|value_identifier| {
+                        self.value_identifiers
+                            .get(&(layer_identifier.clone(), value_identifier))
+                    })
+                    .or_else(|| {
+                        self.values
+                            .get(&(Some(layer_identifier.clone()), Some(value)))
+                    })
+                    .or_else(|| {
+                        group_identifier.and_then(|group_identifier| {
+                            self.groups
+                                .get(&(layer_identifier.clone(), group_identifier))
+                        })
+                    })
+                    .or_else(|| self.values.get(&(None, Some(value))))
+                    .map(|ldtk_int_cell| (value, ldtk_int_cell.as_ref()))
+            })
+            .collect();
+
+        LayerIntCellRegistrations { values, default }
+    }
+
+    /// Checks the registrations by value identifier and group identifier against the project's
+    /// definitions, returning those that can never match.
+    pub fn unresolved_registrations(
+        &self,
+        defs: &Definitions,
+    ) -> Vec<UnresolvedIntCellRegistration> {
+        let layer_definition = |layer_identifier: &String| {
+            defs.layers
+                .iter()
+                .find(|layer_definition| layer_definition.identifier == *layer_identifier)
+        };
+
+        let value_identifiers =
+            self.value_identifiers
+                .keys()
+                .filter_map(|(layer_identifier, value_identifier)| {
+                    match layer_definition(layer_identifier) {
+                        None => Some(UnresolvedIntCellRegistration::UnknownLayer {
+                            layer_identifier: layer_identifier.clone(),
+                        }),
+                        Some(layer_definition)
+                            if !layer_definition.int_grid_values.iter().any(
+                                |value_definition| {
+                                    value_definition.identifier.as_ref() == Some(value_identifier)
+                                },
+                            ) =>
+                        {
+                            Some(UnresolvedIntCellRegistration::UnknownValueIdentifier {
+                                layer_identifier: layer_identifier.clone(),
+                                value_identifier: value_identifier.clone(),
+                            })
+                        }
+                        _ => None,
+                    }
+                });
+
+        let groups =
+            self.groups
+                .keys()
+                .filter_map(|(layer_identifier, group_identifier)| {
+                    match layer_definition(layer_identifier) {
+                        None => Some(UnresolvedIntCellRegistration::UnknownLayer {
+                            layer_identifier: layer_identifier.clone(),
+                        }),
+                        Some(layer_definition)
+                            if !layer_definition.int_grid_values_groups.iter().any(|group| {
+                                group.identifier.as_ref() == Some(group_identifier)
+                            }) =>
+                        {
+                            Some(UnresolvedIntCellRegistration::UnknownGroup {
+                                layer_identifier: layer_identifier.clone(),
+                                group_identifier: group_identifier.clone(),
+                            })
+                        }
+                        _ => None,
+                    }
+                });
+
+        let mut unresolved: Vec<_> = value_identifiers.chain(groups).collect();
+        unresolved.sort();
+        unresolved.dedup();
+        unresolved
+    }
+}
+
+/// The registrations of [LdtkIntCellMap] for a single layer, by IntGrid value.
+///
+/// Created with [LdtkIntCellMap::for_layer].
+pub struct LayerIntCellRegistrations<'a> {
+    values: HashMap<i32, &'a dyn PhantomLdtkIntCellTrait>,
+    default: Option<&'a dyn PhantomLdtkIntCellTrait>,
+}
+
+impl<'a> LayerIntCellRegistrations<'a> {
+    /// Returns the most specific registration for cells with the given value.
+    pub fn get(&self, value: i32) -> Option<&'a dyn PhantomLdtkIntCellTrait> {
+        self.values.get(&value).copied().or(self.default)
+    }
+}
+
+/// A registration in [LdtkIntCellMap] whose names don't exist in a project's definitions.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Error)]
+pub enum UnresolvedIntCellRegistration {
+    #[error("there is no layer with the identifier {layer_identifier}")]
+    UnknownLayer { layer_identifier: String },
+    #[error(
+        "layer {layer_identifier} has no IntGrid value with the identifier {value_identifier}"
+    )]
+    UnknownValueIdentifier {
+        layer_identifier: String,
+        value_identifier: String,
+    },
+    #[error("layer {layer_identifier} has no IntGrid value group with the identifier {group_identifier}")]
+    UnknownGroup {
+        layer_identifier: String,
+        group_identifier: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer_definition() -> LayerDefinition {
+        LayerDefinition {
+            int_grid_values: vec![
+                IntGridValueDefinition {
+                    value: 1,
+                    identifier: Some("wall".to_string()),
+                    group_uid: 7,
+                    ..default()
+                },
+                IntGridValueDefinition {
+                    value: 2,
+                    identifier: Some("water".to_string()),
+                    group_uid: 0,
+                    ..default()
+                },
+            ],
+            int_grid_values_groups: vec![IntGridValueGroupDefinition {
+                uid: 7,
+                identifier: Some("solid".to_string()),
+                ..default()
+            }],
+            ..default()
+        }
+    }
+
+    fn layer_instance() -> LayerInstance {
+        // Rows are listed top to bottom, like int_grid_csv
+        LayerInstance {
+            c_wid: 3,
+            c_hei: 2,
+            int_grid_csv: vec![
+                1, 1, 0, //
+                2, 1, 1,
+            ],
+            ..default()
+        }
+    }
+
+    #[test]
+    fn context_has_value_definition_and_group() {
+        let layer_definition = layer_definition();
+        let layer_instance = layer_instance();
+
+        let wall =
+            IntGridCellContext::new(&layer_instance, &layer_definition, GridCoords::new(1, 0));
+        assert_eq!(wall.int_grid_cell(), IntGridCell { value: 1 });
+        assert_eq!(
+            wall.value_definition.unwrap().identifier,
+            Some("wall".to_string())
+        );
+        assert_eq!(wall.group.unwrap().identifier, Some("solid".to_string()));
+
+        let water =
+            IntGridCellContext::new(&layer_instance, &layer_definition, GridCoords::new(0, 0));
+        assert_eq!(water.value_definition.unwrap().value, 2);
+        assert!(water.group.is_none());
+
+        let empty =
+            IntGridCellContext::new(&layer_instance, &layer_definition, GridCoords::new(2, 1));
+        assert!(empty.value_definition.is_none());
+    }
+
+    #[test]
+    fn context_reads_neighbours() {
+        let layer_definition = layer_definition();
+        let layer_instance = layer_instance();
+
+        let context =
+            IntGridCellContext::new(&layer_instance, &layer_definition, GridCoords::new(0, 0));
+
+        assert_eq!(context.neighbour(IVec2::new(0, 1)), Some(1));
+        assert_eq!(context.neighbour(IVec2::new(1, 0)), Some(1));
+        assert_eq!(context.neighbour(IVec2::new(2, 1)), Some(0));
+        assert_eq!(context.neighbour(IVec2::new(-1, 0)), None);
+        assert_eq!(context.value_at(GridCoords::new(0, 2)), None);
+
+        assert_eq!(
+            context.neighbours().collect::<Vec<_>>(),
+            vec![
+                (IVec2::new(0, 1), 1),
+                (IVec2::new(1, 1), 1),
+                (IVec2::new(1, 0), 1),
+            ]
+        );
+    }
+}
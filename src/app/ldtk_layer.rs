@@ -0,0 +1,226 @@
+use crate::ldtk::{LayerDefinition, LayerInstance};
+use bevy::{ecs::system::EntityCommands, prelude::*};
+use std::{collections::HashMap, marker::PhantomData};
+
+/// [LdtkLayerAppExt]: super::LdtkLayerAppExt
+/// [Bundle]: bevy::prelude::Bundle
+/// [App]: bevy::prelude::App
+/// [Component]: bevy::prelude::Component
+/// [LayerMetadata]: crate::prelude::LayerMetadata
+///
+/// Provides a constructor which can be used for spawning additional components on layer entities.
+///
+/// This is where per-layer data, like parallax factors, render layers, collision groups, or custom
+/// materials, can be turned into components.
+/// After implementing this trait on a [Bundle], you can register it to be inserted on layers with
+/// a given identifier via [LdtkLayerAppExt] functions on your [App].
+/// It is inserted while the level is spawning, so the layer never exists without it.
+///
+/// For common use cases, you'll want to use derive-macro `#[derive(LdtkLayer)]`, but you can
+/// also provide a custom implementation.
+///
+/// You can also implement this trait on non-[Bundle] types, but only [Bundle]s can be registered.
+///
+/// If a layer does NOT match any registration, its entity only has the usual [LayerMetadata],
+/// [Name], and transform components, plus its tilemap if it has tiles.
+///
+/// *Derive macro requires the "derive" feature, which is enabled by default*
+///
+/// ## Derive macro usage
+/// Using `#[derive(LdtkLayer)]` on a [Bundle] struct will allow the type to be registered to the
+/// [App] via [LdtkLayerAppExt] functions:
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_ldtk::prelude::*;
+///
+/// fn main() {
+///     App::empty()
+///         .add_plugins(LdtkPlugin)
+///         .register_ldtk_layer::<MyLayerBundle>("Foreground")
+///         // add other systems, plugins, resources...
+///         .run();
+/// }
+///
+/// # #[derive(Component, Default)]
+/// # struct ComponentA;
+/// # #[derive(Component, Default)]
+/// # struct ComponentB;
+/// #[derive(Bundle, LdtkLayer, Default)]
+/// pub struct MyLayerBundle {
+///     a: ComponentA,
+///     b: ComponentB,
+/// }
+/// ```
+/// Now, when loading your ldtk file, every layer with the identifier "Foreground" will be spawned
+/// with `MyLayerBundle` inserted.
+///
+/// By default, each component or nested bundle in the bundle will be consumed from bundle's
+/// [Default] implementation, which means that deriving (or implementing manually) [Default]
+/// is required (unless all fields are overriden, see below).
+/// However, this behavior can be overridden with some field attribute macros...
+///
+/// ### `#[ldtk_layer]`
+/// Indicates that a component or bundle that implements [LdtkLayer] should be created with
+/// [LdtkLayer::bundle_layer], allowing for nested [LdtkLayer]s.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// # #[derive(Component, Default)]
+/// # struct Solid;
+/// # #[derive(Component, Default)]
+/// # struct Slippery;
+/// #[derive(Bundle, LdtkLayer, Default)]
+/// pub struct Collision {
+///     solid: Solid,
+/// }
+///
+/// #[derive(Bundle, LdtkLayer, Default)]
+/// pub struct Ice {
+///     #[ldtk_layer]
+///     collision: Collision,
+///     slippery: Slippery,
+/// }
+/// ```
+///
+/// ### `#[from_layer_instance]`
+/// Indicates that a component or bundle that implements `From<&LayerInstance>` should be created
+/// using that conversion.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// #[derive(Component, Default)]
+/// struct Opacity(f32);
+///
+/// impl From<&LayerInstance> for Opacity {
+///     fn from(layer_instance: &LayerInstance) -> Opacity {
+///         Opacity(layer_instance.opacity)
+///     }
+/// }
+///
+/// #[derive(Bundle, LdtkLayer, Default)]
+/// pub struct MyLayerBundle {
+///     #[from_layer_instance]
+///     opacity: Opacity,
+/// }
+/// ```
+///
+/// ### `#[from_layer_definition]`
+/// Indicates that a component or bundle that implements `From<&LayerDefinition>` should be
+/// created using that conversion.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::{prelude::*, ldtk::LayerDefinition};
+/// #[derive(Component, Default)]
+/// struct ParallaxFactor(Vec2);
+///
+/// impl From<&LayerDefinition> for ParallaxFactor {
+///     fn from(layer_definition: &LayerDefinition) -> ParallaxFactor {
+///         ParallaxFactor(Vec2::new(
+///             layer_definition.parallax_factor_x,
+///             layer_definition.parallax_factor_y,
+///         ))
+///     }
+/// }
+///
+/// #[derive(Bundle, LdtkLayer, Default)]
+/// pub struct MyLayerBundle {
+///     #[from_layer_definition]
+///     parallax_factor: ParallaxFactor,
+/// }
+/// ```
+///
+/// ### `#[with(...)]`
+/// Indicates that this component or bundle should be initialized with the given function.
+///
+/// Note: The given function should have signature
+/// `fn (layer_instance: &LayerInstance, layer_definition: &LayerDefinition) -> T`
+/// where `T` is the field type. The function should also be accessible in the scope.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::{prelude::*, ldtk::LayerDefinition};
+/// #[derive(Component, Default)]
+/// struct CollisionGroup(u32);
+///
+/// fn collision_group(_: &LayerInstance, layer_definition: &LayerDefinition) -> CollisionGroup {
+///     if layer_definition.ui_filter_tags.iter().any(|tag| tag == "solid") {
+///         CollisionGroup(1)
+///     } else {
+///         CollisionGroup(0)
+///     }
+/// }
+///
+/// #[derive(Bundle, LdtkLayer, Default)]
+/// pub struct MyLayerBundle {
+///     #[with(collision_group)]
+///     collision_group: CollisionGroup,
+/// }
+/// ```
+pub trait LdtkLayer {
+    /// The constructor used by the plugin when spawning layers.
+    ///
+    /// Note: the plugin inserts a layer's [SpatialBundle](bevy::prelude::SpatialBundle),
+    /// [LayerMetadata](crate::prelude::LayerMetadata), [Name], and tilemap **before** this
+    /// bundle, so any of those components in this bundle will overwrite the plugin's.
+    ///
+    /// Tile layers with overlapping tiles are spawned as several tilemap entities.
+    /// Each of them gets its own copy of this bundle.
+    fn bundle_layer(
+        layer_instance: &LayerInstance,
+        layer_definition: &LayerDefinition,
+        asset_server: &AssetServer,
+    ) -> Self;
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Hash)]
+pub struct PhantomLdtkLayer<B: LdtkLayer + Bundle> {
+    ldtk_layer: PhantomData<B>,
+}
+
+impl<B: LdtkLayer + Bundle> PhantomLdtkLayer<B> {
+    pub fn new() -> Self {
+        PhantomLdtkLayer::<B> {
+            ldtk_layer: PhantomData,
+        }
+    }
+}
+
+pub trait PhantomLdtkLayerTrait: Send + Sync {
+    fn evaluate<'a, 'b>(
+        &self,
+        entity_commands: &'b mut EntityCommands<'a>,
+        layer_instance: &LayerInstance,
+        layer_definition: &LayerDefinition,
+        asset_server: &AssetServer,
+    ) -> &'b mut EntityCommands<'a>;
+}
+
+impl<B: LdtkLayer + Bundle> PhantomLdtkLayerTrait for PhantomLdtkLayer<B> {
+    fn evaluate<'a, 'b>(
+        &self,
+        entity_commands: &'b mut EntityCommands<'a>,
+        layer_instance: &LayerInstance,
+        layer_definition: &LayerDefinition,
+        asset_server: &AssetServer,
+    ) -> &'b mut EntityCommands<'a> {
+        entity_commands.insert(B::bundle_layer(
+            layer_instance,
+            layer_definition,
+            asset_server,
+        ))
+    }
+}
+
+/// Used by [LdtkLayerAppExt](super::LdtkLayerAppExt) to associate Ldtk layer identifiers with
+/// [LdtkLayer]s.
+#[derive(Default, Resource, Deref, DerefMut)]
+pub struct LdtkLayerMap(pub HashMap<Option<String>, Box<dyn PhantomLdtkLayerTrait>>);
+
+impl LdtkLayerMap {
+    /// Returns the registration for the layer with the given identifier, falling back to the
+    /// registration for any layer.
+    pub fn get_for_layer(&self, layer_identifier: &str) -> Option<&dyn PhantomLdtkLayerTrait> {
+        self.get(&Some(layer_identifier.to_string()))
+            .or_else(|| self.get(&None))
+            .map(|ldtk_layer| ldtk_layer.as_ref())
+    }
+}
@@ -0,0 +1,219 @@
+use crate::ldtk::loaded_level::LoadedLevel;
+use bevy::{ecs::system::EntityCommands, prelude::*};
+use std::{collections::HashMap, marker::PhantomData};
+
+/// [LdtkLevelAppExt]: super::LdtkLevelAppExt
+/// [Bundle]: bevy::prelude::Bundle
+/// [App]: bevy::prelude::App
+/// [Component]: bevy::prelude::Component
+/// [LevelIid]: crate::prelude::LevelIid
+///
+/// Provides a constructor which can be used for spawning level-wide components on level entities.
+///
+/// This is where data that belongs to the whole level, like its music, gravity, or lighting, can
+/// be turned into components.
+/// After implementing this trait on a [Bundle], you can register it to be inserted on every
+/// level, or on levels with a given identifier, via [LdtkLevelAppExt] functions on your [App].
+///
+/// For common use cases, you'll want to use derive-macro `#[derive(LdtkLevel)]`, but you can
+/// also provide a custom implementation.
+///
+/// You can also implement this trait on non-[Bundle] types, but only [Bundle]s can be registered.
+///
+/// If a level does NOT match any registration, its entity only has the usual [LevelIid] and
+/// transform components.
+///
+/// *Derive macro requires the "derive" feature, which is enabled by default*
+///
+/// ## Derive macro usage
+/// Using `#[derive(LdtkLevel)]` on a [Bundle] struct will allow the type to be registered to the
+/// [App] via [LdtkLevelAppExt] functions:
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_ldtk::prelude::*;
+///
+/// fn main() {
+///     App::empty()
+///         .add_plugins(LdtkPlugin)
+///         .register_ldtk_level::<MyLevelBundle>()
+///         // add other systems, plugins, resources...
+///         .run();
+/// }
+///
+/// # #[derive(Component, Default)]
+/// # struct ComponentA;
+/// # #[derive(Component, Default)]
+/// # struct ComponentB;
+/// #[derive(Bundle, LdtkLevel, Default)]
+/// pub struct MyLevelBundle {
+///     a: ComponentA,
+///     b: ComponentB,
+/// }
+/// ```
+/// Now, when loading your ldtk file, every level entity will have `MyLevelBundle` inserted when
+/// the level starts spawning.
+///
+/// By default, each component or nested bundle in the bundle will be consumed from bundle's
+/// [Default] implementation, which means that deriving (or implementing manually) [Default]
+/// is required (unless all fields are overriden, see below).
+/// However, this behavior can be overridden with some field attribute macros...
+///
+/// ### `#[ldtk_level]`
+/// Indicates that a component or bundle that implements [LdtkLevel] should be created with
+/// [LdtkLevel::bundle_level], allowing for nested [LdtkLevel]s.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// # #[derive(Component, Default)]
+/// # struct Lighting;
+/// # #[derive(Component, Default)]
+/// # struct Rain;
+/// #[derive(Bundle, LdtkLevel, Default)]
+/// pub struct Outdoors {
+///     lighting: Lighting,
+/// }
+///
+/// #[derive(Bundle, LdtkLevel, Default)]
+/// pub struct Storm {
+///     #[ldtk_level]
+///     outdoors: Outdoors,
+///     rain: Rain,
+/// }
+/// ```
+///
+/// ### `#[from_level]`
+/// Indicates that a component or bundle that implements `From<&LoadedLevel>` should be created
+/// using that conversion.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::{prelude::*, ldtk::loaded_level::LoadedLevel};
+/// #[derive(Component, Default)]
+/// struct LevelSize(Vec2);
+///
+/// impl From<&LoadedLevel<'_>> for LevelSize {
+///     fn from(level: &LoadedLevel) -> LevelSize {
+///         LevelSize(Vec2::new(*level.px_wid() as f32, *level.px_hei() as f32))
+///     }
+/// }
+///
+/// #[derive(Bundle, LdtkLevel, Default)]
+/// pub struct MyLevelBundle {
+///     #[from_level]
+///     size: LevelSize,
+/// }
+/// ```
+///
+/// ### `#[ldtk_field...]`
+/// Indicates that a component should be created from one of the level's fields.
+/// It works the same as `#[ldtk_field...]` for
+/// [LdtkEntity](super::LdtkEntity)s, accepting the same field types.
+/// If the field is missing or can't be converted, a warning is logged and the component's
+/// [Default] is used.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// #[derive(Component, Default)]
+/// struct Gravity(f32);
+///
+/// impl From<f32> for Gravity {
+///     fn from(value: f32) -> Gravity {
+///         Gravity(value)
+///     }
+/// }
+///
+/// #[derive(Component, Default)]
+/// struct Music(String);
+///
+/// impl From<String> for Music {
+///     fn from(value: String) -> Music {
+///         Music(value)
+///     }
+/// }
+///
+/// #[derive(Bundle, LdtkLevel, Default)]
+/// pub struct MyLevelBundle {
+///     #[ldtk_field("Gravity")]
+///     gravity: Gravity,
+///     #[ldtk_field("Music")]
+///     music: Music,
+/// }
+/// ```
+///
+/// ### `#[with(...)]`
+/// Indicates that this component or bundle should be initialized with the given function.
+///
+/// Note: The given function should have signature `fn (level: &LoadedLevel) -> T`
+/// where `T` is the field type. The function should also be accessible in the scope.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::{prelude::*, ldtk::loaded_level::LoadedLevel};
+/// #[derive(Component, Default)]
+/// struct Lighting(Color);
+///
+/// fn lighting_from_background(level: &LoadedLevel) -> Lighting {
+///     Lighting(*level.bg_color())
+/// }
+///
+/// #[derive(Bundle, LdtkLevel, Default)]
+/// pub struct MyLevelBundle {
+///     #[with(lighting_from_background)]
+///     lighting: Lighting,
+/// }
+/// ```
+pub trait LdtkLevel {
+    /// The constructor used by the plugin when a level starts spawning.
+    ///
+    /// The bundle is inserted on the level entity before any of its layers are spawned.
+    /// If you need access to more of the [World](bevy::prelude::World), you can create a system
+    /// that reacts to [LevelEvent::Spawned](crate::prelude::LevelEvent::Spawned) instead of
+    /// implementing this trait.
+    fn bundle_level(level: &LoadedLevel, asset_server: &AssetServer) -> Self;
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Hash)]
+pub struct PhantomLdtkLevel<B: LdtkLevel + Bundle> {
+    ldtk_level: PhantomData<B>,
+}
+
+impl<B: LdtkLevel + Bundle> PhantomLdtkLevel<B> {
+    pub fn new() -> Self {
+        PhantomLdtkLevel::<B> {
+            ldtk_level: PhantomData,
+        }
+    }
+}
+
+pub trait PhantomLdtkLevelTrait: Send + Sync {
+    fn evaluate<'a, 'b>(
+        &self,
+        entity_commands: &'b mut EntityCommands<'a>,
+        level: &LoadedLevel,
+        asset_server: &AssetServer,
+    ) -> &'b mut EntityCommands<'a>;
+}
+
+impl<B: LdtkLevel + Bundle> PhantomLdtkLevelTrait for PhantomLdtkLevel<B> {
+    fn evaluate<'a, 'b>(
+        &self,
+        entity_commands: &'b mut EntityCommands<'a>,
+        level: &LoadedLevel,
+        asset_server: &AssetServer,
+    ) -> &'b mut EntityCommands<'a> {
+        entity_commands.insert(B::bundle_level(level, asset_server))
+    }
+}
+
+/// Used by [LdtkLevelAppExt](super::LdtkLevelAppExt) to associate Ldtk level identifiers with
+/// [LdtkLevel]s.
+#[derive(Default, Resource, Deref, DerefMut)]
+pub struct LdtkLevelMap(pub HashMap<Option<String>, Box<dyn PhantomLdtkLevelTrait>>);
+
+impl LdtkLevelMap {
+    /// Returns the registration for the level with the given identifier, falling back to the
+    /// registration for any level.
+    pub fn get_for_level(&self, level_identifier: &str) -> Option<&dyn PhantomLdtkLevelTrait> {
+        self.get(&Some(level_identifier.to_string()))
+            .or_else(|| self.get(&None))
+            .map(|ldtk_level| ldtk_level.as_ref())
+    }
+}
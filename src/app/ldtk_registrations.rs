@@ -0,0 +1,14 @@
+use crate::app::{LdtkEntityMap, LdtkEntitySystemMap, LdtkIntCellMap, LdtkLayerMap, LdtkLevelMap};
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// [SystemParam] giving access to everything registered with the `AppExt` traits of this module.
+///
+/// Used by the plugin when spawning levels.
+#[derive(SystemParam)]
+pub struct LdtkRegistrations<'w> {
+    pub entities: Res<'w, LdtkEntityMap>,
+    pub entity_systems: Res<'w, LdtkEntitySystemMap>,
+    pub int_cells: Res<'w, LdtkIntCellMap>,
+    pub layers: Res<'w, LdtkLayerMap>,
+    pub levels: Res<'w, LdtkLevelMap>,
+}
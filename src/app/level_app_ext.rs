@@ -0,0 +1,139 @@
+//! Provides [LdtkLevelAppExt] for registering bundles to insert on level entities.
+use crate::app::ldtk_level::*;
+use bevy::prelude::*;
+
+/// [Bundle]: bevy::prelude::Bundle
+/// [App]: bevy::prelude::App
+///
+/// Provides functions to register [Bundle]s to bevy's [App] for levels, either all of them or
+/// those with a particular identifier.
+///
+/// After being registered, level entities will have these [Bundle]s inserted when they start
+/// spawning.
+///
+/// Not intended for custom implementations on your own types.
+pub trait LdtkLevelAppExt {
+    /// Used internally by all the other LDtk level registration functions.
+    ///
+    /// Similar to [LdtkLevelAppExt::register_ldtk_level_for_identifier], except setting
+    /// `level_identifier` to [None] will make the registration apply to any level.
+    ///
+    /// A level matching both kinds of registration only gets the one for its identifier.
+    /// Registering twice for the same identifier replaces the first registration.
+    fn register_ldtk_level_for_identifier_optional<B: LdtkLevel + Bundle>(
+        &mut self,
+        level_identifier: Option<String>,
+    ) -> &mut Self;
+
+    /// Registers [LdtkLevel] types to be inserted on levels with the given identifier in an LDtk
+    /// file.
+    ///
+    /// This example lets the plugin know that it should insert a BossArena on the "Boss_room"
+    /// level.
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_ldtk::prelude::*;
+    ///
+    /// fn main() {
+    ///     App::empty()
+    ///         .add_plugins(LdtkPlugin)
+    ///         .register_ldtk_level_for_identifier::<BossArena>("Boss_room")
+    ///         // add other systems, plugins, resources...
+    ///         .run();
+    /// }
+    ///
+    /// # #[derive(Component, Default)]
+    /// # struct BossMusic;
+    /// # #[derive(Component, Default)]
+    /// # struct LockedDoors;
+    /// #[derive(Bundle, LdtkLevel, Default)]
+    /// pub struct BossArena {
+    ///     music: BossMusic,
+    ///     doors: LockedDoors,
+    /// }
+    /// ```
+    ///
+    /// You can find more details on the `#[derive(LdtkLevel)]` macro at [LdtkLevel].
+    fn register_ldtk_level_for_identifier<B: LdtkLevel + Bundle>(
+        &mut self,
+        level_identifier: &str,
+    ) -> &mut Self {
+        self.register_ldtk_level_for_identifier_optional::<B>(Some(level_identifier.to_string()))
+    }
+
+    /// Similar to [LdtkLevelAppExt::register_ldtk_level_for_identifier], except it applies the
+    /// registration to all levels.
+    fn register_ldtk_level<B: LdtkLevel + Bundle>(&mut self) -> &mut Self {
+        self.register_ldtk_level_for_identifier_optional::<B>(None)
+    }
+}
+
+impl LdtkLevelAppExt for App {
+    fn register_ldtk_level_for_identifier_optional<B: LdtkLevel + Bundle>(
+        &mut self,
+        level_identifier: Option<String>,
+    ) -> &mut Self {
+        let new_entry = Box::new(PhantomLdtkLevel::<B>::new());
+        self.world
+            .get_resource_or_insert_with(LdtkLevelMap::default)
+            .insert(level_identifier, new_entry);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldtk::loaded_level::LoadedLevel;
+
+    #[derive(Default, Component, Debug)]
+    struct ComponentA;
+
+    #[derive(Default, Bundle)]
+    struct LdtkLevelBundleA {
+        a: ComponentA,
+    }
+
+    impl LdtkLevel for LdtkLevelBundleA {
+        fn bundle_level(_: &LoadedLevel, _: &AssetServer) -> LdtkLevelBundleA {
+            LdtkLevelBundleA::default()
+        }
+    }
+
+    #[derive(Default, Component, Debug)]
+    struct ComponentB;
+
+    #[derive(Default, Bundle)]
+    struct LdtkLevelBundleB {
+        b: ComponentB,
+    }
+
+    impl LdtkLevel for LdtkLevelBundleB {
+        fn bundle_level(_: &LoadedLevel, _: &AssetServer) -> LdtkLevelBundleB {
+            LdtkLevelBundleB::default()
+        }
+    }
+
+    #[test]
+    fn test_ldtk_level_registrations() {
+        let mut app = App::new();
+        app.register_ldtk_level_for_identifier::<LdtkLevelBundleA>("Level_0")
+            .register_ldtk_level::<LdtkLevelBundleB>();
+
+        let ldtk_level_map = app.world.resource::<LdtkLevelMap>();
+
+        let for_identifier = ldtk_level_map.get(&Some("Level_0".to_string())).unwrap();
+        let for_any = ldtk_level_map.get(&None).unwrap();
+
+        assert!(std::ptr::addr_eq(
+            ldtk_level_map.get_for_level("Level_0").unwrap(),
+            for_identifier.as_ref(),
+        ));
+        assert!(std::ptr::addr_eq(
+            ldtk_level_map.get_for_level("Level_1").unwrap(),
+            for_any.as_ref(),
+        ));
+
+        assert!(LdtkLevelMap::default().get_for_level("Level_0").is_none());
+    }
+}
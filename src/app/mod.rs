@@ -2,10 +2,24 @@
 
 mod entity_app_ext;
 mod int_cell_app_ext;
+mod layer_app_ext;
 mod ldtk_entity;
+mod ldtk_entity_system;
+#[doc(hidden)]
+pub mod ldtk_field;
 mod ldtk_int_cell;
+mod ldtk_layer;
+mod ldtk_level;
+mod ldtk_registrations;
+mod level_app_ext;
 
 pub use entity_app_ext::*;
 pub use int_cell_app_ext::*;
+pub use layer_app_ext::*;
 pub use ldtk_entity::*;
+pub use ldtk_entity_system::*;
 pub use ldtk_int_cell::*;
+pub use ldtk_layer::*;
+pub use ldtk_level::*;
+pub use ldtk_registrations::*;
+pub use level_app_ext::*;
@@ -36,6 +36,14 @@ impl LdtkExternalLevel {
         LoadedLevel::try_from(&self.data)
             .expect("construction of LdtkExternalLevel should guarantee that the level is loaded.")
     }
+
+    /// Mutable access to the raw LDtk level data, for editing the level at runtime.
+    ///
+    /// The level's layer instances should not be set to `None`, otherwise [`LdtkExternalLevel::data`]
+    /// will panic.
+    pub fn data_mut(&mut self) -> &mut Level {
+        &mut self.data
+    }
 }
 
 /// Errors that can occur when loading an [`LdtkExternalLevel`] asset.
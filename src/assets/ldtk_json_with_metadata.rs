@@ -9,6 +9,9 @@ use bevy::reflect::{Reflect, TypePath};
 use derive_getters::Getters;
 use std::collections::HashMap;
 
+use crate::assets::LdtkProjectSaveError;
+use std::{fs, path::Path};
+
 #[cfg(feature = "internal_levels")]
 use crate::assets::InternalLevels;
 
@@ -64,6 +67,15 @@ where
             level_map,
         }
     }
+
+    /// Mutable access to the raw ldtk json data.
+    ///
+    /// See [`LdtkProject::json_data_mut`] for caveats.
+    ///
+    /// [`LdtkProject::json_data_mut`]: crate::assets::LdtkProject::json_data_mut
+    pub fn json_data_mut(&mut self) -> &mut LdtkJson {
+        &mut self.json_data
+    }
 }
 
 impl<L> RawLevelAccessor for LdtkJsonWithMetadata<L>
@@ -89,6 +101,15 @@ impl LevelMetadataAccessor for LdtkJsonWithMetadata<InternalLevels> {
 
 #[cfg(feature = "internal_levels")]
 impl LdtkJsonWithMetadata<InternalLevels> {
+    /// Write this project to the `.ldtk` file at the given path.
+    ///
+    /// Fields that LDtk derives from other data are updated first, see
+    /// [`LdtkJson::update_derived_fields`].
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), LdtkProjectSaveError> {
+        fs::write(path, self.json_data.to_ldtk_string()?)?;
+        Ok(())
+    }
+
     /// Iterate through this project's loaded levels.
     ///
     /// This first iterates through [root levels, then world levels](RawLevelAccessor#root-vs-world-levels).
@@ -145,6 +166,58 @@ impl LevelMetadataAccessor for LdtkJsonWithMetadata<ExternalLevels> {
 
 #[cfg(feature = "external_levels")]
 impl LdtkJsonWithMetadata<ExternalLevels> {
+    /// Write this project to the `.ldtk` file at the given path, and its levels to their `.ldtkl`
+    /// files relative to it.
+    ///
+    /// Fields that LDtk derives from other data are updated first, see
+    /// [`LdtkJson::update_derived_fields`].
+    ///
+    /// Every level's [`LdtkExternalLevel`] asset needs to be loaded.
+    pub fn save(
+        &self,
+        path: impl AsRef<Path>,
+        external_level_assets: &Assets<LdtkExternalLevel>,
+    ) -> Result<(), LdtkProjectSaveError> {
+        let path = path.as_ref();
+        let directory = path.parent().unwrap_or(Path::new(""));
+
+        let levels_with_layouts =
+            self.json_data
+                .levels
+                .iter()
+                .map(|level| (level, self.json_data.world_layout))
+                .chain(self.json_data.worlds.iter().flat_map(|world| {
+                    world.levels.iter().map(|level| (level, world.world_layout))
+                }));
+
+        // Serialize everything before writing anything, to avoid saving projects partially
+        let mut files = vec![(path.to_path_buf(), self.json_data.to_ldtk_string()?)];
+
+        for (level, world_layout) in levels_with_layouts {
+            let external_rel_path = level
+                .external_rel_path
+                .as_ref()
+                .ok_or(LdtkProjectSaveError::ExternalLevelWithNullPath)?;
+
+            let external_level = self
+                .get_external_level_by_iid(external_level_assets, &level.iid)
+                .ok_or_else(|| LdtkProjectSaveError::ExternalLevelNotLoaded(level.iid.clone()))?;
+
+            files.push((
+                directory.join(external_rel_path),
+                external_level
+                    .raw()
+                    .to_ldtkl_string(&self.json_data.defs, world_layout)?,
+            ));
+        }
+
+        for (path, contents) in files {
+            fs::write(path, contents)?;
+        }
+
+        Ok(())
+    }
+
     /// Iterate through this project's external levels.
     ///
     /// This first iterates through [root levels, then world levels](RawLevelAccessor#root-vs-world-levels).
@@ -745,5 +818,49 @@ pub mod tests {
                 None,
             );
         }
+
+        #[test]
+        fn save_writes_project_and_external_levels() {
+            let mut app = app_setup();
+            let mut project = fake_and_load_ldtk_json_with_metadata(&mut app);
+
+            for (i, level) in project.json_data.levels.iter_mut().enumerate() {
+                level.external_rel_path = Some(format!("levels/level_{i}.ldtkl"));
+            }
+
+            let assets = app
+                .world
+                .get_resource::<Assets<LdtkExternalLevel>>()
+                .unwrap();
+
+            let directory =
+                std::env::temp_dir().join(format!("bevy_ecs_ldtk_save_{}", project.json_data.iid));
+            fs::create_dir_all(directory.join("levels")).unwrap();
+
+            project
+                .save(directory.join("project.ldtk"), assets)
+                .unwrap();
+
+            let saved_json: LdtkJson =
+                serde_json::from_str(&fs::read_to_string(directory.join("project.ldtk")).unwrap())
+                    .unwrap();
+
+            assert_eq!(saved_json.iid, project.json_data.iid);
+            assert_eq!(saved_json.levels.len(), project.json_data.levels.len());
+
+            for (i, level) in saved_json.levels.iter().enumerate() {
+                assert!(level.layer_instances.is_none());
+
+                let saved_level: Level = serde_json::from_str(
+                    &fs::read_to_string(directory.join(format!("levels/level_{i}.ldtkl"))).unwrap(),
+                )
+                .unwrap();
+
+                assert_eq!(saved_level.iid, level.iid);
+                assert!(saved_level.layer_instances.is_some());
+            }
+
+            fs::remove_dir_all(directory).unwrap();
+        }
     }
 }
@@ -89,8 +89,8 @@ pub struct LdtkProject {
 impl LdtkProject {
     /// Construct a new [`LdtkProject`].
     ///
-    /// Private to preserve type guarantees about loaded levels.
-    fn new(
+    /// Private to the crate to preserve type guarantees about loaded levels.
+    pub(crate) fn new(
         data: LdtkProjectData,
         tileset_map: HashMap<i32, Handle<Image>>,
         int_grid_image_handle: Option<Handle<Image>>,
@@ -107,6 +107,23 @@ impl LdtkProject {
         self.data.json_data()
     }
 
+    /// Mutable access to the raw ldtk json data, for editing the project at runtime.
+    ///
+    /// Edited projects can be written back to LDtk files with
+    /// [`LdtkJsonWithMetadata::save`](LdtkJsonWithMetadata#method.save), via
+    /// [`LdtkProject::as_standalone`] or [`LdtkProject::as_parent`].
+    ///
+    /// Level metadata is only calculated when the project is loaded.
+    /// So, levels should not be added, removed, or reordered, and internal levels should keep their
+    /// layer instances.
+    /// Otherwise, level accessors may return incorrect results or panic.
+    ///
+    /// Note that getting the project mutably from [`Assets`] sends an [`AssetEvent::Modified`],
+    /// which respawns any worlds using the project.
+    pub fn json_data_mut(&mut self) -> &mut LdtkJson {
+        self.data.json_data_mut()
+    }
+
     /// Unwrap as a [`LdtkJsonWithMetadata<InternalLevels>`].
     /// For use on internal-levels ldtk projects only.
     ///
@@ -174,6 +191,24 @@ pub enum LdtkProjectLoaderError {
     ExternalLevelWithNullPath,
 }
 
+/// Errors that can occur when saving an [`LdtkProject`] to LDtk files.
+#[allow(dead_code)]
+#[derive(Debug, Error)]
+pub enum LdtkProjectSaveError {
+    /// Encountered IO error writing LDtk project
+    #[error("encountered IO error writing LDtk project: {0}")]
+    Io(#[from] io::Error),
+    /// Unable to serialize LDtk project
+    #[error("unable to serialize LDtk project: {0}")]
+    Serialize(#[from] serde_json::Error),
+    /// LDtk project uses external levels, but some level's `external_rel_path` is null.
+    #[error("LDtk project uses external levels, but some level's external_rel_path is null")]
+    ExternalLevelWithNullPath,
+    /// LDtk project uses external levels, but some level's asset isn't loaded.
+    #[error("LDtk project uses external levels, but the asset for level {0} isn't loaded")]
+    ExternalLevelNotLoaded(String),
+}
+
 /// AssetLoader for [`LdtkProject`].
 #[derive(Default)]
 pub struct LdtkProjectLoader;
@@ -184,11 +219,16 @@ fn load_level_metadata(
     level: &Level,
     expect_level_loaded: bool,
 ) -> Result<LevelMetadata, LdtkProjectLoaderError> {
-    let bg_image = level.bg_rel_path.as_ref().map(|rel_path| {
-        let asset_path = ldtk_path_to_asset_path(load_context.path(), rel_path);
+    // Images aren't used, and may not be loadable, without the render feature
+    let bg_image = level
+        .bg_rel_path
+        .as_ref()
+        .filter(|_| cfg!(feature = "render"))
+        .map(|rel_path| {
+            let asset_path = ldtk_path_to_asset_path(load_context.path(), rel_path);
 
-        load_context.load(asset_path)
-    });
+            load_context.load(asset_path)
+        });
 
     if expect_level_loaded && level.layer_instances.is_none() {
         Err(LdtkProjectLoaderError::InternalLevelWithNullLayers)?;
@@ -237,7 +277,12 @@ impl AssetLoader for LdtkProjectLoader {
             let data: LdtkJson = serde_json::from_slice(&bytes)?;
 
             let mut tileset_map: HashMap<i32, Handle<Image>> = HashMap::new();
-            for tileset in &data.defs.tilesets {
+            for tileset in data
+                .defs
+                .tilesets
+                .iter()
+                .filter(|_| cfg!(feature = "render"))
+            {
                 if let Some(tileset_path) = &tileset.rel_path {
                     let asset_path = ldtk_path_to_asset_path(load_context.path(), tileset_path);
 
@@ -250,11 +295,15 @@ impl AssetLoader for LdtkProjectLoader {
                 }
             }
 
+            #[cfg(feature = "render")]
             let int_grid_image_handle = data
                 .defs
                 .create_int_grid_image()
                 .map(|image| load_context.add_labeled_asset("int_grid_image".to_string(), image));
 
+            #[cfg(not(feature = "render"))]
+            let int_grid_image_handle = None;
+
             let ldtk_project = if data.external_levels {
                 #[cfg(feature = "external_levels")]
                 {
@@ -53,6 +53,20 @@ impl LdtkProjectData {
         }
     }
 
+    /// Mutable access to the raw ldtk json data.
+    ///
+    /// See [`LdtkProject::json_data_mut`] for caveats.
+    ///
+    /// [`LdtkProject::json_data_mut`]: crate::assets::LdtkProject::json_data_mut
+    pub fn json_data_mut(&mut self) -> &mut LdtkJson {
+        match self {
+            #[cfg(feature = "internal_levels")]
+            LdtkProjectData::Standalone(project) => project.json_data_mut(),
+            #[cfg(feature = "external_levels")]
+            LdtkProjectData::Parent(project) => project.json_data_mut(),
+        }
+    }
+
     /// Unwrap as a [`LdtkJsonWithMetadata<InternalLevels>`].
     /// For use on internal-levels ldtk projects only.
     ///
@@ -16,6 +16,9 @@ pub trait LevelMetadataAccessor: RawLevelAccessor {
 
     /// Immutable access to a level at the given level iid.
     ///
+    /// [`LevelSelection::Streaming`] can select multiple levels, so this always returns `None` for
+    /// that variant.
+    ///
     /// Note: all levels are considered [raw](crate::assets::LdtkProject#raw-vs-loaded-levels).
     // We accept an `&String` here to avoid creating a new `String`.
     // Implementations will use this to index a `HashMap<String, _>`, which requires `&String`.
@@ -46,6 +49,7 @@ pub trait LevelMetadataAccessor: RawLevelAccessor {
             LevelSelection::Uid(selected_uid) => self
                 .iter_raw_levels()
                 .find(|Level { uid, .. }| uid == selected_uid),
+            LevelSelection::Streaming { .. } => None,
         }
     }
 }
@@ -33,7 +33,7 @@ mod ldtk_project_data;
 pub use ldtk_project_data::LdtkProjectData;
 
 mod ldtk_project;
-pub use ldtk_project::LdtkProject;
+pub use ldtk_project::{LdtkProject, LdtkProjectSaveError};
 
 mod level_indices;
 pub use level_indices::LevelIndices;
@@ -0,0 +1,515 @@
+//! Runtime evaluation of LDtk auto-layer rules.
+//!
+//! LDtk resolves auto-layer rules in the editor and exports the resulting tiles, which is all that's
+//! needed to spawn levels.
+//! The evaluator in this module re-runs those rules so that auto-tiles can be updated when
+//! `IntGrid` values change at runtime.
+//!
+//! All coordinates in this module are LDtk grid coordinates, i.e. `y` increases downwards.
+//!
+//! Some rule features are not supported:
+//! - stamp tile mode, pixel offsets, and random offsets (rules using these are skipped)
+//! - multi-tile rectangles (only the first tile of a rule's chosen tile rectangle is used)
+//! - perlin noise filtering (rules are applied as if perlin filtering was disabled)
+//! - biome requirements (rule groups are applied as if they had no biome requirements)
+
+use crate::ldtk::{AutoLayerRuleDefinition, Checker, LayerDefinition, TileMode};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Pattern value that matches any non-zero `IntGrid` value.
+const ANYTHING: i32 = 1000001;
+
+/// A tile resulting from an auto-layer rule.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Hash)]
+pub(crate) struct AutoTile {
+    pub tile_id: i32,
+    pub flip_x: bool,
+    pub flip_y: bool,
+}
+
+/// Random number generator used by LDtk for deterministic per-cell randomness.
+///
+/// Mirrors `dn.M.randSeedCoords` with 32-bit wrapping arithmetic, including its `iabs`.
+/// The remainder is Euclidean, so the result is in `0..max` even if the hash is `i32::MIN`, which
+/// `iabs` can't make positive.
+fn rand_seed_coords(seed: i32, x: i32, y: i32, max: i32) -> i32 {
+    let h = seed
+        .wrapping_add(x.wrapping_mul(374761393))
+        .wrapping_add(y.wrapping_mul(668265263));
+    let h = (h ^ (h >> 13)).wrapping_mul(1274126177);
+    (h ^ (h >> 16)).wrapping_abs().rem_euclid(max)
+}
+
+/// Evaluates the auto-layer rules of a single layer.
+pub(crate) struct AutoLayerRuleEvaluator<'a> {
+    rules: Vec<&'a AutoLayerRuleDefinition>,
+    int_grid_groups: HashMap<i32, i32>,
+    seed: i32,
+}
+
+impl<'a> AutoLayerRuleEvaluator<'a> {
+    /// Creates an evaluator for the rules of `layer_definition`.
+    ///
+    /// `source_definition` is the `IntGrid` layer providing the values, which is the same as
+    /// `layer_definition` for `IntGrid` layers with rules.
+    /// Optional rule groups are only applied if their uid is in `optional_rules`.
+    pub fn new(
+        layer_definition: &'a LayerDefinition,
+        source_definition: &LayerDefinition,
+        seed: i32,
+        optional_rules: &[i32],
+    ) -> Self {
+        let rules = layer_definition
+            .auto_rule_groups
+            .iter()
+            .filter(|group| {
+                group.active && (!group.is_optional || optional_rules.contains(&group.uid))
+            })
+            .flat_map(|group| group.rules.iter())
+            .filter(|rule| {
+                rule.active
+                    && !rule.tile_rects_ids.is_empty()
+                    && rule.tile_mode == TileMode::Single
+                    && rule.tile_x_offset == 0
+                    && rule.tile_y_offset == 0
+                    && rule.tile_random_x_min == 0
+                    && rule.tile_random_x_max == 0
+                    && rule.tile_random_y_min == 0
+                    && rule.tile_random_y_max == 0
+            })
+            .collect();
+
+        let int_grid_groups = source_definition
+            .int_grid_values
+            .iter()
+            .map(|value_definition| (value_definition.value, value_definition.group_uid))
+            .collect();
+
+        AutoLayerRuleEvaluator {
+            rules,
+            int_grid_groups,
+            seed,
+        }
+    }
+
+    /// Returns true if there are no rules to evaluate.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The furthest distance, in cells, that a rule pattern looks from the evaluated cell.
+    pub fn pattern_radius(&self) -> i32 {
+        self.rules
+            .iter()
+            .map(|rule| rule.size / 2)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Evaluates the rules for the given cell, returning the resulting tiles from bottom to top.
+    ///
+    /// `int_grid` should return the `IntGrid` value at the given coordinates, or `None` if they are
+    /// out of bounds.
+    pub fn evaluate_cell(
+        &self,
+        int_grid: impl Fn(IVec2) -> Option<i32>,
+        cell: IVec2,
+    ) -> Vec<AutoTile> {
+        let mut tiles = Vec::new();
+
+        for rule in &self.rules {
+            if !self.passes_modulo(rule, cell) || !self.passes_chance(rule, cell) {
+                continue;
+            }
+
+            let mut matched = false;
+
+            for (flips, direction) in [
+                (0, IVec2::new(1, 1)),
+                (1, IVec2::new(-1, 1)),
+                (2, IVec2::new(1, -1)),
+                (3, IVec2::new(-1, -1)),
+            ] {
+                let flip_allowed = match flips {
+                    1 => rule.flip_x,
+                    2 => rule.flip_y,
+                    3 => rule.flip_x && rule.flip_y,
+                    _ => true,
+                };
+
+                if (matched && rule.break_on_match) || !flip_allowed {
+                    continue;
+                }
+
+                if self.pattern_matches(rule, &int_grid, cell, direction) {
+                    let tile_rects_index = rand_seed_coords(
+                        self.seed.wrapping_add(rule.uid).wrapping_add(flips),
+                        cell.x,
+                        cell.y,
+                        rule.tile_rects_ids.len() as i32,
+                    );
+
+                    // Multi-tile rectangles aren't supported, so only their first tile is used
+                    if let Some(tile_id) = rule.tile_rects_ids[tile_rects_index as usize].first() {
+                        tiles.push(AutoTile {
+                            tile_id: *tile_id,
+                            flip_x: flips & 1 != 0,
+                            flip_y: flips & 2 != 0,
+                        });
+                    }
+
+                    matched = true;
+                }
+            }
+
+            if matched && rule.break_on_match {
+                break;
+            }
+        }
+
+        // Rules higher in the list are drawn on top
+        tiles.reverse();
+        tiles
+    }
+
+    fn passes_modulo(&self, rule: &AutoLayerRuleDefinition, cell: IVec2) -> bool {
+        let x_modulo = rule.x_modulo.max(1);
+        let y_modulo = rule.y_modulo.max(1);
+
+        let x = cell.x - rule.x_offset;
+        let y = cell.y - rule.y_offset;
+
+        let y_passes = match rule.checker {
+            Checker::Vertical => (y + (x / x_modulo) % 2) % y_modulo == 0,
+            _ => y % y_modulo == 0,
+        };
+
+        let x_passes = match rule.checker {
+            Checker::Horizontal => (x + (y / y_modulo) % 2) % x_modulo == 0,
+            _ => x % x_modulo == 0,
+        };
+
+        x_passes && y_passes
+    }
+
+    fn passes_chance(&self, rule: &AutoLayerRuleDefinition, cell: IVec2) -> bool {
+        if rule.chance <= 0. {
+            return false;
+        }
+
+        rule.chance >= 1.
+            || rand_seed_coords(self.seed.wrapping_add(rule.uid), cell.x, cell.y, 100)
+                < (rule.chance * 100.) as i32
+    }
+
+    fn pattern_matches(
+        &self,
+        rule: &AutoLayerRuleDefinition,
+        int_grid: &impl Fn(IVec2) -> Option<i32>,
+        cell: IVec2,
+        direction: IVec2,
+    ) -> bool {
+        let radius = rule.size / 2;
+
+        for py in 0..rule.size {
+            for px in 0..rule.size {
+                let Some(&pattern_value) = rule.pattern.get((px + py * rule.size) as usize) else {
+                    return false;
+                };
+
+                if pattern_value == 0 {
+                    continue;
+                }
+
+                let coords = cell + direction * IVec2::new(px - radius, py - radius);
+
+                let Some(value) = int_grid(coords).or(rule.out_of_bounds_value) else {
+                    return false;
+                };
+
+                let positive = pattern_value > 0;
+
+                let is_match = match pattern_value.abs() {
+                    ANYTHING => value != 0,
+                    group_value if group_value > 999 => {
+                        let group_uid = group_value / 1000 - 1;
+                        self.int_grid_groups.get(&value) == Some(&group_uid)
+                    }
+                    expected_value => value == expected_value,
+                };
+
+                if is_match != positive {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldtk::{AutoLayerRuleGroup, IntGridValueDefinition};
+
+    fn rule(uid: i32, pattern: Vec<i32>, tile_id: i32) -> AutoLayerRuleDefinition {
+        AutoLayerRuleDefinition {
+            active: true,
+            break_on_match: true,
+            chance: 1.,
+            pattern,
+            size: 3,
+            tile_rects_ids: vec![vec![tile_id]],
+            uid,
+            x_modulo: 1,
+            y_modulo: 1,
+            ..default()
+        }
+    }
+
+    fn layer_definition(rules: Vec<AutoLayerRuleDefinition>) -> LayerDefinition {
+        LayerDefinition {
+            auto_rule_groups: vec![AutoLayerRuleGroup {
+                active: true,
+                rules,
+                ..default()
+            }],
+            int_grid_values: vec![
+                IntGridValueDefinition {
+                    value: 1,
+                    group_uid: 1,
+                    ..default()
+                },
+                IntGridValueDefinition {
+                    value: 2,
+                    group_uid: 1,
+                    ..default()
+                },
+                IntGridValueDefinition {
+                    value: 3,
+                    ..default()
+                },
+            ],
+            ..default()
+        }
+    }
+
+    /// 3x3 grid, accessed with LDtk coordinates
+    fn grid(values: [i32; 9]) -> impl Fn(IVec2) -> Option<i32> {
+        move |coords: IVec2| {
+            (coords.x >= 0 && coords.y >= 0 && coords.x < 3 && coords.y < 3)
+                .then(|| values[(coords.y * 3 + coords.x) as usize])
+        }
+    }
+
+    #[test]
+    fn pattern_values_are_matched() {
+        #[rustfmt::skip]
+        let rules = vec![
+            // requires 1 below and not 1 above
+            rule(1, vec![0, -1, 0, 0, 1, 0, 0, 1, 0], 10),
+            // requires anything to the left
+            rule(2, vec![0, 0, 0, ANYTHING, 0, 0, 0, 0, 0], 20),
+            // requires a value in group 1 to the right
+            rule(3, vec![0, 0, 0, 0, 0, 2000, 0, 0, 0], 30),
+        ];
+        let definition = layer_definition(rules);
+        let evaluator = AutoLayerRuleEvaluator::new(&definition, &definition, 0, &[]);
+
+        #[rustfmt::skip]
+        let int_grid = grid([
+            0, 0, 0,
+            0, 1, 0,
+            0, 1, 0,
+        ]);
+        assert_eq!(
+            evaluator.evaluate_cell(&int_grid, IVec2::new(1, 1)),
+            vec![AutoTile {
+                tile_id: 10,
+                ..default()
+            }]
+        );
+        assert_eq!(evaluator.evaluate_cell(&int_grid, IVec2::new(1, 2)), vec![]);
+
+        #[rustfmt::skip]
+        let int_grid = grid([
+            0, 0, 0,
+            3, 1, 2,
+            0, 0, 0,
+        ]);
+        assert_eq!(
+            evaluator.evaluate_cell(&int_grid, IVec2::new(1, 1)),
+            vec![AutoTile {
+                tile_id: 20,
+                ..default()
+            }]
+        );
+    }
+
+    #[test]
+    fn break_on_match_stacks_tiles() {
+        let mut top_rule = rule(1, vec![0, 0, 0, 0, 1, 0, 0, 0, 0], 10);
+        top_rule.break_on_match = false;
+        let bottom_rule = rule(2, vec![0, 0, 0, 0, 1, 0, 0, 0, 0], 20);
+        let ignored_rule = rule(3, vec![0, 0, 0, 0, 1, 0, 0, 0, 0], 30);
+
+        let definition = layer_definition(vec![top_rule, bottom_rule, ignored_rule]);
+        let evaluator = AutoLayerRuleEvaluator::new(&definition, &definition, 0, &[]);
+
+        let int_grid = grid([0, 0, 0, 0, 1, 0, 0, 0, 0]);
+
+        assert_eq!(
+            evaluator
+                .evaluate_cell(&int_grid, IVec2::new(1, 1))
+                .iter()
+                .map(|tile| tile.tile_id)
+                .collect::<Vec<_>>(),
+            vec![20, 10]
+        );
+    }
+
+    #[test]
+    fn flipped_patterns_are_matched() {
+        // requires 1 to the right
+        let mut flipping_rule = rule(1, vec![0, 0, 0, 0, 1, 1, 0, 0, 0], 10);
+        flipping_rule.flip_x = true;
+
+        let definition = layer_definition(vec![flipping_rule]);
+        let evaluator = AutoLayerRuleEvaluator::new(&definition, &definition, 0, &[]);
+
+        let int_grid = grid([0, 0, 0, 1, 1, 0, 0, 0, 0]);
+
+        assert_eq!(
+            evaluator.evaluate_cell(&int_grid, IVec2::new(1, 1)),
+            vec![AutoTile {
+                tile_id: 10,
+                flip_x: true,
+                flip_y: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn out_of_bounds_value_is_used() {
+        // requires 1 above
+        let mut edge_rule = rule(1, vec![0, 1, 0, 0, 1, 0, 0, 0, 0], 10);
+
+        let definition = layer_definition(vec![edge_rule.clone()]);
+        let evaluator = AutoLayerRuleEvaluator::new(&definition, &definition, 0, &[]);
+        let int_grid = grid([1, 1, 1, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(evaluator.evaluate_cell(&int_grid, IVec2::new(1, 0)), vec![]);
+
+        edge_rule.out_of_bounds_value = Some(1);
+        let definition = layer_definition(vec![edge_rule]);
+        let evaluator = AutoLayerRuleEvaluator::new(&definition, &definition, 0, &[]);
+        assert_eq!(
+            evaluator.evaluate_cell(&int_grid, IVec2::new(1, 0)).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn modulo_and_checker_filter_cells() {
+        let mut modulo_rule = rule(1, vec![0, 0, 0, 0, 1, 0, 0, 0, 0], 10);
+        modulo_rule.x_modulo = 2;
+        modulo_rule.x_offset = 1;
+        modulo_rule.checker = Checker::None;
+
+        let definition = layer_definition(vec![modulo_rule.clone()]);
+        let evaluator = AutoLayerRuleEvaluator::new(&definition, &definition, 0, &[]);
+        let int_grid = grid([1; 9]);
+
+        let matched_cells = |evaluator: &AutoLayerRuleEvaluator| {
+            (0..3)
+                .flat_map(|y| (0..3).map(move |x| IVec2::new(x, y)))
+                .filter(|cell| !evaluator.evaluate_cell(&int_grid, *cell).is_empty())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            matched_cells(&evaluator),
+            vec![IVec2::new(1, 0), IVec2::new(1, 1), IVec2::new(1, 2)]
+        );
+
+        modulo_rule.x_offset = 0;
+        modulo_rule.checker = Checker::Horizontal;
+        let definition = layer_definition(vec![modulo_rule]);
+        let evaluator = AutoLayerRuleEvaluator::new(&definition, &definition, 0, &[]);
+
+        assert_eq!(
+            matched_cells(&evaluator),
+            vec![
+                IVec2::new(0, 0),
+                IVec2::new(2, 0),
+                IVec2::new(1, 1),
+                IVec2::new(0, 2),
+                IVec2::new(2, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn chance_is_deterministic() {
+        let mut chance_rule = rule(1, vec![0, 0, 0, 0, 1, 0, 0, 0, 0], 10);
+        chance_rule.chance = 0.5;
+
+        let definition = layer_definition(vec![chance_rule.clone()]);
+        let evaluator = AutoLayerRuleEvaluator::new(&definition, &definition, 42, &[]);
+        let int_grid = grid([1; 9]);
+
+        let first: Vec<_> = (0..3)
+            .map(|x| evaluator.evaluate_cell(&int_grid, IVec2::new(x, 1)))
+            .collect();
+        let second: Vec<_> = (0..3)
+            .map(|x| evaluator.evaluate_cell(&int_grid, IVec2::new(x, 1)))
+            .collect();
+        assert_eq!(first, second);
+
+        chance_rule.chance = 0.;
+        let definition = layer_definition(vec![chance_rule]);
+        let evaluator = AutoLayerRuleEvaluator::new(&definition, &definition, 42, &[]);
+        assert!(evaluator
+            .evaluate_cell(&int_grid, IVec2::new(1, 1))
+            .is_empty());
+    }
+
+    #[test]
+    fn chance_passes_at_its_rate() {
+        let mut chance_rule = rule(1, vec![0, 0, 0, 0, 1, 0, 0, 0, 0], 10);
+        chance_rule.chance = 0.1;
+
+        let definition = layer_definition(vec![chance_rule.clone()]);
+        let evaluator = AutoLayerRuleEvaluator::new(&definition, &definition, 42, &[]);
+
+        let cells = (0..100).flat_map(|y| (0..100).map(move |x| IVec2::new(x, y)));
+
+        assert!(cells
+            .clone()
+            .all(|cell| (0..100).contains(&rand_seed_coords(42, cell.x, cell.y, 100))));
+        assert!([i32::MIN, -1, i32::MAX].into_iter().all(|seed| {
+            [i32::MIN, -7, 0, i32::MAX]
+                .into_iter()
+                .all(|x| (0..100).contains(&rand_seed_coords(seed, x, x.wrapping_neg(), 100)))
+        }));
+
+        let passes = cells
+            .filter(|cell| evaluator.passes_chance(&chance_rule, *cell))
+            .count();
+        assert!((800..1200).contains(&passes), "{passes} of 10000 passed");
+    }
+
+    #[test]
+    fn optional_groups_require_being_enabled() {
+        let mut definition = layer_definition(vec![rule(1, vec![0, 0, 0, 0, 1, 0, 0, 0, 0], 10)]);
+        definition.auto_rule_groups[0].is_optional = true;
+        definition.auto_rule_groups[0].uid = 5;
+
+        let evaluator = AutoLayerRuleEvaluator::new(&definition, &definition, 0, &[]);
+        assert!(evaluator.is_empty());
+
+        let evaluator = AutoLayerRuleEvaluator::new(&definition, &definition, 0, &[5]);
+        assert_eq!(evaluator.pattern_radius(), 1);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{components::GridCoords, ldtk::LayerInstance};
+use bevy::prelude::*;
+
+#[allow(unused_imports)]
+use crate::{
+    components::IntGridCell,
+    prelude::{LdtkIntCell, LdtkSettings},
+    resources::IntGridStorage,
+};
+
+/// [Component] that stores the values of a whole `IntGrid` layer.
+///
+/// Inserted on the layer entities of `IntGrid` layers when [LdtkSettings::int_grid_storage] is
+/// [IntGridStorage::Layer].
+/// In that case, cells are only spawned as entities if they have a registered [LdtkIntCell]
+/// bundle, so this is where the values of the other cells can be found.
+/// Changes to the [IntGridCell]s of spawned cells are mirrored into it in [PostUpdate].
+///
+/// Empty cells have the value 0.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ecs_ldtk::prelude::*;
+///
+/// fn count_coins(int_grid_layer_query: Query<(&LayerMetadata, &IntGridLayer)>) {
+///     for (layer_metadata, int_grid_layer) in int_grid_layer_query.iter() {
+///         if layer_metadata.identifier == "Pickups" {
+///             let coins = int_grid_layer.iter_value(3).count();
+///             info!("{coins} coins left");
+///         }
+///     }
+/// }
+/// # bevy::ecs::system::assert_is_system(count_coins);
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct IntGridLayer {
+    width: i32,
+    height: i32,
+    /// Values starting at the bottom-left cell, like [GridCoords].
+    values: Vec<i32>,
+}
+
+impl From<&LayerInstance> for IntGridLayer {
+    fn from(layer_instance: &LayerInstance) -> Self {
+        let width = layer_instance.c_wid;
+        let height = layer_instance.c_hei;
+
+        // int_grid_csv starts at the top of the layer, GridCoords start at the bottom
+        let values = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                layer_instance
+                    .int_grid_csv
+                    .get(((height - 1 - y) * width + x) as usize)
+                    .copied()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        IntGridLayer {
+            width,
+            height,
+            values,
+        }
+    }
+}
+
+impl IntGridLayer {
+    /// Width of the layer, in cells.
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// Height of the layer, in cells.
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Returns true if the given coordinates are inside of the layer.
+    pub fn contains(&self, grid_coords: GridCoords) -> bool {
+        (0..self.width).contains(&grid_coords.x) && (0..self.height).contains(&grid_coords.y)
+    }
+
+    fn index(&self, grid_coords: GridCoords) -> Option<usize> {
+        self.contains(grid_coords)
+            .then(|| (grid_coords.y * self.width + grid_coords.x) as usize)
+    }
+
+    /// Returns the value of a cell, or [None] if it's outside of the layer.
+    pub fn get(&self, grid_coords: GridCoords) -> Option<i32> {
+        self.index(grid_coords).map(|index| self.values[index])
+    }
+
+    /// Changes the value of a cell, returning its previous value.
+    ///
+    /// Returns [None], and does nothing, if the cell is outside of the layer.
+    ///
+    /// Unlike changes to [IntGridCell]s, this doesn't re-evaluate auto-layer rules, and isn't
+    /// synced back to the LDtk asset.
+    pub fn set(&mut self, grid_coords: GridCoords, value: i32) -> Option<i32> {
+        let index = self.index(grid_coords)?;
+        Some(std::mem::replace(&mut self.values[index], value))
+    }
+
+    /// Iterates over the coordinates and values of the non-empty cells.
+    pub fn iter(&self) -> impl Iterator<Item = (GridCoords, i32)> + '_ {
+        self.values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value != 0)
+            .map(|(index, value)| {
+                let index = index as i32;
+                (
+                    GridCoords::new(index % self.width, index / self.width),
+                    *value,
+                )
+            })
+    }
+
+    /// Iterates over the coordinates of the cells with the given value.
+    pub fn iter_value(&self, value: i32) -> impl Iterator<Item = GridCoords> + '_ {
+        self.iter()
+            .filter(move |(_, cell_value)| *cell_value == value)
+            .map(|(grid_coords, _)| grid_coords)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int_grid_layer_matches_int_grid_csv() {
+        let layer_instance = LayerInstance {
+            c_wid: 3,
+            c_hei: 2,
+            int_grid_csv: vec![1, 0, 2, 0, 3, 2],
+            ..default()
+        };
+
+        let mut int_grid_layer = IntGridLayer::from(&layer_instance);
+
+        assert_eq!(int_grid_layer.width(), 3);
+        assert_eq!(int_grid_layer.height(), 2);
+        assert_eq!(int_grid_layer.get(GridCoords::new(0, 1)), Some(1));
+        assert_eq!(int_grid_layer.get(GridCoords::new(1, 0)), Some(3));
+        assert_eq!(int_grid_layer.get(GridCoords::new(0, 0)), Some(0));
+        assert_eq!(int_grid_layer.get(GridCoords::new(3, 0)), None);
+
+        assert_eq!(
+            int_grid_layer.iter().collect::<Vec<_>>(),
+            vec![
+                (GridCoords::new(1, 0), 3),
+                (GridCoords::new(2, 0), 2),
+                (GridCoords::new(0, 1), 1),
+                (GridCoords::new(2, 1), 2),
+            ]
+        );
+        assert_eq!(
+            int_grid_layer.iter_value(2).collect::<Vec<_>>(),
+            vec![GridCoords::new(2, 0), GridCoords::new(2, 1)]
+        );
+
+        assert_eq!(int_grid_layer.set(GridCoords::new(2, 0), 0), Some(2));
+        assert_eq!(int_grid_layer.set(GridCoords::new(-1, 0), 1), None);
+        assert_eq!(int_grid_layer.iter_value(2).count(), 1);
+    }
+}
@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::{
+    ldtk::{EntityInstance, FieldValue, ReferenceToAnEntityInstance},
+    resources::EntityIidMap,
+    EntityIid,
+};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Reflect)]
+struct EntityRefSlot {
+    iid: Option<EntityIid>,
+    entity: Option<Entity>,
+}
+
+impl From<&Option<ReferenceToAnEntityInstance>> for EntityRefSlot {
+    fn from(reference: &Option<ReferenceToAnEntityInstance>) -> Self {
+        EntityRefSlot {
+            iid: reference
+                .as_ref()
+                .map(|reference| EntityIid::new(reference.entity_iid.clone())),
+            entity: None,
+        }
+    }
+}
+
+/// [`Component`] that stores the bevy [`Entity`]s targeted by an LDtk entity's `EntityRef` and
+/// `EntityRefs` fields.
+///
+/// Automatically inserted on LDtk entities that have at least one entity reference field.
+///
+/// References are resolved using the [`EntityIidMap`] as soon as their targets spawn, even if the
+/// target is in a different level than the referencing entity.
+/// If a target despawns, its references are reset to `None` until it spawns again.
+///
+/// [`Component`]: https://docs.rs/bevy/latest/bevy/ecs/prelude/trait.Component.html
+/// [`Entity`]: https://docs.rs/bevy/latest/bevy/ecs/entity/struct.Entity.html
+#[derive(Clone, Debug, Default, PartialEq, Eq, Component, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct LdtkEntityRefs {
+    fields: HashMap<String, Vec<EntityRefSlot>>,
+}
+
+impl From<&EntityInstance> for LdtkEntityRefs {
+    fn from(entity_instance: &EntityInstance) -> Self {
+        let fields = entity_instance
+            .field_instances
+            .iter()
+            .filter_map(|field_instance| {
+                let slots = match &field_instance.value {
+                    FieldValue::EntityRef(reference) => vec![reference.into()],
+                    FieldValue::EntityRefs(references) => {
+                        references.iter().map(EntityRefSlot::from).collect()
+                    }
+                    _ => return None,
+                };
+
+                Some((field_instance.identifier.clone(), slots))
+            })
+            .collect();
+
+        LdtkEntityRefs { fields }
+    }
+}
+
+impl LdtkEntityRefs {
+    /// Returns true if there are no entity reference fields stored in this component.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Get the resolved target of the `EntityRef` field with the given identifier.
+    ///
+    /// For `EntityRefs` fields, this returns the target of the first element.
+    ///
+    /// Returns `None` if the field doesn't exist, is null, or its target isn't currently spawned.
+    pub fn get(&self, identifier: &str) -> Option<Entity> {
+        self.fields.get(identifier)?.first()?.entity
+    }
+
+    /// Iterate through the resolved targets of the `EntityRef` or `EntityRefs` field with the
+    /// given identifier, in the order they appear in LDtk.
+    ///
+    /// Elements are `None` if they are null or their target isn't currently spawned.
+    /// The iterator is empty if the field doesn't exist.
+    pub fn get_all(&self, identifier: &str) -> impl Iterator<Item = Option<Entity>> + '_ {
+        self.fields
+            .get(identifier)
+            .into_iter()
+            .flatten()
+            .map(|slot| slot.entity)
+    }
+
+    /// Iterate through every resolved target, paired with the identifier of its field.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Entity)> {
+        self.fields.iter().flat_map(|(identifier, slots)| {
+            slots
+                .iter()
+                .filter_map(move |slot| Some((identifier.as_str(), slot.entity?)))
+        })
+    }
+
+    /// Returns true if every non-null reference has been resolved to a spawned target.
+    pub fn is_resolved(&self) -> bool {
+        self.fields
+            .values()
+            .flatten()
+            .all(|slot| slot.iid.is_none() || slot.entity.is_some())
+    }
+
+    /// Update the resolved targets using the given [`EntityIidMap`].
+    ///
+    /// Returns true if any of the targets changed.
+    pub(crate) fn resolve(&mut self, entity_iid_map: &EntityIidMap) -> bool {
+        let mut changed = false;
+
+        for slot in self.fields.values_mut().flatten() {
+            let entity = slot.iid.as_ref().and_then(|iid| entity_iid_map.get(iid));
+
+            if slot.entity != entity {
+                slot.entity = entity;
+                changed = true;
+            }
+        }
+
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldtk::FieldInstance;
+
+    fn reference(entity_iid: &str) -> Option<ReferenceToAnEntityInstance> {
+        Some(ReferenceToAnEntityInstance {
+            entity_iid: entity_iid.to_string(),
+            ..default()
+        })
+    }
+
+    fn entity_instance_with_refs() -> EntityInstance {
+        let field_instance = |identifier: &str, value: FieldValue| FieldInstance {
+            identifier: identifier.to_string(),
+            tile: None,
+            field_instance_type: String::new(),
+            value,
+            def_uid: 0,
+            real_editor_values: Vec::new(),
+        };
+
+        EntityInstance {
+            field_instances: vec![
+                field_instance("Mother", FieldValue::EntityRef(reference("mother"))),
+                field_instance(
+                    "Children",
+                    FieldValue::EntityRefs(vec![reference("a"), None, reference("b")]),
+                ),
+                field_instance("Health", FieldValue::Int(Some(3))),
+            ],
+            ..default()
+        }
+    }
+
+    #[test]
+    fn only_entity_ref_fields_are_collected() {
+        let entity_refs = LdtkEntityRefs::from(&entity_instance_with_refs());
+
+        assert_eq!(entity_refs.fields.len(), 2);
+        assert!(!entity_refs.fields.contains_key("Health"));
+        assert!(LdtkEntityRefs::from(&EntityInstance::default()).is_empty());
+    }
+
+    #[test]
+    fn references_resolve_and_reset() {
+        let mut entity_refs = LdtkEntityRefs::from(&entity_instance_with_refs());
+        let mut entity_iid_map = EntityIidMap::default();
+
+        assert!(!entity_refs.resolve(&entity_iid_map));
+        assert!(!entity_refs.is_resolved());
+
+        entity_iid_map.insert(EntityIid::new("mother"), Entity::from_raw(0));
+        entity_iid_map.insert(EntityIid::new("b"), Entity::from_raw(2));
+
+        assert!(entity_refs.resolve(&entity_iid_map));
+        assert_eq!(entity_refs.get("Mother"), Some(Entity::from_raw(0)));
+        assert_eq!(
+            entity_refs.get_all("Children").collect::<Vec<_>>(),
+            vec![None, None, Some(Entity::from_raw(2))]
+        );
+        assert!(!entity_refs.is_resolved());
+
+        entity_iid_map.insert(EntityIid::new("a"), Entity::from_raw(1));
+
+        assert!(entity_refs.resolve(&entity_iid_map));
+        assert!(entity_refs.is_resolved());
+        assert_eq!(entity_refs.iter().count(), 3);
+        assert!(!entity_refs.resolve(&entity_iid_map));
+
+        entity_iid_map.retain(|_, entity| *entity != Entity::from_raw(0));
+
+        assert!(entity_refs.resolve(&entity_iid_map));
+        assert_eq!(entity_refs.get("Mother"), None);
+        assert_eq!(entity_refs.get_all("Nonexistent").count(), 0);
+    }
+}
@@ -8,9 +8,21 @@ pub use level_iid::LevelIid;
 mod level_set;
 pub use level_set::LevelSet;
 
+mod ldtk_entity_refs;
+pub use ldtk_entity_refs::LdtkEntityRefs;
+
+mod int_grid_layer;
+pub use int_grid_layer::IntGridLayer;
+
+mod parallax;
+pub use parallax::{LdtkParallax, LdtkParallaxCamera};
+
 pub use crate::ldtk::EntityInstance;
 use crate::{
-    ldtk::{LayerInstance, Type},
+    ldtk::{
+        ldtk_enum::{LdtkEnum, LdtkEnumError},
+        LayerInstance, Type,
+    },
     prelude::LdtkProject,
     utils::ldtk_grid_coords_to_grid_coords,
 };
@@ -41,6 +53,93 @@ pub struct IntGridCell {
     pub value: i32,
 }
 
+/// [Component] added to level entities that are partially spawned.
+///
+/// Only used when levels are spawned across multiple frames with [LevelSpawnBudget::PerFrame].
+/// It is removed once the level has completely spawned.
+///
+/// Large layers can be split between frames too, so it also tracks the layer being spawned.
+///
+/// [LevelSpawnBudget::PerFrame]: crate::prelude::LevelSpawnBudget::PerFrame
+#[derive(Clone, Eq, PartialEq, Debug, Default, Hash, Component)]
+pub struct LevelSpawnProgress {
+    pub(crate) background_spawned: bool,
+    pub(crate) spawned_layers: usize,
+    pub(crate) layer_z: usize,
+    /// Entities spawned so far for the layer being spawned.
+    ///
+    /// Tile layers with overlapping tiles have several tilemaps, which are spawned one after the
+    /// other.
+    pub(crate) layer_entities: Vec<Entity>,
+    /// Rows of the last tilemap in `layer_entities`, or entities of an entity layer, spawned so
+    /// far.
+    /// Zero once the last tilemap has completely spawned.
+    pub(crate) layer_spawned: usize,
+    /// Indices of the tiles of each tilemap of the layer being spawned, sorted by row.
+    ///
+    /// Indices count the layer's grid tiles, then its auto-layer tiles.
+    /// Empty until the layer's tilemaps start spawning.
+    pub(crate) layer_tiles: Vec<Vec<usize>>,
+}
+
+impl LevelSpawnProgress {
+    /// Number of the level's layers that have spawned so far.
+    pub fn spawned_layers(&self) -> usize {
+        self.spawned_layers
+    }
+}
+
+/// [Component] for a rectangle of `IntGrid` cells that share the same value.
+///
+/// Only spawned if [IntGridMerging] is enabled in the [LdtkSettings].
+/// Each rectangle is spawned as a child of its `IntGrid` layer, with a [Transform] placing it at
+/// the center of the rectangle.
+/// This makes them a convenient place to insert physics colliders, by querying for
+/// `Added<MergedIntGridRegion>`.
+///
+/// The rectangles are greedy-meshed per value and per layer, and are respawned with their level.
+///
+/// [IntGridMerging]: crate::prelude::IntGridMerging
+/// [LdtkSettings]: crate::prelude::LdtkSettings
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Hash, Component, Reflect)]
+#[reflect(Component)]
+pub struct MergedIntGridRegion {
+    /// The `IntGrid` value of every cell in this region.
+    pub value: i32,
+    /// The cells covered by this region in [GridCoords].
+    ///
+    /// `min` is the bottom-left cell, while `max` is exclusive, i.e. one cell up and to the right
+    /// of the top-right cell.
+    pub rect: IRect,
+}
+
+impl MergedIntGridRegion {
+    /// Size of the region in pixels, given the grid size of its layer.
+    pub fn size_px(&self, grid_size: i32) -> Vec2 {
+        (self.rect.size() * grid_size).as_vec2()
+    }
+}
+
+/// [Component] for the outline of a connected group of `IntGrid` cells that share the same value.
+///
+/// Only spawned if [IntGridMerging] is enabled in the [LdtkSettings] with `outlines: true`.
+/// Like [MergedIntGridRegion]s, these are spawned as children of their `IntGrid` layer.
+///
+/// [IntGridMerging]: crate::prelude::IntGridMerging
+/// [LdtkSettings]: crate::prelude::LdtkSettings
+#[derive(Clone, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct MergedIntGridOutline {
+    /// The `IntGrid` value of the outlined cells.
+    pub value: i32,
+    /// Vertices of the outline relative to this entity's [Transform], in pixels.
+    ///
+    /// The polyline is closed, i.e. the last vertex connects back to the first.
+    /// Outer boundaries are wound counter-clockwise and the boundaries of holes clockwise, so the
+    /// outlined cells are always to the left of each edge.
+    pub points: Vec<Vec2>,
+}
+
 /// [`Component`] that indicates that an ldtk entity should be a child of the world, not their layer.
 ///
 /// For a more detailed explanation, please see the
@@ -208,6 +307,16 @@ pub struct TileEnumTags {
     pub source_enum_uid: Option<i32>,
 }
 
+impl TileEnumTags {
+    /// Parse these tags as values of the [LdtkEnum] `E`.
+    ///
+    /// # Errors
+    /// Returns [LdtkEnumError::UnknownValue] for the first tag that is not a value of `E`.
+    pub fn parse_tags<E: LdtkEnum>(&self) -> Result<Vec<E>, LdtkEnumError> {
+        self.tags.iter().map(|tag| tag.parse()).collect()
+    }
+}
+
 /// [Component] for storing some LDtk layer information on layer entities.
 ///
 /// Based on [LayerInstance], but without the fields with tile and entity information.
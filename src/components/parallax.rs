@@ -0,0 +1,189 @@
+use crate::ldtk::LayerDefinition;
+use bevy::prelude::*;
+
+/// [Component] that makes a layer scroll at a different speed than the rest of its level, like
+/// LDtk's parallax preview.
+///
+/// Automatically inserted on the layer entities of layers with a non-zero parallax factor in their
+/// [LayerDefinition].
+/// Each frame, these layers are offset relative to the camera with a [LdtkParallaxCamera].
+///
+/// Layers are exactly where LDtk places them when the camera is centered on their level.
+#[derive(Copy, Clone, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct LdtkParallax {
+    /// How much the layer follows the camera on each axis.
+    ///
+    /// At 0, the layer doesn't follow the camera at all.
+    /// At 1, it moves along with the camera, so it looks infinitely far away.
+    /// Negative factors move the layer against the camera, so it looks closer than the level.
+    pub factor: Vec2,
+    /// Whether the layer is also scaled down (or up, for negative factors) by its factor.
+    pub scaling: bool,
+    /// The translation the layer was spawned with, relative to its level.
+    pub origin: Vec3,
+    /// The center of the layer's level, relative to the level.
+    pub level_center: Vec2,
+}
+
+impl LdtkParallax {
+    /// Creates the parallax of a layer spawned at `origin`, or [None] if the layer has no
+    /// parallax.
+    pub fn from_layer_definition(
+        layer_definition: &LayerDefinition,
+        origin: Vec3,
+        level_center: Vec2,
+    ) -> Option<LdtkParallax> {
+        let factor = Vec2::new(
+            layer_definition.parallax_factor_x,
+            layer_definition.parallax_factor_y,
+        );
+
+        (factor != Vec2::ZERO).then_some(LdtkParallax {
+            factor,
+            scaling: layer_definition.parallax_scaling,
+            origin,
+            level_center,
+        })
+    }
+
+    /// The scale of the layer.
+    pub fn scale(&self) -> Vec2 {
+        if self.scaling {
+            Vec2::ONE - self.factor
+        } else {
+            Vec2::ONE
+        }
+    }
+
+    /// Returns the [Transform] of the layer, relative to its level, for a camera at the given
+    /// translation, also relative to the level.
+    pub fn transform(&self, camera_translation: Vec2) -> Transform {
+        let scale = self.scale();
+
+        // Scaling happens around the level center, so the layer stays centered on its level
+        let translation = self.level_center
+            + (self.origin.truncate() - self.level_center) * scale
+            + (camera_translation - self.level_center) * self.factor;
+
+        Transform::from_translation(translation.extend(self.origin.z)).with_scale(scale.extend(1.))
+    }
+}
+
+/// [Component] marking the camera that [LdtkParallax] layers are offset relative to.
+///
+/// If there is no such camera, or more than one, parallax layers stay where they are.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ecs_ldtk::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((Camera2dBundle::default(), LdtkParallaxCamera));
+/// }
+/// # bevy::ecs::system::assert_is_system(setup);
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Hash, Component, Reflect)]
+#[reflect(Component)]
+pub struct LdtkParallaxCamera;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::apply_parallax;
+
+    #[test]
+    fn parallax_layers_follow_the_camera_by_their_factor() {
+        let layer_definition = LayerDefinition {
+            parallax_factor_x: 0.5,
+            parallax_factor_y: -0.5,
+            parallax_scaling: false,
+            ..default()
+        };
+
+        let parallax = LdtkParallax::from_layer_definition(
+            &layer_definition,
+            Vec3::new(8., 8., 2.),
+            Vec2::new(64., 32.),
+        )
+        .unwrap();
+
+        assert_eq!(
+            parallax.transform(Vec2::new(64., 32.)),
+            Transform::from_xyz(8., 8., 2.)
+        );
+        assert_eq!(
+            parallax.transform(Vec2::new(84., 52.)),
+            Transform::from_xyz(18., -2., 2.)
+        );
+
+        let scaled = LdtkParallax {
+            scaling: true,
+            ..parallax
+        };
+
+        assert_eq!(scaled.scale(), Vec2::new(0.5, 1.5));
+        assert_eq!(
+            scaled.transform(Vec2::new(64., 32.)),
+            Transform::from_xyz(36., -4., 2.).with_scale(Vec3::new(0.5, 1.5, 1.))
+        );
+
+        assert_eq!(
+            LdtkParallax::from_layer_definition(&default(), Vec3::ZERO, Vec2::ZERO),
+            None
+        );
+    }
+
+    #[test]
+    fn parallax_layers_are_moved_relative_to_the_camera() {
+        let mut app = App::new();
+        app.add_systems(Update, apply_parallax);
+
+        let parallax = LdtkParallax {
+            factor: Vec2::splat(0.5),
+            scaling: false,
+            origin: Vec3::new(8., 8., 1.),
+            level_center: Vec2::new(64., 32.),
+        };
+
+        let level_entity = app
+            .world
+            .spawn(TransformBundle::from_transform(Transform::from_xyz(
+                100., 0., 0.,
+            )))
+            .id();
+
+        let layer_entity = app
+            .world
+            .spawn((TransformBundle::default(), parallax))
+            .set_parent(level_entity)
+            .id();
+
+        let camera_entity = app
+            .world
+            .spawn((
+                TransformBundle::from_transform(Transform::from_xyz(164., 52., 0.)),
+                LdtkParallaxCamera,
+            ))
+            .id();
+
+        app.update();
+
+        assert_eq!(
+            *app.world.get::<Transform>(layer_entity).unwrap(),
+            Transform::from_xyz(8., 18., 1.)
+        );
+
+        // GlobalTransforms haven't propagated, but the camera's current Transform is used
+        app.world
+            .get_mut::<Transform>(camera_entity)
+            .unwrap()
+            .translation
+            .x = 184.;
+        app.update();
+
+        assert_eq!(
+            *app.world.get::<Transform>(layer_entity).unwrap(),
+            Transform::from_xyz(18., 18., 1.)
+        );
+    }
+}
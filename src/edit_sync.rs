@@ -0,0 +1,231 @@
+//! Edits to spawned entities that can be mirrored back into LDtk level data.
+//!
+//! Used by the plugin when [AssetEditSync::Enabled] is set, see
+//! [sync_edits_to_ldtk_assets](crate::systems::sync_edits_to_ldtk_assets).
+//!
+//! [AssetEditSync::Enabled]: crate::resources::AssetEditSync::Enabled
+
+use crate::{
+    components::GridCoords,
+    ldtk::{EntityInstance, Level},
+    utils::grid_coords_to_ldtk_grid_coords,
+};
+
+/// A change made to a spawned entity that should be written into its [Level].
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum LevelEdit {
+    /// An LDtk entity whose [EntityInstance] or [GridCoords] changed.
+    ///
+    /// The entire instance is written into the level.
+    /// If `grid_coords` is given, the instance is moved to that cell first, keeping its offset
+    /// within the cell.
+    Entity {
+        instance: EntityInstance,
+        grid_coords: Option<GridCoords>,
+    },
+    /// An `IntGrid` cell whose value changed.
+    IntGrid {
+        layer_iid: String,
+        grid_coords: GridCoords,
+        value: i32,
+    },
+}
+
+impl LevelEdit {
+    /// Returns true if the level contains the target of this edit.
+    #[cfg_attr(not(feature = "external_levels"), allow(dead_code))]
+    pub fn applies_to(&self, level: &Level) -> bool {
+        let mut layer_instances = level.layer_instances.iter().flatten();
+
+        match self {
+            LevelEdit::Entity { instance, .. } => layer_instances.any(|layer_instance| {
+                layer_instance
+                    .entity_instances
+                    .iter()
+                    .any(|entity_instance| entity_instance.iid == instance.iid)
+            }),
+            LevelEdit::IntGrid { layer_iid, .. } => {
+                layer_instances.any(|layer_instance| layer_instance.iid == *layer_iid)
+            }
+        }
+    }
+
+    /// Writes this edit into the level, returning true if its target was found.
+    ///
+    /// For entity edits, the instance is updated to match what was written, which only differs if
+    /// it was moved.
+    pub fn apply(&mut self, level: &mut Level) -> bool {
+        for layer_instance in level.layer_instances.iter_mut().flatten() {
+            match self {
+                LevelEdit::Entity {
+                    instance,
+                    grid_coords,
+                } => {
+                    let Some(asset_instance) = layer_instance
+                        .entity_instances
+                        .iter_mut()
+                        .find(|entity_instance| entity_instance.iid == instance.iid)
+                    else {
+                        continue;
+                    };
+
+                    if let Some(grid_coords) = grid_coords {
+                        let grid =
+                            grid_coords_to_ldtk_grid_coords(*grid_coords, layer_instance.c_hei);
+                        let px_delta = (grid - instance.grid) * layer_instance.grid_size;
+
+                        instance.px += px_delta;
+                        instance.grid = grid;
+                        instance.world_x = instance.world_x.map(|world_x| world_x + px_delta.x);
+                        instance.world_y = instance.world_y.map(|world_y| world_y + px_delta.y);
+                    }
+
+                    *asset_instance = instance.clone();
+                    return true;
+                }
+                LevelEdit::IntGrid {
+                    layer_iid,
+                    grid_coords,
+                    value,
+                } => {
+                    if layer_instance.iid != *layer_iid {
+                        continue;
+                    }
+
+                    let (c_wid, c_hei) = (layer_instance.c_wid, layer_instance.c_hei);
+
+                    if (0..c_wid).contains(&grid_coords.x) && (0..c_hei).contains(&grid_coords.y) {
+                        let index = (c_hei - 1 - grid_coords.y) * c_wid + grid_coords.x;
+
+                        if let Some(csv_value) = layer_instance.int_grid_csv.get_mut(index as usize)
+                        {
+                            *csv_value = *value;
+                        }
+                    }
+
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldtk::{FieldInstance, FieldValue, LayerInstance};
+    use bevy::prelude::*;
+
+    fn level() -> Level {
+        Level {
+            layer_instances: Some(vec![
+                LayerInstance {
+                    iid: "entities".to_string(),
+                    c_wid: 4,
+                    c_hei: 3,
+                    grid_size: 16,
+                    entity_instances: vec![EntityInstance {
+                        iid: "player".to_string(),
+                        grid: IVec2::new(1, 1),
+                        px: IVec2::new(24, 20),
+                        world_x: Some(124),
+                        world_y: Some(20),
+                        ..default()
+                    }],
+                    ..default()
+                },
+                LayerInstance {
+                    iid: "int_grid".to_string(),
+                    c_wid: 4,
+                    c_hei: 3,
+                    grid_size: 16,
+                    int_grid_csv: vec![0; 12],
+                    ..default()
+                },
+            ]),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn entity_edits_are_written() {
+        let mut level = level();
+
+        let mut instance = level.layer_instances.as_ref().unwrap()[0].entity_instances[0].clone();
+        instance.field_instances.push(FieldInstance {
+            identifier: "Health".to_string(),
+            tile: None,
+            field_instance_type: "Int".to_string(),
+            value: FieldValue::Int(Some(5)),
+            def_uid: 0,
+            real_editor_values: Vec::new(),
+        });
+
+        let mut edit = LevelEdit::Entity {
+            instance: instance.clone(),
+            grid_coords: None,
+        };
+
+        assert!(edit.applies_to(&level));
+        assert!(edit.apply(&mut level));
+        assert_eq!(
+            level.layer_instances.as_ref().unwrap()[0].entity_instances[0],
+            instance
+        );
+    }
+
+    #[test]
+    fn moved_entities_keep_cell_offset() {
+        let mut level = level();
+
+        let instance = level.layer_instances.as_ref().unwrap()[0].entity_instances[0].clone();
+
+        // GridCoords are y-up, so (3, 0) is the bottom-right cell
+        let mut edit = LevelEdit::Entity {
+            instance,
+            grid_coords: Some(GridCoords::new(3, 0)),
+        };
+
+        assert!(edit.apply(&mut level));
+
+        let moved = &level.layer_instances.as_ref().unwrap()[0].entity_instances[0];
+        assert_eq!(moved.grid, IVec2::new(3, 2));
+        assert_eq!(moved.px, IVec2::new(56, 36));
+        assert_eq!(moved.world_x, Some(156));
+        assert_eq!(moved.world_y, Some(36));
+
+        let LevelEdit::Entity { instance, .. } = edit else {
+            unreachable!()
+        };
+        assert_eq!(&instance, moved);
+    }
+
+    #[test]
+    fn int_grid_edits_are_written() {
+        let mut level = level();
+
+        let mut edit = LevelEdit::IntGrid {
+            layer_iid: "int_grid".to_string(),
+            grid_coords: GridCoords::new(1, 2),
+            value: 3,
+        };
+
+        assert!(edit.applies_to(&level));
+        assert!(edit.apply(&mut level));
+        assert_eq!(
+            level.layer_instances.as_ref().unwrap()[1].int_grid_csv[1],
+            3
+        );
+
+        let mut missing_layer = LevelEdit::IntGrid {
+            layer_iid: "nonexistent".to_string(),
+            grid_coords: GridCoords::new(1, 2),
+            value: 3,
+        };
+
+        assert!(!missing_layer.applies_to(&level));
+        assert!(!missing_layer.apply(&mut level));
+    }
+}
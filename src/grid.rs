@@ -0,0 +1,460 @@
+//! Spatial queries over spawned tile layers.
+
+use crate::{
+    components::{GridCoords, IntGridCell, IntGridLayer, LayerMetadata, TileMetadata},
+    utils::translation_to_grid_coords,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use seek_ecs_tilemap::tiles::TileStorage;
+
+/// A tile found by [LdtkGrid].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LdtkGridTile<'a> {
+    /// The tilemap entity of the layer the tile belongs to.
+    pub layer_entity: Entity,
+    /// The tile entity.
+    pub tile_entity: Entity,
+    /// The coordinates of the tile in its layer.
+    pub grid_coords: GridCoords,
+    /// The [IntGridCell] of the tile, if it belongs to an IntGrid layer.
+    pub int_grid_cell: Option<IntGridCell>,
+    /// The [TileMetadata] of the tile, if it has any.
+    pub tile_metadata: Option<&'a TileMetadata>,
+}
+
+/// [SystemParam] for looking up spawned tiles by world position or [GridCoords].
+///
+/// Lookups by world position work across every spawned level, so they don't depend on which
+/// level a point is in, nor on the [LevelSpawnBehavior](crate::prelude::LevelSpawnBehavior).
+/// Lookups by [GridCoords] need a layer entity, since each level has its own coordinates.
+///
+/// IntGrid, Tile, and AutoLayer layers can be queried.
+/// Layers with overlapping tiles are spawned as several tilemap entities, and each of them is a
+/// separate layer entity for [LdtkGrid].
+///
+/// IntGrid values are also read from [IntGridLayer]s, so they're found even if their cells don't
+/// have entities.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ecs_ldtk::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn lava_damage(ldtk_grid: LdtkGrid, player_query: Query<&GlobalTransform, With<Player>>) {
+///     for player_transform in player_query.iter() {
+///         let lava = ldtk_grid.int_grid_value_at("Terrain", player_transform.translation().truncate());
+///
+///         if lava == Some(3) {
+///             info!("ouch");
+///         }
+///     }
+/// }
+/// # bevy::ecs::system::assert_is_system(lava_damage);
+/// ```
+#[derive(SystemParam)]
+pub struct LdtkGrid<'w, 's> {
+    #[allow(clippy::type_complexity)]
+    layer_query: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static LayerMetadata,
+            &'static TileStorage,
+            Option<&'static Parent>,
+            Option<&'static IntGridLayer>,
+        ),
+    >,
+    level_query: Query<'w, 's, &'static GlobalTransform>,
+    tile_query: Query<'w, 's, (Option<&'static IntGridCell>, Option<&'static TileMetadata>)>,
+}
+
+impl<'w, 's> LdtkGrid<'w, 's> {
+    /// Iterates over the tile layer entities with the given identifier, in every level.
+    pub fn layers<'a>(&'a self, layer_identifier: &'a str) -> impl Iterator<Item = Entity> + 'a {
+        self.layer_query
+            .iter()
+            .filter(move |(_, layer_metadata, ..)| layer_metadata.identifier == layer_identifier)
+            .map(|(layer_entity, ..)| layer_entity)
+    }
+
+    /// Returns the [GridCoords] of the given layer that contain a world-space point, or [None] if
+    /// the point is outside of the layer.
+    ///
+    /// Cells are where they are in the layer's level, so [LdtkParallax] offsets are ignored.
+    ///
+    /// [LdtkParallax]: crate::prelude::LdtkParallax
+    pub fn grid_coords(&self, layer_entity: Entity, translation: Vec2) -> Option<GridCoords> {
+        let (_, layer_metadata, _, parent, _) = self.layer_query.get(layer_entity).ok()?;
+        let level_transform = self.level_query.get(parent?.get()).ok()?;
+
+        // The layer's translation depends on its tile size and pivot, but its grid always starts
+        // at its pixel offset in the level
+        let layer_origin = Vec2::new(
+            layer_metadata.px_total_offset_x as f32,
+            -layer_metadata.px_total_offset_y as f32,
+        );
+
+        let layer_translation = level_transform
+            .affine()
+            .inverse()
+            .transform_point3(translation.extend(0.))
+            .truncate()
+            - layer_origin;
+
+        let grid_size = IVec2::splat(layer_metadata.grid_size);
+
+        let size = (IVec2::new(layer_metadata.c_wid, layer_metadata.c_hei) * grid_size).as_vec2();
+        if layer_translation.cmplt(Vec2::ZERO).any() || layer_translation.cmpge(size).any() {
+            return None;
+        }
+
+        Some(translation_to_grid_coords(layer_translation, grid_size))
+    }
+
+    /// Returns the tile at the given [GridCoords] of a layer, or [None] if there isn't one.
+    pub fn tile(&self, layer_entity: Entity, grid_coords: GridCoords) -> Option<LdtkGridTile<'_>> {
+        let (_, layer_metadata, storage, ..) = self.layer_query.get(layer_entity).ok()?;
+
+        if !(0..layer_metadata.c_wid).contains(&grid_coords.x)
+            || !(0..layer_metadata.c_hei).contains(&grid_coords.y)
+        {
+            return None;
+        }
+
+        let tile_entity = storage.get(&grid_coords.into())?;
+        let (int_grid_cell, tile_metadata) = self.tile_query.get(tile_entity).ok()?;
+
+        Some(LdtkGridTile {
+            layer_entity,
+            tile_entity,
+            grid_coords,
+            int_grid_cell: int_grid_cell.copied(),
+            tile_metadata,
+        })
+    }
+
+    /// Iterates over the tiles containing a world-space point, on every layer with the given
+    /// identifier.
+    pub fn tiles_at<'a>(
+        &'a self,
+        layer_identifier: &'a str,
+        translation: Vec2,
+    ) -> impl Iterator<Item = LdtkGridTile<'a>> + 'a {
+        self.layers(layer_identifier)
+            .filter_map(move |layer_entity| {
+                self.tile(layer_entity, self.grid_coords(layer_entity, translation)?)
+            })
+    }
+
+    /// Returns the tile containing a world-space point on a layer with the given identifier.
+    ///
+    /// If several tiles do, tiles with an [IntGridCell] are preferred.
+    pub fn tile_at<'a>(
+        &'a self,
+        layer_identifier: &'a str,
+        translation: Vec2,
+    ) -> Option<LdtkGridTile<'a>> {
+        let mut tiles = self.tiles_at(layer_identifier, translation);
+        let first = tiles.next()?;
+
+        if first.int_grid_cell.is_some() {
+            Some(first)
+        } else {
+            tiles
+                .find(|tile| tile.int_grid_cell.is_some())
+                .or(Some(first))
+        }
+    }
+
+    /// Returns the IntGrid value at the given [GridCoords] of a layer.
+    ///
+    /// This is [None] for empty cells, as well as for coordinates outside of the layer.
+    /// Cells with an [IntGridCell] are read from it, so edits are seen right away, even before
+    /// they're mirrored into the layer's [IntGridLayer].
+    pub fn int_grid_value(&self, layer_entity: Entity, grid_coords: GridCoords) -> Option<i32> {
+        let int_grid_cell = self
+            .tile(layer_entity, grid_coords)
+            .and_then(|tile| tile.int_grid_cell);
+
+        // Cells with an entity may have been edited since the IntGridLayer was last synced
+        let value = match (int_grid_cell, self.layer_query.get(layer_entity)) {
+            (Some(int_grid_cell), _) => Some(int_grid_cell.value),
+            (None, Ok((.., Some(int_grid_layer)))) => int_grid_layer.get(grid_coords),
+            _ => None,
+        };
+
+        value.filter(|value| *value != 0)
+    }
+
+    /// Returns the IntGrid value at a world-space point on a layer with the given identifier.
+    ///
+    /// Like [LdtkGrid::int_grid_value], this is [None] for empty cells.
+    pub fn int_grid_value_at(&self, layer_identifier: &str, translation: Vec2) -> Option<i32> {
+        self.layers(layer_identifier).find_map(|layer_entity| {
+            self.int_grid_value(layer_entity, self.grid_coords(layer_entity, translation)?)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::SystemState;
+    use seek_ecs_tilemap::{map::TilemapSize, tiles::TilePos};
+
+    /// Spawns a 2x2 IntGrid layer of 16px tiles in a level whose bottom-left corner is at `origin`.
+    fn spawn_layer(world: &mut World, identifier: &str, origin: Vec2, values: [i32; 4]) -> Entity {
+        let mut storage = TileStorage::empty(TilemapSize { x: 2, y: 2 });
+
+        for (i, value) in values.into_iter().enumerate() {
+            let tile_pos = TilePos::new(i as u32 % 2, i as u32 / 2);
+            if value != 0 {
+                let tile_entity = world.spawn(IntGridCell { value }).id();
+                storage.set(&tile_pos, tile_entity);
+            }
+        }
+
+        let level = world
+            .spawn(GlobalTransform::from_translation(origin.extend(0.)))
+            .id();
+
+        world
+            .spawn((
+                LayerMetadata {
+                    identifier: identifier.to_string(),
+                    c_wid: 2,
+                    c_hei: 2,
+                    grid_size: 16,
+                    ..default()
+                },
+                storage,
+                GlobalTransform::from_translation((origin + Vec2::splat(8.)).extend(0.)),
+            ))
+            .set_parent(level)
+            .id()
+    }
+
+    #[test]
+    fn tiles_are_found_by_world_position() {
+        let mut world = World::new();
+        // Two levels side by side, in world translation mode
+        let left = spawn_layer(&mut world, "Terrain", Vec2::ZERO, [1, 0, 0, 2]);
+        let right = spawn_layer(&mut world, "Terrain", Vec2::new(32., 0.), [3, 3, 3, 3]);
+        spawn_layer(&mut world, "Decoration", Vec2::ZERO, [4, 4, 4, 4]);
+
+        let mut system_state: SystemState<LdtkGrid> = SystemState::new(&mut world);
+        let ldtk_grid = system_state.get(&world);
+
+        assert_eq!(
+            ldtk_grid.grid_coords(left, Vec2::new(20., 5.)),
+            Some(GridCoords::new(1, 0))
+        );
+        assert_eq!(ldtk_grid.grid_coords(left, Vec2::new(-1., 5.)), None);
+        assert_eq!(ldtk_grid.grid_coords(left, Vec2::new(32., 5.)), None);
+
+        assert_eq!(
+            ldtk_grid.int_grid_value_at("Terrain", Vec2::new(5., 5.)),
+            Some(1)
+        );
+        assert_eq!(
+            ldtk_grid.int_grid_value_at("Terrain", Vec2::new(20., 20.)),
+            Some(2)
+        );
+        assert_eq!(
+            ldtk_grid.int_grid_value_at("Terrain", Vec2::new(20., 5.)),
+            None
+        );
+        assert_eq!(
+            ldtk_grid.int_grid_value_at("Terrain", Vec2::new(40., 5.)),
+            Some(3)
+        );
+        assert_eq!(
+            ldtk_grid.int_grid_value_at("Terrain", Vec2::new(70., 5.)),
+            None
+        );
+
+        let tile = ldtk_grid.tile_at("Terrain", Vec2::new(40., 20.)).unwrap();
+        assert_eq!(tile.layer_entity, right);
+        assert_eq!(tile.grid_coords, GridCoords::new(0, 1));
+        assert_eq!(tile.int_grid_cell, Some(IntGridCell { value: 3 }));
+
+        assert_eq!(
+            ldtk_grid.int_grid_value(left, GridCoords::new(1, 1)),
+            Some(2)
+        );
+        assert_eq!(ldtk_grid.int_grid_value(left, GridCoords::new(2, 1)), None);
+        assert_eq!(ldtk_grid.layers("Terrain").count(), 2);
+    }
+
+    #[test]
+    fn int_grid_values_are_read_from_int_grid_layers() {
+        let mut world = World::new();
+
+        let layer_instance = crate::ldtk::LayerInstance {
+            c_wid: 2,
+            c_hei: 2,
+            int_grid_csv: vec![0, 1, 2, 0],
+            ..default()
+        };
+
+        let level = world.spawn(GlobalTransform::default()).id();
+        let layer = world
+            .spawn((
+                LayerMetadata {
+                    identifier: "Terrain".to_string(),
+                    c_wid: 2,
+                    c_hei: 2,
+                    grid_size: 16,
+                    ..default()
+                },
+                TileStorage::empty(TilemapSize { x: 2, y: 2 }),
+                GlobalTransform::from_translation(Vec3::new(8., 8., 0.)),
+                IntGridLayer::from(&layer_instance),
+            ))
+            .set_parent(level)
+            .id();
+
+        let mut system_state: SystemState<LdtkGrid> = SystemState::new(&mut world);
+        let ldtk_grid = system_state.get(&world);
+
+        assert_eq!(
+            ldtk_grid.int_grid_value(layer, GridCoords::new(1, 1)),
+            Some(1)
+        );
+        assert_eq!(ldtk_grid.int_grid_value(layer, GridCoords::new(0, 1)), None);
+        assert_eq!(
+            ldtk_grid.int_grid_value_at("Terrain", Vec2::new(5., 5.)),
+            Some(2)
+        );
+        assert_eq!(ldtk_grid.tile(layer, GridCoords::new(0, 0)), None);
+    }
+
+    #[test]
+    fn edited_int_grid_cells_are_read_from_their_entities() {
+        use crate::{components::LdtkParent, systems::sync_int_grid_layers};
+
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(sync_int_grid_layers);
+
+        let layer_instance = crate::ldtk::LayerInstance {
+            c_wid: 2,
+            c_hei: 2,
+            int_grid_csv: vec![0, 1, 2, 0],
+            ..default()
+        };
+
+        let level = world.spawn(GlobalTransform::default()).id();
+        let layer = world.spawn_empty().set_parent(level).id();
+
+        // Only the top-right cell is registered, so it's the only one with an entity
+        let cell = world
+            .spawn((
+                IntGridCell { value: 1 },
+                GridCoords::new(1, 1),
+                LdtkParent(layer),
+            ))
+            .id();
+
+        let mut storage = TileStorage::empty(TilemapSize { x: 2, y: 2 });
+        storage.set(&TilePos::new(1, 1), cell);
+
+        world.entity_mut(layer).insert((
+            LayerMetadata {
+                identifier: "Terrain".to_string(),
+                c_wid: 2,
+                c_hei: 2,
+                grid_size: 16,
+                ..default()
+            },
+            storage,
+            GlobalTransform::from_translation(Vec3::new(8., 8., 0.)),
+            IntGridLayer::from(&layer_instance),
+        ));
+
+        schedule.run(&mut world);
+
+        world.get_mut::<IntGridCell>(cell).unwrap().value = 3;
+
+        let mut system_state: SystemState<LdtkGrid> = SystemState::new(&mut world);
+        assert_eq!(
+            system_state
+                .get(&world)
+                .int_grid_value(layer, GridCoords::new(1, 1)),
+            Some(3)
+        );
+
+        schedule.run(&mut world);
+
+        let int_grid_layer = world.get::<IntGridLayer>(layer).unwrap();
+        assert_eq!(int_grid_layer.get(GridCoords::new(1, 1)), Some(3));
+        assert_eq!(int_grid_layer.get(GridCoords::new(0, 0)), Some(2));
+
+        // Emptied cells read as empty, like cells without an entity
+        world.get_mut::<IntGridCell>(cell).unwrap().value = 0;
+        schedule.run(&mut world);
+
+        assert_eq!(
+            system_state
+                .get(&world)
+                .int_grid_value(layer, GridCoords::new(1, 1)),
+            None
+        );
+        assert_eq!(
+            world
+                .get::<IntGridLayer>(layer)
+                .unwrap()
+                .get(GridCoords::new(1, 1)),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn grid_coords_use_the_grid_size_and_layer_offset() {
+        let mut world = World::new();
+
+        let level = world
+            .spawn(GlobalTransform::from_translation(Vec3::new(100., 50., 0.)))
+            .id();
+
+        // A 4x2 layer with a 16px grid, 32px tiles, and an offset of (8, -4) pixels in LDtk.
+        // Its translation includes the adjustments for its tile size, so it doesn't match its grid.
+        let layer = world
+            .spawn((
+                LayerMetadata {
+                    identifier: "Terrain".to_string(),
+                    c_wid: 4,
+                    c_hei: 2,
+                    grid_size: 16,
+                    px_total_offset_x: 8,
+                    px_total_offset_y: -4,
+                    ..default()
+                },
+                TileStorage::empty(TilemapSize { x: 4, y: 2 }),
+                GlobalTransform::from_translation(Vec3::new(124., 38., 0.)),
+            ))
+            .set_parent(level)
+            .id();
+
+        let mut system_state: SystemState<LdtkGrid> = SystemState::new(&mut world);
+        let ldtk_grid = system_state.get(&world);
+
+        assert_eq!(
+            ldtk_grid.grid_coords(layer, Vec2::new(108., 54.)),
+            Some(GridCoords::new(0, 0))
+        );
+        assert_eq!(
+            ldtk_grid.grid_coords(layer, Vec2::new(171., 85.)),
+            Some(GridCoords::new(3, 1))
+        );
+        assert_eq!(
+            ldtk_grid.grid_coords(layer, Vec2::new(124., 60.)),
+            Some(GridCoords::new(1, 0))
+        );
+        assert_eq!(ldtk_grid.grid_coords(layer, Vec2::new(107., 54.)), None);
+        assert_eq!(ldtk_grid.grid_coords(layer, Vec2::new(172., 54.)), None);
+        assert_eq!(ldtk_grid.grid_coords(layer, Vec2::new(108., 86.)), None);
+    }
+}
@@ -0,0 +1,488 @@
+use crate::ldtk::{
+    Definitions, FieldDefinition, FieldInstance, FieldValue, LayerInstance, LdtkJson, Level, Type,
+    WorldLayout,
+};
+use bevy::prelude::*;
+use serde::Serialize;
+use serde_json::{json, ser::PrettyFormatter, Serializer, Value};
+
+impl LdtkJson {
+    /// Recalculates the fields of this project that LDtk derives from other data.
+    ///
+    /// This includes the double-underscore fields that duplicate definition data, like
+    /// `__identifier`, `__type`, `__gridSize`, and `__pxTotalOffsetX`, as well as positional data
+    /// like `__cWid`, `__grid`, and `__worldX`.
+    /// `intGridCsv`s are resized to match their layer's dimensions, and the `realEditorValues`
+    /// of field instances are updated to match their `__value`s.
+    ///
+    /// Should be used after editing a project, before writing it back to a file.
+    /// This is done automatically by [`LdtkJson::to_ldtk_string`].
+    pub fn update_derived_fields(&mut self) {
+        let defs = &self.defs;
+
+        for level in self.levels.iter_mut() {
+            level.update_derived_fields(defs, self.world_layout);
+        }
+
+        for world in self.worlds.iter_mut() {
+            for level in world.levels.iter_mut() {
+                level.update_derived_fields(defs, world.world_layout);
+            }
+        }
+    }
+
+    /// Serializes this project into the contents of a `.ldtk` file, after updating its
+    /// [derived fields](LdtkJson::update_derived_fields).
+    ///
+    /// For projects with external levels, the levels in the project are written without layer
+    /// instances, like LDtk does.
+    /// The levels themselves can be serialized with [`Level::to_ldtkl_string`].
+    pub fn to_ldtk_string(&self) -> Result<String, serde_json::Error> {
+        let mut ldtk_json = self.clone();
+        ldtk_json.update_derived_fields();
+
+        if ldtk_json.external_levels {
+            ldtk_json
+                .levels
+                .iter_mut()
+                .chain(
+                    ldtk_json
+                        .worlds
+                        .iter_mut()
+                        .flat_map(|world| world.levels.iter_mut()),
+                )
+                .for_each(|level| level.layer_instances = None);
+        }
+
+        to_pretty_string(&ldtk_json)
+    }
+}
+
+impl Level {
+    /// Recalculates the fields of this level that LDtk derives from other data.
+    ///
+    /// `world_layout` is the layout of the world containing the level, which determines if
+    /// entities have world coordinates.
+    ///
+    /// See [`LdtkJson::update_derived_fields`] for more details.
+    pub fn update_derived_fields(&mut self, defs: &Definitions, world_layout: Option<WorldLayout>) {
+        for field_instance in self.field_instances.iter_mut() {
+            if let Some(field_definition) = defs
+                .level_fields
+                .iter()
+                .find(|field_definition| field_definition.uid == field_instance.def_uid)
+            {
+                field_instance.update_derived_fields(field_definition);
+            }
+        }
+
+        let has_world_coords = matches!(
+            world_layout,
+            Some(WorldLayout::Free) | Some(WorldLayout::GridVania)
+        );
+        let world_coords = IVec2::new(self.world_x, self.world_y);
+        let level_size = IVec2::new(self.px_wid, self.px_hei);
+
+        for layer_instance in self.layer_instances.iter_mut().flatten() {
+            layer_instance.level_id = self.uid;
+            layer_instance.update_derived_fields(defs, level_size);
+
+            let grid_size = layer_instance.grid_size.max(1);
+
+            for entity_instance in layer_instance.entity_instances.iter_mut() {
+                let Some(entity_definition) = defs
+                    .entities
+                    .iter()
+                    .find(|entity_definition| entity_definition.uid == entity_instance.def_uid)
+                else {
+                    continue;
+                };
+
+                entity_instance.identifier = entity_definition.identifier.clone();
+                entity_instance.pivot =
+                    Vec2::new(entity_definition.pivot_x, entity_definition.pivot_y);
+                entity_instance.tags = entity_definition.tags.clone();
+
+                // Entities on the right or bottom edge of a cell are considered part of that cell
+                let edge_adjustment = IVec2::new(
+                    (entity_definition.pivot_x == 1.) as i32,
+                    (entity_definition.pivot_y == 1.) as i32,
+                );
+                entity_instance.grid =
+                    (entity_instance.px - edge_adjustment).div_euclid(IVec2::splat(grid_size));
+
+                let world_px = has_world_coords.then_some(world_coords + entity_instance.px);
+                entity_instance.world_x = world_px.map(|world_px| world_px.x);
+                entity_instance.world_y = world_px.map(|world_px| world_px.y);
+
+                for field_instance in entity_instance.field_instances.iter_mut() {
+                    if let Some(field_definition) = entity_definition
+                        .field_defs
+                        .iter()
+                        .find(|field_definition| field_definition.uid == field_instance.def_uid)
+                    {
+                        field_instance.update_derived_fields(field_definition);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Serializes this level into the contents of an external `.ldtkl` file, after updating its
+    /// [derived fields](Level::update_derived_fields).
+    pub fn to_ldtkl_string(
+        &self,
+        defs: &Definitions,
+        world_layout: Option<WorldLayout>,
+    ) -> Result<String, serde_json::Error> {
+        let mut level = self.clone();
+        level.update_derived_fields(defs, world_layout);
+
+        to_pretty_string(&level)
+    }
+}
+
+impl LayerInstance {
+    fn update_derived_fields(&mut self, defs: &Definitions, level_size: IVec2) {
+        let Some(layer_definition) = defs
+            .layers
+            .iter()
+            .find(|layer_definition| layer_definition.uid == self.layer_def_uid)
+        else {
+            return;
+        };
+
+        let (old_c_wid, old_c_hei) = (self.c_wid, self.c_hei);
+
+        self.identifier = layer_definition.identifier.clone();
+        self.layer_instance_type = layer_definition.purple_type;
+        self.grid_size = layer_definition.grid_size;
+        self.opacity = layer_definition.display_opacity;
+
+        let grid_size = self.grid_size.max(1);
+        self.c_wid = (level_size.x + grid_size - 1) / grid_size;
+        self.c_hei = (level_size.y + grid_size - 1) / grid_size;
+
+        self.px_total_offset_x = self.px_offset_x + layer_definition.px_offset_x;
+        self.px_total_offset_y = self.px_offset_y + layer_definition.px_offset_y;
+
+        self.tileset_def_uid = self
+            .override_tileset_uid
+            .or(layer_definition.tileset_def_uid);
+        self.tileset_rel_path = self.tileset_def_uid.and_then(|tileset_def_uid| {
+            defs.tilesets
+                .iter()
+                .find(|tileset_definition| tileset_definition.uid == tileset_def_uid)?
+                .rel_path
+                .clone()
+        });
+
+        if self.layer_instance_type == Type::IntGrid
+            && self.int_grid_csv.len() != (self.c_wid * self.c_hei) as usize
+        {
+            // Keep every cell at the same coordinates, cropping or filling with 0s as needed
+            let mut int_grid_csv = vec![0; (self.c_wid * self.c_hei) as usize];
+
+            for (i, value) in self.int_grid_csv.iter().enumerate() {
+                let x = i as i32 % old_c_wid.max(1);
+                let y = i as i32 / old_c_wid.max(1);
+
+                if x < self.c_wid && y < self.c_hei && y < old_c_hei {
+                    int_grid_csv[(y * self.c_wid + x) as usize] = *value;
+                }
+            }
+
+            self.int_grid_csv = int_grid_csv;
+        }
+    }
+}
+
+impl FieldInstance {
+    fn update_derived_fields(&mut self, field_definition: &FieldDefinition) {
+        self.identifier = field_definition.identifier.clone();
+        self.field_instance_type = field_definition.field_definition_type.clone();
+
+        let Some(editor_values) = editor_values(&self.value) else {
+            return;
+        };
+
+        let default_value = default_editor_value(field_definition);
+
+        let mut changed =
+            field_definition.is_array && editor_values.len() != self.real_editor_values.len();
+
+        let real_editor_values = editor_values
+            .into_iter()
+            .enumerate()
+            .map(|(i, editor_value)| {
+                let current_value = self.real_editor_values.get(i).cloned().flatten();
+
+                let unchanged = match (&current_value, &editor_value) {
+                    (Some(current_value), Some(editor_value)) => {
+                        json_eq(current_value, editor_value)
+                    }
+                    // LDtk stores null, or nothing, for values that equal the field's default
+                    (None, Some(editor_value)) => default_value
+                        .as_ref()
+                        .is_some_and(|default_value| json_eq(default_value, editor_value)),
+                    (_, None) => current_value.is_none(),
+                };
+
+                if unchanged {
+                    current_value
+                } else {
+                    changed = true;
+                    editor_value
+                }
+            })
+            .collect();
+
+        if changed {
+            self.real_editor_values = real_editor_values;
+        }
+    }
+}
+
+/// Converts a [`FieldValue`] to the `realEditorValues` LDtk uses to store it.
+///
+/// Returns `None` for colors and tiles, whose editor values aren't updated.
+fn editor_values(value: &FieldValue) -> Option<Vec<Option<Value>>> {
+    fn editor_value(id: &str, param: impl Serialize) -> Value {
+        json!({ "id": id, "params": [param] })
+    }
+
+    // Round-trip floats through their shortest representation, so 0.1 isn't stored as
+    // 0.10000000149011612
+    fn float(value: f32) -> Value {
+        editor_value(
+            "V_Float",
+            value.to_string().parse::<f64>().unwrap_or_default(),
+        )
+    }
+
+    fn string(value: &str) -> Value {
+        editor_value("V_String", value)
+    }
+
+    fn point(value: IVec2) -> Value {
+        string(&format!("{},{}", value.x, value.y))
+    }
+
+    let int = |value: i32| editor_value("V_Int", value);
+    let bool = |value: bool| editor_value("V_Bool", value);
+
+    Some(match value {
+        FieldValue::Int(value) => vec![value.map(int)],
+        FieldValue::Float(value) => vec![value.map(float)],
+        FieldValue::Bool(value) => vec![Some(bool(*value))],
+        FieldValue::String(value) | FieldValue::FilePath(value) | FieldValue::Enum(value) => {
+            vec![value.as_deref().map(string)]
+        }
+        FieldValue::EntityRef(value) => {
+            vec![value.as_ref().map(|value| string(&value.entity_iid))]
+        }
+        FieldValue::Point(value) => vec![value.map(point)],
+        FieldValue::Ints(values) => values.iter().map(|value| value.map(int)).collect(),
+        FieldValue::Floats(values) => values.iter().map(|value| value.map(float)).collect(),
+        FieldValue::Bools(values) => values.iter().map(|value| Some(bool(*value))).collect(),
+        FieldValue::Strings(values) | FieldValue::FilePaths(values) | FieldValue::Enums(values) => {
+            values
+                .iter()
+                .map(|value| value.as_deref().map(string))
+                .collect()
+        }
+        FieldValue::EntityRefs(values) => values
+            .iter()
+            .map(|value| value.as_ref().map(|value| string(&value.entity_iid)))
+            .collect(),
+        FieldValue::Points(values) => values.iter().map(|value| value.map(point)).collect(),
+        FieldValue::Color(_)
+        | FieldValue::Colors(_)
+        | FieldValue::Tile(_)
+        | FieldValue::Tiles(_) => return None,
+    })
+}
+
+/// The editor value LDtk uses for a field when it's stored as null.
+fn default_editor_value(field_definition: &FieldDefinition) -> Option<Value> {
+    if field_definition.default_override.is_some() {
+        return field_definition.default_override.clone();
+    }
+
+    if field_definition.can_be_null {
+        return None;
+    }
+
+    match field_definition.purple_type.as_str() {
+        "F_Int" => Some(json!({ "id": "V_Int", "params": [0] })),
+        "F_Float" => Some(json!({ "id": "V_Float", "params": [0] })),
+        "F_Bool" => Some(json!({ "id": "V_Bool", "params": [false] })),
+        _ => None,
+    }
+}
+
+/// Compares JSON values, treating numbers as equal if they are at `f32` precision.
+fn json_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => {
+            a.as_f64().map(|a| a as f32) == b.as_f64().map(|b| b as f32)
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_eq(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| json_eq(a, b)))
+        }
+        _ => a == b,
+    }
+}
+
+/// Serializes with tab indentation, like LDtk does.
+fn to_pretty_string(value: &impl Serialize) -> Result<String, serde_json::Error> {
+    let mut bytes = Vec::new();
+    value.serialize(&mut Serializer::with_formatter(
+        &mut bytes,
+        PrettyFormatter::with_indent(b"\t"),
+    ))?;
+
+    Ok(String::from_utf8(bytes).expect("serde_json should only produce valid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldtk::ReferenceToAnEntityInstance;
+
+    const PROJECTS: [&str; 6] = [
+        include_str!("../../assets/collectathon.ldtk"),
+        include_str!("../../assets/field_instances.ldtk"),
+        include_str!("../../assets/my_project.ldtk"),
+        include_str!("../../assets/tile-based-game.ldtk"),
+        include_str!("../../assets/Typical_2D_platformer_example.ldtk"),
+        include_str!("../../assets/WorldMap_Free_layout.ldtk"),
+    ];
+
+    #[test]
+    fn unedited_projects_round_trip() {
+        for project in PROJECTS {
+            let ldtk_json: LdtkJson = serde_json::from_str(project).unwrap();
+            let saved = ldtk_json.to_ldtk_string().unwrap();
+
+            let original: Value = serde_json::from_str(project).unwrap();
+            let saved: Value = serde_json::from_str(&saved).unwrap();
+
+            assert!(json_eq(&original, &saved));
+            assert_eq!(
+                serde_json::from_str::<LdtkJson>(&project).unwrap(),
+                ldtk_json
+            );
+        }
+    }
+
+    #[test]
+    fn edits_update_derived_fields() {
+        let mut ldtk_json: LdtkJson = serde_json::from_str(PROJECTS[1]).unwrap();
+
+        let level = &mut ldtk_json.levels[0];
+        level.px_wid += 16;
+
+        let layer_instance = level
+            .layer_instances
+            .as_mut()
+            .unwrap()
+            .iter_mut()
+            .find(|layer_instance| !layer_instance.entity_instances.is_empty())
+            .unwrap();
+        layer_instance.px_offset_x = 4;
+
+        let entity_instance = &mut layer_instance.entity_instances[0];
+        entity_instance.px += IVec2::new(32, 16);
+        let expected_px = entity_instance.px;
+
+        let field_instance = entity_instance
+            .field_instances
+            .iter_mut()
+            .find(|field_instance| matches!(field_instance.value, FieldValue::Int(_)))
+            .unwrap();
+        field_instance.value = FieldValue::Int(Some(7));
+
+        let int_grid_layer = level
+            .layer_instances
+            .as_ref()
+            .unwrap()
+            .iter()
+            .position(|layer_instance| layer_instance.layer_instance_type == Type::IntGrid)
+            .unwrap();
+        let old_c_wid = level.layer_instances.as_ref().unwrap()[int_grid_layer].c_wid;
+        let old_int_grid_csv = level.layer_instances.as_ref().unwrap()[int_grid_layer]
+            .int_grid_csv
+            .clone();
+
+        ldtk_json.update_derived_fields();
+
+        let level = &ldtk_json.levels[0];
+        let layer_instances = level.layer_instances.as_ref().unwrap();
+
+        let int_grid_layer = &layer_instances[int_grid_layer];
+        let grid_size = int_grid_layer.grid_size;
+        assert_eq!(int_grid_layer.c_wid, level.px_wid / grid_size);
+        assert_eq!(
+            int_grid_layer.int_grid_csv.len(),
+            (int_grid_layer.c_wid * int_grid_layer.c_hei) as usize
+        );
+        assert_eq!(int_grid_layer.int_grid_csv[1], old_int_grid_csv[1]);
+        assert_eq!(
+            int_grid_layer.int_grid_csv[int_grid_layer.c_wid as usize],
+            old_int_grid_csv[old_c_wid as usize]
+        );
+
+        let layer_instance = layer_instances
+            .iter()
+            .find(|layer_instance| !layer_instance.entity_instances.is_empty())
+            .unwrap();
+        assert_eq!(layer_instance.px_total_offset_x, 4);
+
+        let entity_instance = &layer_instance.entity_instances[0];
+        assert_eq!(entity_instance.px, expected_px);
+        assert_eq!(
+            entity_instance.grid,
+            (expected_px - entity_instance.pivot.as_ivec2())
+                .div_euclid(IVec2::splat(layer_instance.grid_size))
+        );
+
+        let field_instance = entity_instance
+            .field_instances
+            .iter()
+            .find(|field_instance| matches!(field_instance.value, FieldValue::Int(_)))
+            .unwrap();
+        assert_eq!(
+            field_instance.real_editor_values,
+            vec![Some(json!({ "id": "V_Int", "params": [7] }))]
+        );
+    }
+
+    #[test]
+    fn editor_values_match_ldtk_format() {
+        assert_eq!(
+            editor_values(&FieldValue::Points(vec![Some(IVec2::new(32, 9)), None])),
+            Some(vec![
+                Some(json!({ "id": "V_String", "params": ["32,9"] })),
+                None
+            ])
+        );
+        assert_eq!(
+            editor_values(&FieldValue::EntityRef(Some(ReferenceToAnEntityInstance {
+                entity_iid: "iid".to_string(),
+                ..default()
+            }))),
+            Some(vec![Some(json!({ "id": "V_String", "params": ["iid"] }))])
+        );
+        assert_eq!(
+            editor_values(&FieldValue::Float(Some(0.1))),
+            Some(vec![Some(json!({ "id": "V_Float", "params": [0.1] }))])
+        );
+        assert_eq!(editor_values(&FieldValue::Colors(Vec::new())), None);
+    }
+}
@@ -0,0 +1,179 @@
+//! Contains [`LdtkEnum`] trait, implemented by Rust enums generated from LDtk enum definitions.
+use crate::ldtk::FieldValue;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Errors related to the [`LdtkEnum`] trait.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum LdtkEnumError {
+    /// The value is not one of the enum's values.
+    #[error("{value} is not a value of the {enum_identifier} enum")]
+    UnknownValue {
+        enum_identifier: &'static str,
+        value: String,
+    },
+    /// The [`FieldValue`] is not [`FieldValue::Enum`].
+    #[error("expected a value of the {enum_identifier} enum, but the field is not an enum")]
+    WrongFieldType { enum_identifier: &'static str },
+    /// The [`FieldValue`] is [`FieldValue::Enum`], but the value is null.
+    #[error("expected a value of the {enum_identifier} enum, but the value is null")]
+    UnexpectedNull { enum_identifier: &'static str },
+}
+
+/// A Rust enum mirroring an [`EnumDefinition`] of an LDtk project.
+///
+/// Rather than implementing this manually, you will usually generate these enums with the
+/// `ldtk_enums!` macro (requires the `derive` feature).
+/// It reads the given project at compile time, relative to your crate's `Cargo.toml`, and emits
+/// one enum per enum definition in the project, including external enums.
+/// Each enum implements this trait, [`FromStr`], [`std::fmt::Display`], and
+/// `TryFrom<&FieldValue>`.
+///
+/// Since the enums are generated from the project itself, a typo in an enum value becomes a
+/// compile error, and changes made to the enum definitions in LDtk are reflected on the next
+/// build.
+/// ```
+/// use bevy_ecs_ldtk::prelude::*;
+///
+/// mod enums {
+///     bevy_ecs_ldtk::ldtk_enums!("assets/field_instances.ldtk");
+/// }
+///
+/// use enums::Equipment;
+///
+/// assert_eq!("Sword".parse::<Equipment>(), Ok(Equipment::Sword));
+/// assert_eq!(Equipment::Shield.to_string(), "Shield");
+/// assert_eq!(Equipment::IDENTIFIER, "Equipment");
+/// assert_eq!(
+///     Equipment::try_from(&FieldValue::Enum(Some("Helmet".to_string()))),
+///     Ok(Equipment::Helmet)
+/// );
+/// ```
+///
+/// Misspelled values do not compile:
+/// ```compile_fail
+/// mod enums {
+///     bevy_ecs_ldtk::ldtk_enums!("assets/field_instances.ldtk");
+/// }
+///
+/// let _ = enums::Equipment::Helmett;
+/// ```
+///
+/// Typed enum fields can be accessed with the `ldtk_enum` methods of
+/// [`LdtkFields`](crate::ldtk::ldtk_fields::LdtkFields), like
+/// [`get_ldtk_enum_field`](crate::ldtk::ldtk_fields::LdtkFields::get_ldtk_enum_field).
+/// Tile enum tags can be parsed with
+/// [`TileEnumTags::parse_tags`](crate::prelude::TileEnumTags::parse_tags).
+///
+/// [`EnumDefinition`]: crate::ldtk::EnumDefinition
+pub trait LdtkEnum: FromStr<Err = LdtkEnumError> + Copy + 'static {
+    /// The identifier of the LDtk enum definition.
+    const IDENTIFIER: &'static str;
+
+    /// All values of the enum, in the order they are defined in LDtk.
+    const VALUES: &'static [Self];
+
+    /// The LDtk identifier of this value.
+    fn as_str(&self) -> &'static str;
+
+    /// Parse a non-null [`FieldValue::Enum`] as this enum.
+    ///
+    /// This is what the generated `TryFrom<&FieldValue>` implementations use.
+    ///
+    /// # Errors
+    /// - returns [`LdtkEnumError::WrongFieldType`] if the value is not [`FieldValue::Enum`].
+    /// - returns [`LdtkEnumError::UnexpectedNull`] if the value is null.
+    /// - returns [`LdtkEnumError::UnknownValue`] if the value is not one of this enum's values.
+    fn from_field_value(value: &FieldValue) -> Result<Self, LdtkEnumError> {
+        match value {
+            FieldValue::Enum(Some(value)) => value.parse(),
+            FieldValue::Enum(None) => Err(LdtkEnumError::UnexpectedNull {
+                enum_identifier: Self::IDENTIFIER,
+            }),
+            _ => Err(LdtkEnumError::WrongFieldType {
+                enum_identifier: Self::IDENTIFIER,
+            }),
+        }
+    }
+
+    /// Parse an LDtk identifier as one of this enum's values.
+    ///
+    /// This is what the generated [`FromStr`] implementations use.
+    ///
+    /// # Errors
+    /// - returns [`LdtkEnumError::UnknownValue`] if the value is not one of this enum's values.
+    fn from_identifier(identifier: &str) -> Result<Self, LdtkEnumError> {
+        Self::VALUES
+            .iter()
+            .find(|value| value.as_str() == identifier)
+            .copied()
+            .ok_or_else(|| LdtkEnumError::UnknownValue {
+                enum_identifier: Self::IDENTIFIER,
+                value: identifier.to_string(),
+            })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum Fruit {
+        Apple,
+        Banana,
+    }
+
+    impl LdtkEnum for Fruit {
+        const IDENTIFIER: &'static str = "Fruit";
+        const VALUES: &'static [Self] = &[Fruit::Apple, Fruit::Banana];
+
+        fn as_str(&self) -> &'static str {
+            match self {
+                Fruit::Apple => "Apple",
+                Fruit::Banana => "Banana",
+            }
+        }
+    }
+
+    impl FromStr for Fruit {
+        type Err = LdtkEnumError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Self::from_identifier(s)
+        }
+    }
+
+    #[test]
+    fn from_identifier_matches_values() {
+        assert_eq!("Apple".parse::<Fruit>(), Ok(Fruit::Apple));
+        assert_eq!("Banana".parse::<Fruit>(), Ok(Fruit::Banana));
+        assert_eq!(
+            "apple".parse::<Fruit>(),
+            Err(LdtkEnumError::UnknownValue {
+                enum_identifier: "Fruit",
+                value: "apple".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn from_field_value_requires_non_null_enum() {
+        assert_eq!(
+            Fruit::from_field_value(&FieldValue::Enum(Some("Banana".to_string()))),
+            Ok(Fruit::Banana)
+        );
+        assert_eq!(
+            Fruit::from_field_value(&FieldValue::Enum(None)),
+            Err(LdtkEnumError::UnexpectedNull {
+                enum_identifier: "Fruit"
+            })
+        );
+        assert_eq!(
+            Fruit::from_field_value(&FieldValue::String(Some("Banana".to_string()))),
+            Err(LdtkEnumError::WrongFieldType {
+                enum_identifier: "Fruit"
+            })
+        );
+    }
+}
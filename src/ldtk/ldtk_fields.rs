@@ -1,7 +1,10 @@
 //! Contains [`LdtkFields`] trait, providing convenience methods for accessing field instances.
+//!
+//! Also contains the [`FromLdtkField`] and [`LdtkFieldValue`] traits, used for extracting fields
+//! into arbitrary types, such as by `#[derive(LdtkFields)]`.
 use crate::ldtk::{
-    all_some_iter::AllSomeIter, EntityInstance, FieldInstance, FieldValue, Level,
-    ReferenceToAnEntityInstance, TilesetRectangle,
+    all_some_iter::AllSomeIter, ldtk_enum::LdtkEnum, EntityInstance, FieldInstance, FieldValue,
+    Level, ReferenceToAnEntityInstance, TilesetRectangle,
 };
 use bevy::prelude::*;
 use paste::paste;
@@ -19,8 +22,17 @@ pub enum LdtkFieldsError {
     /// The field instance exists and is the correct variant, but the value is null.
     #[error("found {identifier} field of the correct type, but the value is null")]
     UnexpectedNull { identifier: String },
+    /// The field instance is an enum, but the value is not part of the expected [`LdtkEnum`].
+    #[error("found {identifier} field, but {value} is not a value of the expected enum")]
+    UnknownEnumValue { identifier: String, value: String },
 }
 
+/// Every error encountered while converting an item's fields into a struct with
+/// `#[derive(LdtkFields)]`.
+#[derive(Debug, PartialEq, Eq, Error)]
+#[error("could not convert fields: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+pub struct LdtkFieldsErrors(pub Vec<LdtkFieldsError>);
+
 /// Base macro for generating a method that accesses a field instance and unwraps its [FieldValue]
 /// variant into the assigned type, or errors if it isn't the correct variant.
 ///
@@ -149,6 +161,62 @@ macro_rules! create_plural_fields_methods {
 }
 
 /// Convenience methods for accessing field instances.
+///
+/// # Derive macro
+/// With the `derive` feature, `#[derive(LdtkFields)]` converts all of an entity's or level's
+/// fields into a struct at once, by implementing `TryFrom<&EntityInstance>` and `TryFrom<&Level>`.
+///
+/// Each struct field is extracted from the LDtk field with the same name using
+/// [`get_typed_field`](LdtkFields::get_typed_field), so its type can be any [`FromLdtkField`].
+/// Use `Option<T>` for nullable fields, `Vec<T>` for arrays, and `Vec<Option<T>>` for arrays
+/// with nullable elements.
+/// A different LDtk field can be used with `#[ldtk(rename = "...")]`, and
+/// `#[ldtk(default)]` uses the [Default] value if the field is missing or null.
+///
+/// If any fields can't be converted, all of the errors are returned in [`LdtkFieldsErrors`].
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ecs_ldtk::{ldtk::FieldInstance, prelude::*};
+///
+/// #[derive(Debug, PartialEq, LdtkFields)]
+/// struct Enemy {
+///     #[ldtk(rename = "HP")]
+///     health: i32,
+///     name: Option<String>,
+///     patrol: Vec<IVec2>,
+///     #[ldtk(default)]
+///     speed: f32,
+/// }
+///
+/// # fn field(identifier: &str, value: FieldValue) -> FieldInstance {
+/// #     FieldInstance {
+/// #         identifier: identifier.to_string(),
+/// #         value,
+/// #         field_instance_type: String::new(),
+/// #         tile: None,
+/// #         def_uid: 0,
+/// #         real_editor_values: Vec::new(),
+/// #     }
+/// # }
+/// let entity_instance = EntityInstance {
+///     field_instances: vec![
+///         field("HP", FieldValue::Int(Some(3))),
+///         field("name", FieldValue::String(None)),
+///         field("patrol", FieldValue::Points(vec![Some(IVec2::new(1, 2))])),
+///     ],
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     Enemy::try_from(&entity_instance),
+///     Ok(Enemy {
+///         health: 3,
+///         name: None,
+///         patrol: vec![IVec2::new(1, 2)],
+///         speed: 0.,
+///     })
+/// );
+/// ```
 pub trait LdtkFields {
     /// Immutable accessor for this item's field instances, by reference.
     fn field_instances(&self) -> &[FieldInstance];
@@ -204,6 +272,105 @@ pub trait LdtkFields {
     create_plural_fields_methods!(Tiles, TilesetRectangle);
     create_plural_fields_methods!(EntityRefs, ReferenceToAnEntityInstance);
     create_plural_fields_methods!(Points, IVec2);
+
+    /// Get this item's Enum field value for the given identifier, parsed as the [`LdtkEnum`] `E`.
+    ///
+    /// # Errors
+    /// - returns [`LdtkFieldsError::FieldNotFound`] if no field with the given identifier exists.
+    /// - returns [`LdtkFieldsError::WrongFieldType`] if the field is not [`FieldValue::Enum`].
+    /// - returns [`LdtkFieldsError::UnknownEnumValue`] if the value is not a value of `E`.
+    fn get_maybe_ldtk_enum_field<E: LdtkEnum>(
+        &self,
+        identifier: &str,
+    ) -> Result<Option<E>, LdtkFieldsError>
+    where
+        Self: Sized,
+    {
+        self.get_maybe_enum_field(identifier)?
+            .as_ref()
+            .map(|value| parse_ldtk_enum(identifier, value))
+            .transpose()
+    }
+
+    /// Get this item's non-null Enum field value for the given identifier, parsed as the
+    /// [`LdtkEnum`] `E`.
+    ///
+    /// # Errors
+    /// - returns [`LdtkFieldsError::FieldNotFound`] if no field with the given identifier exists.
+    /// - returns [`LdtkFieldsError::WrongFieldType`] if the field is not [`FieldValue::Enum`].
+    /// - returns [`LdtkFieldsError::UnexpectedNull`] if the field is null.
+    /// - returns [`LdtkFieldsError::UnknownEnumValue`] if the value is not a value of `E`.
+    fn get_ldtk_enum_field<E: LdtkEnum>(&self, identifier: &str) -> Result<E, LdtkFieldsError>
+    where
+        Self: Sized,
+    {
+        parse_ldtk_enum(identifier, self.get_enum_field(identifier)?)
+    }
+
+    /// Get this item's Enums field value for the given identifier, parsed as the [`LdtkEnum`] `E`.
+    ///
+    /// # Errors
+    /// - returns [`LdtkFieldsError::FieldNotFound`] if no field with the given identifier exists.
+    /// - returns [`LdtkFieldsError::WrongFieldType`] if the field is not [`FieldValue::Enums`].
+    /// - returns [`LdtkFieldsError::UnknownEnumValue`] if any value is not a value of `E`.
+    fn get_maybe_ldtk_enums_field<E: LdtkEnum>(
+        &self,
+        identifier: &str,
+    ) -> Result<Vec<Option<E>>, LdtkFieldsError>
+    where
+        Self: Sized,
+    {
+        self.get_maybe_enums_field(identifier)?
+            .iter()
+            .map(|value| {
+                value
+                    .as_ref()
+                    .map(|value| parse_ldtk_enum(identifier, value))
+                    .transpose()
+            })
+            .collect()
+    }
+
+    /// Get this item's non-null Enums field value for the given identifier, parsed as the
+    /// [`LdtkEnum`] `E`.
+    ///
+    /// # Errors
+    /// - returns [`LdtkFieldsError::FieldNotFound`] if no field with the given identifier exists.
+    /// - returns [`LdtkFieldsError::WrongFieldType`] if the field is not [`FieldValue::Enums`].
+    /// - returns [`LdtkFieldsError::UnexpectedNull`] if any value is null.
+    /// - returns [`LdtkFieldsError::UnknownEnumValue`] if any value is not a value of `E`.
+    fn get_ldtk_enums_field<E: LdtkEnum>(&self, identifier: &str) -> Result<Vec<E>, LdtkFieldsError>
+    where
+        Self: Sized,
+    {
+        self.iter_enums_field(identifier)?
+            .map(|value| parse_ldtk_enum(identifier, value))
+            .collect()
+    }
+
+    /// Get this item's field value for the given identifier, converted to any [`FromLdtkField`]
+    /// type.
+    ///
+    /// Nullable fields can be accessed as [`Option`]s, and array fields as [`Vec`]s.
+    ///
+    /// # Errors
+    /// - returns [`LdtkFieldsError::FieldNotFound`] if no field with the given identifier exists.
+    /// - returns any error of [`FromLdtkField::from_ldtk_field`].
+    fn get_typed_field<T: FromLdtkField>(&self, identifier: &str) -> Result<T, LdtkFieldsError>
+    where
+        Self: Sized,
+    {
+        T::from_ldtk_field(identifier, self.get_field(identifier)?)
+    }
+}
+
+fn parse_ldtk_enum<E: LdtkEnum>(identifier: &str, value: &str) -> Result<E, LdtkFieldsError> {
+    value
+        .parse()
+        .map_err(|_| LdtkFieldsError::UnknownEnumValue {
+            identifier: identifier.to_string(),
+            value: value.to_string(),
+        })
 }
 
 impl LdtkFields for EntityInstance {
@@ -218,6 +385,187 @@ impl LdtkFields for Level {
     }
 }
 
+/// Types that can be extracted from a single [`FieldValue`], or from the elements of an array
+/// [`FieldValue`].
+///
+/// Implemented for the types of every [`FieldValue`] variant, and for every [`LdtkEnum`].
+/// [`String`] can be extracted from String, FilePath, and Enum fields.
+pub trait LdtkFieldValue: Sized {
+    /// Extract a nullable value from a non-array field.
+    ///
+    /// # Errors
+    /// - returns [`LdtkFieldsError::WrongFieldType`] if the value is the wrong variant.
+    fn from_field_value(
+        identifier: &str,
+        value: &FieldValue,
+    ) -> Result<Option<Self>, LdtkFieldsError>;
+
+    /// Extract nullable values from an array field.
+    ///
+    /// # Errors
+    /// - returns [`LdtkFieldsError::WrongFieldType`] if the value is the wrong variant.
+    fn from_field_values(
+        identifier: &str,
+        value: &FieldValue,
+    ) -> Result<Vec<Option<Self>>, LdtkFieldsError>;
+}
+
+/// Implements [`LdtkFieldValue`] for a type stored in nullable [`FieldValue`] variants.
+macro_rules! impl_ldtk_field_value {
+    ($type:ty, $($variant:ident / $plural:ident),+) => {
+        impl LdtkFieldValue for $type {
+            fn from_field_value(
+                identifier: &str,
+                value: &FieldValue,
+            ) -> Result<Option<Self>, LdtkFieldsError> {
+                match value {
+                    $(FieldValue::$variant(value))|+ => Ok(value.clone()),
+                    _ => Err(LdtkFieldsError::WrongFieldType {
+                        identifier: identifier.to_string(),
+                    }),
+                }
+            }
+
+            fn from_field_values(
+                identifier: &str,
+                value: &FieldValue,
+            ) -> Result<Vec<Option<Self>>, LdtkFieldsError> {
+                match value {
+                    $(FieldValue::$plural(values))|+ => Ok(values.clone()),
+                    _ => Err(LdtkFieldsError::WrongFieldType {
+                        identifier: identifier.to_string(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+/// Implements [`LdtkFieldValue`] for a type stored in non-nullable [`FieldValue`] variants.
+macro_rules! impl_just_ldtk_field_value {
+    ($type:ty, $variant:ident / $plural:ident) => {
+        impl LdtkFieldValue for $type {
+            fn from_field_value(
+                identifier: &str,
+                value: &FieldValue,
+            ) -> Result<Option<Self>, LdtkFieldsError> {
+                match value {
+                    FieldValue::$variant(value) => Ok(Some(*value)),
+                    _ => Err(LdtkFieldsError::WrongFieldType {
+                        identifier: identifier.to_string(),
+                    }),
+                }
+            }
+
+            fn from_field_values(
+                identifier: &str,
+                value: &FieldValue,
+            ) -> Result<Vec<Option<Self>>, LdtkFieldsError> {
+                match value {
+                    FieldValue::$plural(values) => Ok(values.iter().copied().map(Some).collect()),
+                    _ => Err(LdtkFieldsError::WrongFieldType {
+                        identifier: identifier.to_string(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+impl_ldtk_field_value!(i32, Int / Ints);
+impl_ldtk_field_value!(f32, Float / Floats);
+impl_just_ldtk_field_value!(bool, Bool / Bools);
+impl_ldtk_field_value!(String, String / Strings, FilePath / FilePaths, Enum / Enums);
+impl_just_ldtk_field_value!(Color, Color / Colors);
+impl_ldtk_field_value!(TilesetRectangle, Tile / Tiles);
+impl_ldtk_field_value!(ReferenceToAnEntityInstance, EntityRef / EntityRefs);
+impl_ldtk_field_value!(IVec2, Point / Points);
+
+impl<E: LdtkEnum> LdtkFieldValue for E {
+    fn from_field_value(
+        identifier: &str,
+        value: &FieldValue,
+    ) -> Result<Option<Self>, LdtkFieldsError> {
+        match value {
+            FieldValue::Enum(value) => value
+                .as_ref()
+                .map(|value| parse_ldtk_enum(identifier, value))
+                .transpose(),
+            _ => Err(LdtkFieldsError::WrongFieldType {
+                identifier: identifier.to_string(),
+            }),
+        }
+    }
+
+    fn from_field_values(
+        identifier: &str,
+        value: &FieldValue,
+    ) -> Result<Vec<Option<Self>>, LdtkFieldsError> {
+        match value {
+            FieldValue::Enums(values) => values
+                .iter()
+                .map(|value| {
+                    value
+                        .as_ref()
+                        .map(|value| parse_ldtk_enum(identifier, value))
+                        .transpose()
+                })
+                .collect(),
+            _ => Err(LdtkFieldsError::WrongFieldType {
+                identifier: identifier.to_string(),
+            }),
+        }
+    }
+}
+
+/// Types that a whole [`FieldValue`] can be converted into.
+///
+/// For any [`LdtkFieldValue`] `T`...
+/// - `T` is extracted from non-null, non-array fields.
+/// - `Option<T>` is extracted from nullable, non-array fields.
+/// - `Vec<T>` is extracted from array fields with no null elements.
+/// - `Vec<Option<T>>` is extracted from array fields with nullable elements.
+pub trait FromLdtkField: Sized {
+    /// Convert the value of the field with the given identifier.
+    ///
+    /// # Errors
+    /// - returns [`LdtkFieldsError::WrongFieldType`] if the value is the wrong variant.
+    /// - returns [`LdtkFieldsError::UnexpectedNull`] if an unexpected null value is found.
+    /// - returns [`LdtkFieldsError::UnknownEnumValue`] if an enum value can't be parsed.
+    fn from_ldtk_field(identifier: &str, value: &FieldValue) -> Result<Self, LdtkFieldsError>;
+}
+
+impl<T: LdtkFieldValue> FromLdtkField for T {
+    fn from_ldtk_field(identifier: &str, value: &FieldValue) -> Result<Self, LdtkFieldsError> {
+        T::from_field_value(identifier, value)?.ok_or_else(|| LdtkFieldsError::UnexpectedNull {
+            identifier: identifier.to_string(),
+        })
+    }
+}
+
+impl<T: LdtkFieldValue> FromLdtkField for Option<T> {
+    fn from_ldtk_field(identifier: &str, value: &FieldValue) -> Result<Self, LdtkFieldsError> {
+        T::from_field_value(identifier, value)
+    }
+}
+
+impl<T: LdtkFieldValue> FromLdtkField for Vec<T> {
+    fn from_ldtk_field(identifier: &str, value: &FieldValue) -> Result<Self, LdtkFieldsError> {
+        T::from_field_values(identifier, value)?
+            .into_iter()
+            .collect::<Option<_>>()
+            .ok_or_else(|| LdtkFieldsError::UnexpectedNull {
+                identifier: identifier.to_string(),
+            })
+    }
+}
+
+impl<T: LdtkFieldValue> FromLdtkField for Vec<Option<T>> {
+    fn from_ldtk_field(identifier: &str, value: &FieldValue) -> Result<Self, LdtkFieldsError> {
+        T::from_field_values(identifier, value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -683,4 +1031,133 @@ mod tests {
         "Points",
         [IVec2::default(), IVec2::default()]
     );
+
+    #[test]
+    fn ldtk_enum_fields_are_parsed() {
+        use crate::ldtk::ldtk_enum::tests::Fruit;
+
+        let field_instances = vec![
+            field_instance_from_value("Fruit", FieldValue::Enum(Some("Apple".to_string()))),
+            field_instance_from_value("NoFruit", FieldValue::Enum(None)),
+            field_instance_from_value("Vegetable", FieldValue::Enum(Some("Carrot".to_string()))),
+            field_instance_from_value(
+                "Fruits",
+                FieldValue::Enums(vec![Some("Banana".to_string()), Some("Apple".to_string())]),
+            ),
+            field_instance_from_value(
+                "FruitsNullable",
+                FieldValue::Enums(vec![None, Some("Banana".to_string())]),
+            ),
+        ];
+
+        assert_eq!(
+            field_instances.get_ldtk_enum_field("Fruit"),
+            Ok(Fruit::Apple)
+        );
+        assert_eq!(
+            field_instances.get_maybe_ldtk_enum_field::<Fruit>("NoFruit"),
+            Ok(None)
+        );
+        assert_eq!(
+            field_instances.get_ldtk_enum_field::<Fruit>("NoFruit"),
+            Err(LdtkFieldsError::UnexpectedNull {
+                identifier: "NoFruit".to_string()
+            })
+        );
+        assert_eq!(
+            field_instances.get_ldtk_enum_field::<Fruit>("Vegetable"),
+            Err(LdtkFieldsError::UnknownEnumValue {
+                identifier: "Vegetable".to_string(),
+                value: "Carrot".to_string()
+            })
+        );
+        assert_eq!(
+            field_instances.get_ldtk_enum_field::<Fruit>("Fruits"),
+            Err(LdtkFieldsError::WrongFieldType {
+                identifier: "Fruits".to_string()
+            })
+        );
+
+        assert_eq!(
+            field_instances.get_ldtk_enums_field("Fruits"),
+            Ok(vec![Fruit::Banana, Fruit::Apple])
+        );
+        assert_eq!(
+            field_instances.get_maybe_ldtk_enums_field("FruitsNullable"),
+            Ok(vec![None, Some(Fruit::Banana)])
+        );
+        assert_eq!(
+            field_instances.get_ldtk_enums_field::<Fruit>("FruitsNullable"),
+            Err(LdtkFieldsError::UnexpectedNull {
+                identifier: "FruitsNullable".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn typed_fields_are_converted() {
+        use crate::ldtk::ldtk_enum::tests::Fruit;
+
+        let field_instances = sample_field_instances();
+
+        assert_eq!(field_instances.get_typed_field("IntSome"), Ok(0));
+        assert_eq!(
+            field_instances.get_typed_field::<Option<i32>>("IntNone"),
+            Ok(None)
+        );
+        assert_eq!(
+            field_instances.get_typed_field::<i32>("IntNone"),
+            Err(LdtkFieldsError::UnexpectedNull {
+                identifier: "IntNone".to_string()
+            })
+        );
+        assert_eq!(
+            field_instances.get_typed_field::<f32>("IntSome"),
+            Err(LdtkFieldsError::WrongFieldType {
+                identifier: "IntSome".to_string()
+            })
+        );
+        assert_eq!(field_instances.get_typed_field("Bool"), Ok(true));
+        assert_eq!(
+            field_instances.get_typed_field("FilePathSome"),
+            Ok("three".to_string())
+        );
+        assert_eq!(
+            field_instances.get_typed_field("EnumSome"),
+            Ok("Four".to_string())
+        );
+        assert_eq!(
+            field_instances.get_typed_field::<Fruit>("EnumSome"),
+            Err(LdtkFieldsError::UnknownEnumValue {
+                identifier: "EnumSome".to_string(),
+                value: "Four".to_string()
+            })
+        );
+
+        assert_eq!(
+            field_instances.get_typed_field("Ints"),
+            Ok(vec![Some(6), Some(7)])
+        );
+        assert_eq!(field_instances.get_typed_field("Ints"), Ok(vec![6, 7]));
+        assert_eq!(
+            field_instances.get_typed_field("IntsNullable"),
+            Ok(vec![None, Some(5)])
+        );
+        assert_eq!(
+            field_instances.get_typed_field::<Vec<i32>>("IntsNullable"),
+            Err(LdtkFieldsError::UnexpectedNull {
+                identifier: "IntsNullable".to_string()
+            })
+        );
+        assert_eq!(
+            field_instances.get_typed_field("Bools"),
+            Ok(vec![false, true])
+        );
+        assert_eq!(
+            field_instances.get_typed_field::<i32>("Missing"),
+            Err(LdtkFieldsError::FieldNotFound {
+                identifier: "Missing".to_string()
+            })
+        );
+    }
 }
@@ -24,6 +24,10 @@
 //! 10. All urls in docs have been changed to hyperlinks with `<>`
 //! 11. `From<&EntityInstance>` implemented for [`EntityInstance`]
 //! 12. [`LayerInstance::layer_instance_type`] changed from [`String`] to [`Type`].
+//! 13. [`LdtkJson::header`] and [`LdtkJsonHeader`] added for the `__header__` field, which isn't
+//!     part of the JSON schema.
+//! 14. Deprecated [Option] fields, and [`EntityInstance::world_x`]/[`EntityInstance::world_y`],
+//!     are skipped when serializing if they are [None], since LDtk omits them in this case.
 
 use bevy::{
     prelude::{Color, Component, IVec2, Vec2},
@@ -37,10 +41,13 @@ use crate::prelude::LdtkEntity;
 
 pub mod all_some_iter;
 mod color;
+mod derived_fields;
 #[cfg(test)]
 pub mod fake;
 mod field_instance;
+#[cfg(feature = "render")]
 mod impl_definitions;
+pub mod ldtk_enum;
 pub mod ldtk_fields;
 pub mod loaded_level;
 pub mod raw_level_accessor;
@@ -55,10 +62,14 @@ pub use field_instance::*;
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Reflect)]
 #[serde(rename_all = "camelCase")]
 pub struct LdtkJson {
+    /// Information about the file and the LDtk version that wrote it.
+    #[serde(rename = "__header__", skip_serializing_if = "Option::is_none")]
+    pub header: Option<LdtkJsonHeader>,
+
     /// This object is not actually used by LDtk. It ONLY exists to force explicit references to
     /// all types, to make sure QuickType finds them and integrate all of them. Otherwise,
     /// Quicktype will drop types that are not explicitely used.
-    #[serde(rename = "__FORCED_REFS")]
+    #[serde(rename = "__FORCED_REFS", skip_serializing_if = "Option::is_none")]
     pub forced_refs: Option<ForcedRefs>,
 
     /// LDtk application build identifier.<br/>  This is only used to identify the LDtk version
@@ -124,6 +135,7 @@ pub struct LdtkJson {
 
     /// **WARNING**: this deprecated value is no longer exported since version 0.9.3  Replaced
     /// by: `imageExportMode`
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub export_png: Option<bool>,
 
     /// If TRUE, a Tiled compatible file will also be generated along with the LDtk JSON file
@@ -213,6 +225,26 @@ pub struct LdtkJson {
     pub worlds: Vec<World>,
 }
 
+/// Header written at the top of every project file, describing the file and the LDtk version
+/// that wrote it.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, Eq, PartialEq, Reflect)]
+#[serde(rename_all = "camelCase")]
+pub struct LdtkJsonHeader {
+    pub file_type: String,
+
+    pub app: String,
+
+    pub doc: String,
+
+    pub schema: String,
+
+    pub app_author: String,
+
+    pub app_version: String,
+
+    pub url: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, Eq, PartialEq, Reflect)]
 pub struct LdtkCustomCommand {
     pub command: String,
@@ -354,6 +386,7 @@ pub struct EntityDefinition {
 
     /// **WARNING**: this deprecated value is no longer exported since version 1.2.0  Replaced
     /// by: `tileRect`
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tile_id: Option<i32>,
 
     pub tile_opacity: f32,
@@ -745,7 +778,7 @@ pub struct EnumDefinition {
 pub struct EnumValueDefinition {
     /// **WARNING**: this deprecated value is no longer exported since version 1.4.0  Replaced
     /// by: `tileRect`
-    #[serde(rename = "__tileSrcRect")]
+    #[serde(rename = "__tileSrcRect", skip_serializing_if = "Option::is_none")]
     pub tile_src_rect: Option<Vec<i32>>,
 
     /// Optional color
@@ -756,6 +789,7 @@ pub struct EnumValueDefinition {
 
     /// **WARNING**: this deprecated value is no longer exported since version 1.4.0  Replaced
     /// by: `tileRect`
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tile_id: Option<i32>,
 
     /// Optional tileset rectangle to represents this value
@@ -776,6 +810,7 @@ pub struct LayerDefinition {
 
     /// **WARNING**: this deprecated value is no longer exported since version 1.2.0  Replaced
     /// by: `tilesetDefUid`
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub auto_tileset_def_uid: Option<i32>,
 
     pub auto_tiles_killed_by_other_layer_uid: Option<i32>,
@@ -889,6 +924,7 @@ pub struct AutoLayerRuleGroup {
     pub biome_requirement_mode: i32,
 
     /// *This field was removed in 1.0.0 and should no longer be used.*
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub collapsed: Option<bool>,
 
     pub color: Option<String>,
@@ -964,6 +1000,7 @@ pub struct AutoLayerRuleDefinition {
 
     /// **WARNING**: this deprecated value is no longer exported since version 1.5.0  Replaced
     /// by: `tileRectsIds`
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tile_ids: Option<Vec<i32>>,
 
     /// Defines how tileIds array is used Possible values: `Single`, `Stamp`
@@ -1287,11 +1324,11 @@ pub struct EntityInstance {
     pub tile: Option<TilesetRectangle>,
 
     /// X world coordinate in pixels. Only available in GridVania or Free world layouts.
-    #[serde(rename = "__worldX")]
+    #[serde(rename = "__worldX", skip_serializing_if = "Option::is_none")]
     pub world_x: Option<i32>,
 
     /// Y world coordinate in pixels Only available in GridVania or Free world layouts.
-    #[serde(rename = "__worldY")]
+    #[serde(rename = "__worldY", skip_serializing_if = "Option::is_none")]
     pub world_y: Option<i32>,
 
     /// Reference of the **Entity definition** UID
@@ -1420,6 +1457,7 @@ pub struct LayerInstance {
 
     /// **WARNING**: this deprecated value is no longer exported since version 1.0.0  Replaced
     /// by: `intGridCsv`
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub int_grid: Option<Vec<IntGridValueInstance>>,
 
     /// A list of all values in the IntGrid layer, stored in CSV format (Comma Separated
@@ -1633,6 +1671,7 @@ pub struct NeighbourLevel {
 
     /// **WARNING**: this deprecated value is no longer exported since version 1.2.0  Replaced
     /// by: `levelIid`
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub level_uid: Option<i32>,
 }
 
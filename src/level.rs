@@ -2,15 +2,19 @@
 
 use crate::{
     app::{
-        LdtkEntity, LdtkEntityMap, LdtkIntCellMap, PhantomLdtkEntity, PhantomLdtkEntityTrait,
-        PhantomLdtkIntCell, PhantomLdtkIntCellTrait,
+        IntGridCellContext, LdtkEntity, LdtkEntitySpawnContext, LdtkRegistrations,
+        PhantomLdtkEntity, PhantomLdtkIntCell,
     },
     components::*,
     ldtk::{
         loaded_level::LoadedLevel, EntityDefinition, EnumTagValue, LayerDefinition, LayerInstance,
         LevelBackgroundPosition, TileCustomMetadata, TileInstance, TilesetDefinition, Type,
     },
-    resources::{IntGridRendering, LdtkSettings, LevelBackground},
+    pathfinding::NavGrid,
+    resources::{
+        EntityIidMap, HiddenLayers, IntGridMerging, IntGridRendering, IntGridStorage, LdtkSettings,
+        LevelBackground, LevelSpawnBudget, WorldViewLayers,
+    },
     tile_makers::*,
     utils::*,
 };
@@ -20,9 +24,12 @@ use seek_ecs_tilemap::{
     map::{
         TilemapGridSize, TilemapId, TilemapSize, TilemapSpacing, TilesetTexture, TilemapTileSize,
     },
-    tiles::{TilePos, TileStorage},
+    tiles::{TileBundle, TilePos, TileStorage},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
 };
-use std::collections::{HashMap, HashSet};
 
 use seek_ecs_tilemap::TilemapBundle;
 
@@ -139,11 +146,12 @@ fn insert_spatial_bundle_for_layer_tiles(
     commands: &mut Commands,
     storage: &TileStorage,
     size: &TilemapSize,
+    rows: Range<u32>,
     grid_size: i32,
     tilemap_id: TilemapId,
 ) {
     for x in 0..size.x {
-        for y in 0..size.y {
+        for y in rows.clone() {
             let tile_pos = TilePos { x, y };
             let tile_entity = storage.get(&tile_pos);
 
@@ -170,26 +178,32 @@ fn insert_tile_metadata_for_layer(
     for tile in grid_tiles {
         let grid_coords = tile_to_grid_coords(tile, layer_instance.c_hei, layer_instance.grid_size);
 
-        let tile_entity = tile_storage.get(&grid_coords.into()).unwrap();
+        // Tiles may be missing when a cell was excluded or was spawned without a tile
+        let Some(tile_entity) = tile_storage.get(&grid_coords.into()) else {
+            continue;
+        };
 
         insert_metadata_to_tile(commands, tile, tile_entity, metadata_map, enum_tags_map);
     }
 }
 
-fn layer_grid_tiles(grid_tiles: Vec<TileInstance>) -> Vec<Vec<TileInstance>> {
-    let mut layer = Vec::new();
-    let mut overflow = Vec::new();
-    for tile in grid_tiles {
-        if layer.iter().any(|t: &TileInstance| t.px == tile.px) {
-            overflow.push(tile);
-        } else {
-            layer.push(tile);
+/// Splits tiles into layers without overlapping tiles, returning the indices of each layer's tiles.
+///
+/// There's always at least one layer, even if there are no tiles.
+fn layer_grid_tiles<'a>(
+    grid_tiles: impl IntoIterator<Item = (usize, &'a TileInstance)>,
+) -> Vec<Vec<usize>> {
+    let mut layered_grid_tiles = vec![Vec::new()];
+    let mut overlapping = HashMap::new();
+    for (index, tile) in grid_tiles {
+        // Each tile goes on the layer above the previous tile in the same position
+        let layer: &mut usize = overlapping.entry(tile.px).or_default();
+        if *layer == layered_grid_tiles.len() {
+            layered_grid_tiles.push(Vec::new());
         }
-    }
 
-    let mut layered_grid_tiles = vec![layer];
-    if !overflow.is_empty() {
-        layered_grid_tiles.extend(layer_grid_tiles(overflow));
+        layered_grid_tiles[*layer].push(index);
+        *layer += 1;
     }
 
     layered_grid_tiles
@@ -202,6 +216,372 @@ fn tile_in_layer_bounds(tile: &TileInstance, layer_instance: &LayerInstance) ->
         && tile.px.y < (layer_instance.c_hei * layer_instance.grid_size)
 }
 
+/// Greedy-meshes the cells of an `IntGrid` layer into rectangles of cells with the same value.
+///
+/// Only cells whose value passes `merges_value` are merged.
+/// Rectangles are grown to the right first, and then upwards.
+pub(crate) fn merge_int_grid_cells(
+    int_grid_csv: &[i32],
+    width: i32,
+    height: i32,
+    merges_value: impl Fn(i32) -> bool,
+) -> Vec<MergedIntGridRegion> {
+    if int_grid_csv.len() != (width * height) as usize {
+        return Vec::new();
+    }
+
+    // int_grid_csv starts at the top of the layer, GridCoords start at the bottom
+    let value_at = |x: i32, y: i32| int_grid_csv[((height - 1 - y) * width + x) as usize];
+    let index = |x: i32, y: i32| (y * width + x) as usize;
+
+    let mut merged = vec![false; int_grid_csv.len()];
+    let mut regions = Vec::new();
+
+    for y in 0..height {
+        for x in 0..width {
+            let value = value_at(x, y);
+
+            if merged[index(x, y)] || !merges_value(value) {
+                continue;
+            }
+
+            let mergeable =
+                |x: i32, y: i32, merged: &[bool]| !merged[index(x, y)] && value_at(x, y) == value;
+
+            let mut right = x + 1;
+            while right < width && mergeable(right, y, &merged) {
+                right += 1;
+            }
+
+            let mut top = y + 1;
+            while top < height && (x..right).all(|x| mergeable(x, top, &merged)) {
+                top += 1;
+            }
+
+            for merged_y in y..top {
+                for merged_x in x..right {
+                    merged[index(merged_x, merged_y)] = true;
+                }
+            }
+
+            regions.push(MergedIntGridRegion {
+                value,
+                rect: IRect::new(x, y, right, top),
+            });
+        }
+    }
+
+    regions
+}
+
+/// Traces the outlines of connected groups of `IntGrid` cells with the same value.
+///
+/// Only cells whose value passes `merges_value` are outlined.
+/// Vertices are grid corners, where `(0, 0)` is the bottom-left corner of the bottom-left cell.
+/// Every outline is wound so that its cells are on the left of each edge, and collinear vertices are
+/// removed.
+pub(crate) fn trace_int_grid_outlines(
+    int_grid_csv: &[i32],
+    width: i32,
+    height: i32,
+    merges_value: impl Fn(i32) -> bool,
+) -> Vec<(i32, Vec<IVec2>)> {
+    if int_grid_csv.len() != (width * height) as usize {
+        return Vec::new();
+    }
+
+    let value_at = |coords: IVec2| {
+        (coords.x >= 0 && coords.y >= 0 && coords.x < width && coords.y < height)
+            .then(|| int_grid_csv[((height - 1 - coords.y) * width + coords.x) as usize])
+    };
+
+    // Directed boundary edges of every value, keyed by their starting corner.
+    // Starting corners are also recorded in scan order so the output is deterministic.
+    let mut edges: HashMap<i32, HashMap<IVec2, Vec<IVec2>>> = HashMap::new();
+    let mut edge_starts: Vec<(i32, IVec2)> = Vec::new();
+
+    for y in 0..height {
+        for x in 0..width {
+            let cell = IVec2::new(x, y);
+            let value = int_grid_csv[((height - 1 - y) * width + x) as usize];
+
+            if !merges_value(value) {
+                continue;
+            }
+
+            // (neighbor direction, edge start, edge end), counter-clockwise around the cell
+            let sides = [
+                (IVec2::NEG_Y, IVec2::new(0, 0), IVec2::new(1, 0)),
+                (IVec2::X, IVec2::new(1, 0), IVec2::new(1, 1)),
+                (IVec2::Y, IVec2::new(1, 1), IVec2::new(0, 1)),
+                (IVec2::NEG_X, IVec2::new(0, 1), IVec2::new(0, 0)),
+            ];
+
+            for (direction, start, end) in sides {
+                if value_at(cell + direction) != Some(value) {
+                    edges
+                        .entry(value)
+                        .or_default()
+                        .entry(cell + start)
+                        .or_default()
+                        .push(cell + end);
+                    edge_starts.push((value, cell + start));
+                }
+            }
+        }
+    }
+
+    let mut outlines = Vec::new();
+
+    for (value, start) in edge_starts {
+        let value_edges = edges
+            .get_mut(&value)
+            .expect("edges should exist for recorded starts");
+
+        if value_edges[&start].is_empty() {
+            continue;
+        }
+
+        let mut points = vec![start];
+        let mut current = start;
+        let mut direction: Option<IVec2> = None;
+
+        loop {
+            let ends = value_edges
+                .get_mut(&current)
+                .expect("boundary edges should always form closed loops");
+
+            // Where groups of cells touch diagonally, there are two ways to continue.
+            // Turning left keeps the outline on the same group of cells.
+            let next_index = direction
+                .and_then(|direction| {
+                    ends.iter()
+                        .position(|end| *end - current == direction.perp())
+                })
+                .unwrap_or(ends.len() - 1);
+
+            let next = ends.swap_remove(next_index);
+
+            direction = Some(next - current);
+            current = next;
+
+            if current == start {
+                break;
+            }
+
+            points.push(current);
+        }
+
+        // Remove vertices in the middle of straight lines
+        let simplified = (0..points.len())
+            .filter(|i| {
+                let previous = points[(i + points.len() - 1) % points.len()];
+                let next = points[(i + 1) % points.len()];
+
+                (points[*i] - previous).signum() != (next - points[*i]).signum()
+            })
+            .map(|i| points[i])
+            .collect();
+
+        outlines.push((value, simplified));
+    }
+
+    outlines
+}
+
+/// Spawns [MergedIntGridRegion]s and [MergedIntGridOutline]s as children of an `IntGrid` layer.
+///
+/// `layer_origin` is the translation of the layer relative to its level, not including the layer
+/// offset.
+fn spawn_merged_int_grid_regions(
+    commands: &mut Commands,
+    layer_entity: Entity,
+    layer_instance: &LayerInstance,
+    int_grid_merging: &IntGridMerging,
+    excluded_values: &[i32],
+    layer_origin: Vec2,
+) {
+    let IntGridMerging::Enabled { outlines, .. } = int_grid_merging else {
+        return;
+    };
+
+    let merges_value =
+        |value: i32| int_grid_merging.merges_value(value) && !excluded_values.contains(&value);
+
+    let corner_to_translation =
+        |corner: Vec2| corner * layer_instance.grid_size as f32 - layer_origin;
+
+    let regions = merge_int_grid_cells(
+        &layer_instance.int_grid_csv,
+        layer_instance.c_wid,
+        layer_instance.c_hei,
+        merges_value,
+    );
+
+    commands.entity(layer_entity).with_children(|layer| {
+        for region in regions {
+            let center = (region.rect.min + region.rect.max).as_vec2() / 2.;
+
+            layer.spawn((
+                region,
+                SpatialBundle::from_transform(Transform::from_translation(
+                    corner_to_translation(center).extend(0.),
+                )),
+            ));
+        }
+
+        if *outlines {
+            for (value, corners) in trace_int_grid_outlines(
+                &layer_instance.int_grid_csv,
+                layer_instance.c_wid,
+                layer_instance.c_hei,
+                merges_value,
+            ) {
+                let points = corners
+                    .into_iter()
+                    .map(|corner| corner_to_translation(corner.as_vec2()))
+                    .collect();
+
+                layer.spawn((
+                    MergedIntGridOutline { value, points },
+                    SpatialBundle::default(),
+                ));
+            }
+        }
+    });
+}
+
+/// Tracks how many more tiles and entities can spawn in the current frame.
+///
+/// See [LevelSpawnBudget].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct FrameSpawnBudget {
+    remaining: Option<usize>,
+    spent: bool,
+}
+
+impl FrameSpawnBudget {
+    pub(crate) fn new(level_spawn_budget: LevelSpawnBudget) -> Self {
+        let remaining = match level_spawn_budget {
+            LevelSpawnBudget::Unlimited => None,
+            LevelSpawnBudget::PerFrame(budget) => Some(budget),
+        };
+
+        FrameSpawnBudget {
+            remaining,
+            spent: false,
+        }
+    }
+
+    /// Spends the given cost if it fits into the remaining budget, returning whether it did.
+    ///
+    /// The first cost of the frame always fits, so that spawning can't stall on large layers.
+    fn try_spend(&mut self, cost: usize) -> bool {
+        match &mut self.remaining {
+            None => true,
+            Some(remaining) if cost <= *remaining || !self.spent => {
+                *remaining = remaining.saturating_sub(cost);
+                self.spent = true;
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
+    /// Spends the budget on as many of the `total` units of a layer as fit, continuing from
+    /// `spawned`, and returns the range of units to spawn.
+    ///
+    /// Returns [None] if not even one unit fits.
+    /// Layers without units still cost one unit, for the layer itself.
+    fn try_spend_range(
+        &mut self,
+        spawned: usize,
+        total: usize,
+        unit_cost: usize,
+    ) -> Option<Range<usize>> {
+        if self.remaining.is_none() {
+            return Some(spawned..total);
+        }
+
+        if spawned == total {
+            return self.try_spend(unit_cost).then_some(spawned..total);
+        }
+
+        let mut end = spawned;
+        while end < total && self.try_spend(unit_cost) {
+            end += 1;
+        }
+
+        (end > spawned).then_some(spawned..end)
+    }
+}
+
+/// Iterates through the layers of a level that should be spawned, in spawning order.
+pub(crate) fn layers_to_spawn<'a>(
+    layer_instances: &'a [LayerInstance],
+    layer_definition_map: &'a HashMap<i32, &LayerDefinition>,
+    ldtk_settings: &'a LdtkSettings,
+) -> impl Iterator<Item = &'a LayerInstance> {
+    layer_instances
+        .iter()
+        .filter(|layer| {
+            !ldtk_settings.exclusions.excludes_layer(
+                layer,
+                layer_definition_map.get(&layer.layer_def_uid).copied(),
+            )
+        })
+        .filter(|layer| {
+            ldtk_settings.hidden_layers != HiddenLayers::Excluded
+                || !layer_is_hidden(
+                    layer,
+                    layer_definition_map.get(&layer.layer_def_uid).copied(),
+                    ldtk_settings,
+                )
+        })
+        .rev()
+}
+
+/// Returns true if the layer is hidden in its level in LDtk.
+///
+/// With [WorldViewLayers::Hidden], layers that aren't rendered in the world view are also hidden.
+fn layer_is_hidden(
+    layer_instance: &LayerInstance,
+    layer_definition: Option<&LayerDefinition>,
+    ldtk_settings: &LdtkSettings,
+) -> bool {
+    !layer_instance.visible
+        || (ldtk_settings.world_view_layers == WorldViewLayers::Hidden
+            && layer_definition
+                .is_some_and(|layer_definition| !layer_definition.render_in_world_view))
+}
+
+/// The [SpatialBundle] of a layer entity, which is hidden if the layer is hidden in LDtk.
+fn layer_spatial_bundle(
+    layer_instance: &LayerInstance,
+    layer_definition: &LayerDefinition,
+    translation: Vec3,
+    ldtk_settings: &LdtkSettings,
+) -> SpatialBundle {
+    let visibility = if ldtk_settings.hidden_layers == HiddenLayers::Hidden
+        && layer_is_hidden(layer_instance, Some(layer_definition), ldtk_settings)
+    {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+
+    SpatialBundle {
+        visibility,
+        transform: Transform::from_translation(translation),
+        ..default()
+    }
+}
+
+/// Spawns the given level as children of the `ldtk_entity`.
+///
+/// Levels can be spawned across multiple calls, resuming from the given `progress`.
+/// Layers are spawned until the `budget` runs out.
+/// Returns true if the level has completely spawned.
 #[allow(clippy::too_many_arguments)]
 pub fn spawn_level(
     level: LoadedLevel,
@@ -210,8 +590,7 @@ pub fn spawn_level(
     asset_server: &AssetServer,
     images: &Assets<Image>,
     texture_atlases: &mut Assets<TextureAtlasLayout>,
-    ldtk_entity_map: &LdtkEntityMap,
-    ldtk_int_cell_map: &LdtkIntCellMap,
+    ldtk_registrations: &LdtkRegistrations,
     entity_definition_map: &HashMap<i32, &EntityDefinition>,
     layer_definition_map: &HashMap<i32, &LayerDefinition>,
     tileset_map: &HashMap<i32, Handle<Image>>,
@@ -220,12 +599,28 @@ pub fn spawn_level(
     worldly_set: HashSet<Worldly>,
     ldtk_entity: Entity,
     ldtk_settings: &LdtkSettings,
-) {
+    entity_iid_map: &mut EntityIidMap,
+    progress: &mut LevelSpawnProgress,
+    budget: &mut FrameSpawnBudget,
+) -> bool {
     let layer_instances = level.layer_instances();
 
-    let mut layer_z = 0;
+    let mut layer_z = progress.layer_z;
+
+    let logic_only = ldtk_settings.level_spawn_mode.is_logic_only();
+    let layer_storage = ldtk_settings.int_grid_storage == IntGridStorage::Layer;
+
+    // The background is spawned on the first call for this level, so the level bundle is too
+    if !progress.background_spawned {
+        if let Some(ldtk_level) = ldtk_registrations.levels.get_for_level(level.identifier()) {
+            ldtk_level.evaluate(&mut commands.entity(ldtk_entity), &level, asset_server);
+        }
+    }
 
-    if ldtk_settings.level_background == LevelBackground::Rendered {
+    if !progress.background_spawned
+        && !logic_only
+        && ldtk_settings.level_background == LevelBackground::Rendered
+    {
         let translation = Vec3::new(*level.px_wid() as f32, *level.px_hei() as f32, 0.) / 2.;
 
         let background_entity = commands
@@ -268,75 +663,147 @@ pub fn spawn_level(
         }
     }
 
-    for layer_instance in layer_instances
-        .iter()
-        .filter(|layer| {
-            !ldtk_settings
-                .exclusions
-                .layer_identifiers
-                .contains(&layer.identifier)
-        })
-        .rev()
+    progress.background_spawned = true;
+
+    for layer_instance in layers_to_spawn(layer_instances, layer_definition_map, ldtk_settings)
+        .skip(progress.spawned_layers)
     {
+        let layer_definition = layer_definition_map
+            .get(&layer_instance.layer_def_uid)
+            .expect("Encountered layer without definition");
+
+        let ldtk_layer = ldtk_registrations
+            .layers
+            .get_for_layer(&layer_instance.identifier);
+
         let layer_offset = Vec2::new(
             layer_instance.px_total_offset_x as f32,
             -layer_instance.px_total_offset_y as f32,
         );
 
+        let level_center = Vec2::new(*level.px_wid() as f32, *level.px_hei() as f32) / 2.;
+
         match layer_instance.layer_instance_type {
             Type::Entities => {
-                let layer_entity = commands
-                    .spawn(SpatialBundle::from_transform(Transform::from_translation(
-                        layer_offset.extend(layer_z as f32),
-                    )))
-                    .insert(LayerMetadata::from(layer_instance))
-                    .insert(Name::new(layer_instance.identifier.to_owned()))
-                    .with_children(|commands| {
-                        for entity_instance in &layer_instance.entity_instances {
-                            let transform = calculate_transform_from_entity_instance(
-                                entity_instance,
-                                entity_definition_map,
-                                *level.px_hei(),
-                            );
-                            // Note: entities do not seem to be affected visually by layer offsets in
-                            // the editor, so no layer offset is added to the transform here.
+                let entity_instances: Vec<&EntityInstance> = layer_instance
+                    .entity_instances
+                    .iter()
+                    .filter(|entity_instance| {
+                        !ldtk_settings.exclusions.excludes_entity(entity_instance)
+                    })
+                    .collect();
 
-                            let (tileset, tileset_definition) = match &entity_instance.tile {
-                                Some(t) => (
-                                    tileset_map.get(&t.tileset_uid),
-                                    tileset_definition_map.get(&t.tileset_uid).copied(),
-                                ),
-                                None => (None, None),
-                            };
+                let Some(chunk) =
+                    budget.try_spend_range(progress.layer_spawned, entity_instances.len(), 1)
+                else {
+                    progress.layer_z = layer_z;
+                    return false;
+                };
 
-                            let predicted_worldly = Worldly::bundle_entity(
-                                entity_instance,
+                let layer_entity = match progress.layer_entities.first() {
+                    Some(layer_entity) => *layer_entity,
+                    None => {
+                        let layer_translation = layer_offset.extend(layer_z as f32);
+
+                        let layer_entity = commands
+                            .spawn(layer_spatial_bundle(
                                 layer_instance,
-                                tileset,
-                                tileset_definition,
+                                layer_definition,
+                                layer_translation,
+                                ldtk_settings,
+                            ))
+                            .insert(LayerMetadata::from(layer_instance))
+                            .insert(Name::new(layer_instance.identifier.to_owned()))
+                            .id();
+
+                        if let Some(parallax) = LdtkParallax::from_layer_definition(
+                            layer_definition,
+                            layer_translation,
+                            level_center,
+                        ) {
+                            commands.entity(layer_entity).insert(parallax);
+                        }
+
+                        if let Some(ldtk_layer) = ldtk_layer {
+                            ldtk_layer.evaluate(
+                                &mut commands.entity(layer_entity),
+                                layer_instance,
+                                layer_definition,
                                 asset_server,
-                                texture_atlases,
                             );
+                        }
+
+                        commands.entity(ldtk_entity).add_child(layer_entity);
+
+                        progress.layer_entities.push(layer_entity);
+                        layer_entity
+                    }
+                };
+
+                let mut entity_systems = Vec::new();
+
+                commands.entity(layer_entity).with_children(|commands| {
+                    for entity_instance in entity_instances[chunk.clone()].iter().copied() {
+                        let transform = calculate_transform_from_entity_instance(
+                            entity_instance,
+                            entity_definition_map,
+                            *level.px_hei(),
+                        );
+                        // Note: entities do not seem to be affected visually by layer offsets in
+                        // the editor, so no layer offset is added to the transform here.
+
+                        let (tileset, tileset_definition) = match &entity_instance.tile {
+                            Some(t) => (
+                                tileset_map.get(&t.tileset_uid).filter(|_| !logic_only),
+                                tileset_definition_map.get(&t.tileset_uid).copied(),
+                            ),
+                            None => (None, None),
+                        };
+
+                        let predicted_worldly = Worldly::bundle_entity(
+                            entity_instance,
+                            layer_instance,
+                            tileset,
+                            tileset_definition,
+                            asset_server,
+                            texture_atlases,
+                        );
 
-                            if !worldly_set.contains(&predicted_worldly) {
-                                let default_ldtk_entity: Box<dyn PhantomLdtkEntityTrait> =
-                                    Box::new(PhantomLdtkEntity::<EntityInstanceBundle>::new());
-                                let mut entity_commands = commands.spawn_empty();
-
-                                // insert Name before evaluating LdtkEntitys so that user-provided
-                                // names aren't overwritten
-                                entity_commands.insert((
-                                    EntityIid::new(entity_instance.iid.to_owned()),
-                                    Name::new(entity_instance.identifier.to_owned()),
-                                ));
-
-                                ldtk_map_get_or_default(
-                                    layer_instance.identifier.clone(),
-                                    entity_instance.identifier.clone(),
-                                    &default_ldtk_entity,
-                                    ldtk_entity_map,
+                        if !worldly_set.contains(&predicted_worldly) {
+                            let default_ldtk_entity =
+                                PhantomLdtkEntity::<EntityInstanceBundle>::new();
+                            let mut entity_commands = commands.spawn_empty();
+
+                            let entity_iid = EntityIid::new(entity_instance.iid.to_owned());
+                            entity_iid_map.insert(entity_iid.clone(), entity_commands.id());
+
+                            // insert Name before evaluating LdtkEntitys so that user-provided
+                            // names aren't overwritten
+                            entity_commands.insert((
+                                entity_iid,
+                                Name::new(entity_instance.identifier.to_owned()),
+                            ));
+
+                            let entity_refs = LdtkEntityRefs::from(entity_instance);
+                            if !entity_refs.is_empty() {
+                                entity_commands.insert(entity_refs);
+                            }
+
+                            let ldtk_entity = ldtk_registrations
+                                .entities
+                                .get(&layer_instance.identifier, entity_instance)
+                                .unwrap_or(&default_ldtk_entity);
+
+                            // additive and tag registrations compose with the main registration
+                            for ldtk_entity in std::iter::once(ldtk_entity)
+                                .chain(
+                                    ldtk_registrations
+                                        .entities
+                                        .get_additive(&layer_instance.identifier, entity_instance),
                                 )
-                                .evaluate(
+                                .chain(ldtk_registrations.entities.get_tagged(entity_instance))
+                            {
+                                ldtk_entity.evaluate(
                                     &mut entity_commands,
                                     entity_instance,
                                     layer_instance,
@@ -345,17 +812,49 @@ pub fn spawn_level(
                                     asset_server,
                                     texture_atlases,
                                 );
+                            }
 
-                                entity_commands.insert(SpatialBundle {
-                                    transform,
-                                    ..default()
-                                });
+                            entity_commands.insert(SpatialBundle {
+                                transform,
+                                ..default()
+                            });
+
+                            if let Some(system_ids) = try_each_optional_permutation(
+                                layer_instance.identifier.clone(),
+                                entity_instance.identifier.clone(),
+                                |x, y| ldtk_registrations.entity_systems.get(&(x, y)),
+                            ) {
+                                let entity = entity_commands.id();
+                                entity_systems.extend(system_ids.iter().map(|system_id| {
+                                    (*system_id, entity, entity_instance, tileset)
+                                }));
                             }
                         }
-                    })
-                    .id();
+                    }
+                });
+
+                // Run entity systems once the hierarchy is in place
+                for (system_id, entity, entity_instance, tileset) in entity_systems {
+                    commands.run_system_with_input(
+                        system_id,
+                        LdtkEntitySpawnContext {
+                            entity,
+                            entity_instance: entity_instance.clone(),
+                            tileset: tileset.cloned(),
+                            layer_entity,
+                            layer_metadata: LayerMetadata::from(layer_instance),
+                            level_entity: ldtk_entity,
+                            level_iid: LevelIid::new(level.iid().clone()),
+                        },
+                    );
+                }
+
+                progress.layer_spawned = chunk.end;
+                if chunk.end < entity_instances.len() {
+                    progress.layer_z = layer_z;
+                    return false;
+                }
 
-                commands.entity(ldtk_entity).add_child(layer_entity);
                 layer_z += 1;
             }
             _ => {
@@ -390,7 +889,7 @@ pub fn spawn_level(
                 };
 
                 let spacing = match tileset_definition {
-                    Some(tileset_definition) if tileset_definition.spacing != 0 => {
+                    Some(tileset_definition) if tileset_definition.spacing != 0 && !logic_only => {
                         // TODO: Check that this is still an issue with upcoming
                         // bevy_ecs_tilemap releases
                         #[cfg(not(feature = "atlas"))]
@@ -413,13 +912,16 @@ pub fn spawn_level(
                     _ => TilemapSpacing::default(),
                 };
 
+                // Logic-only layers don't get a tilemap, so they don't need a texture
                 let texture = match (tileset_definition, int_grid_image_handle) {
-                    (Some(tileset_definition), _) => TilesetTexture::Single(
+                    _ if logic_only => None,
+                    (Some(tileset_definition), _) => Some(TilesetTexture::Single(
                         tileset_map.get(&tileset_definition.uid).unwrap().clone(),
-                    ),
-                    (None, Some(handle)) => TilesetTexture::Single(handle.clone()),
+                    )),
+                    (None, Some(handle)) => Some(TilesetTexture::Single(handle.clone())),
                     _ => {
                         warn!("unable to render tilemap layer, it has no tileset and no intgrid layers were expected");
+                        progress.spawned_layers += 1;
                         continue;
                     }
                 };
@@ -457,89 +959,232 @@ pub fn spawn_level(
                     }
                 }
 
-                let mut grid_tiles = layer_instance.grid_tiles.clone();
-                grid_tiles.extend(layer_instance.auto_layer_tiles.clone());
+                // The last tilemap of the layer may have only partially spawned in previous frames
+                let spawned_tilemaps =
+                    progress.layer_entities.len() - usize::from(progress.layer_spawned > 0);
+
+                // Cells with excluded values are spawned like empty cells, without auto-tiles
+                let excluded_values: Vec<i32> = layer_definition
+                    .int_grid_values
+                    .iter()
+                    .map(|value_definition| value_definition.value)
+                    .filter(|value| {
+                        ldtk_settings
+                            .exclusions
+                            .excludes_int_grid_value(&layer_instance.identifier, *value)
+                    })
+                    .collect();
+
+                let excludes_cell = |grid_coords: GridCoords| {
+                    !excluded_values.is_empty()
+                        && layer_instance
+                            .int_grid_csv
+                            .get(
+                                ((layer_instance.c_hei - 1 - grid_coords.y) * layer_instance.c_wid
+                                    + grid_coords.x) as usize,
+                            )
+                            .is_some_and(|value| excluded_values.contains(value))
+                };
 
-                for (i, grid_tiles) in layer_grid_tiles(grid_tiles)
-                    .into_iter()
-                    // filter out tiles that are out of bounds
-                    .map(|grid_tiles| {
-                        grid_tiles
-                            .into_iter()
-                            .filter(|tile| tile_in_layer_bounds(tile, layer_instance))
-                            .collect::<Vec<_>>()
+                // Grid tiles come first, then auto-layer tiles
+                let tile_at = |index: usize| {
+                    layer_instance.grid_tiles.get(index).unwrap_or_else(|| {
+                        &layer_instance.auto_layer_tiles[index - layer_instance.grid_tiles.len()]
                     })
-                    .enumerate()
-                {
-                    let layer_entity = commands.spawn_empty().id();
+                };
+
+                let tile_row = |index: &usize| {
+                    tile_to_grid_coords(
+                        tile_at(*index),
+                        layer_instance.c_hei,
+                        layer_instance.grid_size,
+                    )
+                    .y
+                };
 
-                    let tilemap_bundle = if layer_instance.layer_instance_type == Type::IntGrid {
+                // The tiles of each tilemap are found once per layer, and sorted by row so each
+                // chunk only visits its own rows
+                if progress.layer_tiles.is_empty() {
+                    let tiles = layer_instance
+                        .grid_tiles
+                        .iter()
+                        .chain(layer_instance.auto_layer_tiles.iter())
+                        .enumerate()
+                        .filter(|(index, tile)| {
+                            *index < layer_instance.grid_tiles.len()
+                                || !excludes_cell(tile_to_grid_coords(
+                                    tile,
+                                    layer_instance.c_hei,
+                                    layer_instance.grid_size,
+                                ))
+                        });
+
+                    progress.layer_tiles = layer_grid_tiles(tiles);
+
+                    for tile_indices in progress.layer_tiles.iter_mut() {
+                        // filter out tiles that are out of bounds
+                        tile_indices
+                            .retain(|index| tile_in_layer_bounds(tile_at(*index), layer_instance));
+                        tile_indices.sort_by_key(tile_row);
+                    }
+                }
+
+                for i in spawned_tilemaps..progress.layer_tiles.len() {
+                    // Only the first tilemap of an IntGrid layer has cells, the rest are auto-tiles
+                    if logic_only && layer_instance.layer_instance_type == Type::IntGrid && i > 0 {
+                        break;
+                    }
+
+                    // Tilemaps are spawned a few rows at a time, resuming a partially spawned one
+                    let Some(rows) = budget.try_spend_range(
+                        progress.layer_spawned,
+                        size.y as usize,
+                        size.x as usize,
+                    ) else {
+                        progress.layer_z = layer_z;
+                        return false;
+                    };
+
+                    let resuming = rows.start > 0;
+                    let rows = rows.start as u32..rows.end as u32;
+
+                    let tile_indices = &progress.layer_tiles[i];
+                    let rows_start =
+                        tile_indices.partition_point(|index| tile_row(index) < rows.start as i32);
+                    let rows_end =
+                        tile_indices.partition_point(|index| tile_row(index) < rows.end as i32);
+
+                    // Without a tilemap, the only tiles worth spawning are those with metadata
+                    let grid_tiles: Vec<TileInstance> = tile_indices[rows_start..rows_end]
+                        .iter()
+                        .map(|index| tile_at(*index))
+                        .filter(|tile| {
+                            !logic_only
+                                || metadata_map.contains_key(&tile.t)
+                                || enum_tags_map.contains_key(&tile.t)
+                        })
+                        .cloned()
+                        .collect();
+
+                    let layer_entity = match progress.layer_entities.get(i) {
+                        Some(layer_entity) => *layer_entity,
+                        None => {
+                            let layer_entity = commands.spawn_empty().id();
+                            progress.layer_entities.push(layer_entity);
+                            layer_entity
+                        }
+                    };
+
+                    // Only these positions need to be visited when the layer has no other tiles
+                    let grid_tile_positions: Vec<TilePos> = grid_tiles
+                        .iter()
+                        .map(|tile| {
+                            tile_to_grid_coords(
+                                tile,
+                                layer_instance.c_hei,
+                                layer_instance.grid_size,
+                            )
+                            .into()
+                        })
+                        .collect();
+
+                    let storage = if layer_instance.layer_instance_type == Type::IntGrid {
                         // The current spawning of IntGrid layers doesn't allow using
                         // LayerBuilder::new_batch().
                         // So, the actual LayerBuilder usage diverges greatly here
                         let mut storage = TileStorage::empty(size);
 
                         match tileset_definition {
-                            Some(_) => {
-                                set_all_tiles_with_func(
+                            // Cells without a registration don't get tiles with layer storage, so
+                            // only the tileset's tiles are needed
+                            Some(_) if !logic_only && (layer_storage || i > 0) => {
+                                set_tiles_with_func(
                                     commands,
                                     &mut storage,
-                                    size,
+                                    grid_tile_positions.iter().copied(),
                                     TilemapId(layer_entity),
                                     tile_pos_to_tile_grid_bundle_maker(
                                         tile_pos_to_transparent_tile_maker(
-                                            tile_pos_to_int_grid_with_grid_tiles_tile_maker(
+                                            tile_pos_to_tile_maker(
                                                 &grid_tiles,
-                                                &layer_instance.int_grid_csv,
-                                                layer_instance.c_wid,
                                                 layer_instance.c_hei,
                                                 layer_instance.grid_size,
-                                                i,
                                             ),
                                             layer_instance.opacity,
                                         ),
                                     ),
                                 );
                             }
-                            None => {
-                                let int_grid_value_defs = &layer_definition_map
-                                    .get(&layer_instance.layer_def_uid)
-                                    .expect("Encountered layer without definition")
-                                    .int_grid_values;
+                            Some(_) if !logic_only => {
+                                set_tile_rows_with_func(
+                                    commands,
+                                    &mut storage,
+                                    size,
+                                    rows.clone(),
+                                    TilemapId(layer_entity),
+                                    tile_pos_to_tile_grid_bundle_maker(
+                                        tile_pos_to_transparent_tile_maker(
+                                            tile_pos_to_tile_unless_excluded_maker(
+                                                tile_pos_to_int_grid_with_grid_tiles_tile_maker(
+                                                    &grid_tiles,
+                                                    &layer_instance.int_grid_csv,
+                                                    layer_instance.c_wid,
+                                                    layer_instance.c_hei,
+                                                    layer_instance.grid_size,
+                                                    i,
+                                                ),
+                                                |tile_pos| excludes_cell(tile_pos.into()),
+                                            ),
+                                            layer_instance.opacity,
+                                        ),
+                                    ),
+                                );
+                            }
+                            _ => {
+                                let int_grid_value_defs = &layer_definition.int_grid_values;
 
                                 match ldtk_settings.int_grid_rendering {
-                                    IntGridRendering::Colorful => {
-                                        set_all_tiles_with_func(
+                                    IntGridRendering::Colorful if !logic_only => {
+                                        set_tile_rows_with_func(
                                             commands,
                                             &mut storage,
                                             size,
+                                            rows.clone(),
                                             TilemapId(layer_entity),
                                             tile_pos_to_tile_grid_bundle_maker(
                                                 tile_pos_to_transparent_tile_maker(
-                                                    tile_pos_to_int_grid_colored_tile_maker(
-                                                        &layer_instance.int_grid_csv,
-                                                        int_grid_value_defs,
-                                                        layer_instance.c_wid,
-                                                        layer_instance.c_hei,
+                                                    tile_pos_to_tile_unless_excluded_maker(
+                                                        tile_pos_to_int_grid_colored_tile_maker(
+                                                            &layer_instance.int_grid_csv,
+                                                            int_grid_value_defs,
+                                                            layer_instance.c_wid,
+                                                            layer_instance.c_hei,
+                                                        ),
+                                                        |tile_pos| excludes_cell(tile_pos.into()),
                                                     ),
                                                     layer_instance.opacity,
                                                 ),
                                             ),
                                         );
                                     }
-                                    IntGridRendering::Invisible => {
-                                        set_all_tiles_with_func(
+                                    _ if layer_storage => (),
+                                    _ => {
+                                        set_tile_rows_with_func(
                                             commands,
                                             &mut storage,
                                             size,
+                                            rows.clone(),
                                             TilemapId(layer_entity),
                                             tile_pos_to_tile_grid_bundle_maker(
                                                 tile_pos_to_transparent_tile_maker(
-                                                    tile_pos_to_tile_if_int_grid_nonzero_maker(
-                                                        tile_pos_to_invisible_tile,
-                                                        &layer_instance.int_grid_csv,
-                                                        layer_instance.c_wid,
-                                                        layer_instance.c_hei,
+                                                    tile_pos_to_tile_unless_excluded_maker(
+                                                        tile_pos_to_tile_if_int_grid_nonzero_maker(
+                                                            tile_pos_to_invisible_tile,
+                                                            &layer_instance.int_grid_csv,
+                                                            layer_instance.c_wid,
+                                                            layer_instance.c_hei,
+                                                        ),
+                                                        |tile_pos| excludes_cell(tile_pos.into()),
                                                     ),
                                                     layer_instance.opacity,
                                                 ),
@@ -550,12 +1195,47 @@ pub fn spawn_level(
                             }
                         }
 
+                        // Logic-only layers keep the tileset's tiles with metadata, which may be over
+                        // cells that didn't get a tile
+                        if logic_only && tileset_definition.is_some() {
+                            let missing_tile_positions: Vec<TilePos> = grid_tile_positions
+                                .iter()
+                                .copied()
+                                .filter(|tile_pos| storage.get(tile_pos).is_none())
+                                .collect();
+
+                            set_tiles_with_func(
+                                commands,
+                                &mut storage,
+                                missing_tile_positions,
+                                TilemapId(layer_entity),
+                                tile_pos_to_tile_grid_bundle_maker(tile_pos_to_tile_maker(
+                                    &grid_tiles,
+                                    layer_instance.c_hei,
+                                    layer_instance.grid_size,
+                                )),
+                            );
+                        }
+
                         if i == 0 {
+                            let default_ldtk_int_cell =
+                                PhantomLdtkIntCell::<IntGridCellBundle>::new();
+                            let int_cell_registrations =
+                                ldtk_registrations.int_cells.for_layer(layer_definition);
+
+                            // int_grid_csv starts at the top of the layer, rows at the bottom
+                            let csv_rows =
+                                (size.y - rows.end) as usize..(size.y - rows.start) as usize;
+                            let csv_range =
+                                csv_rows.start * size.x as usize..csv_rows.end * size.x as usize;
+
                             for (i, value) in layer_instance
                                 .int_grid_csv
                                 .iter()
                                 .enumerate()
-                                .filter(|(_, v)| **v != 0)
+                                .skip(csv_range.start)
+                                .take(csv_range.len())
+                                .filter(|(_, v)| **v != 0 && !excluded_values.contains(v))
                             {
                                 let grid_coords = int_grid_index_to_grid_coords(
                                     i,
@@ -563,24 +1243,46 @@ pub fn spawn_level(
                                     layer_instance.c_hei as u32,
                                 ).expect("int_grid_csv indices should be within the bounds of 0..(layer_width * layer_height)");
 
-                                if let Some(tile_entity) = storage.get(&grid_coords.into()) {
-                                    let mut entity_commands = commands.entity(tile_entity);
+                                let ldtk_int_cell = match int_cell_registrations.get(*value) {
+                                    Some(ldtk_int_cell) => ldtk_int_cell,
+                                    None if layer_storage => continue,
+                                    None => &default_ldtk_int_cell,
+                                };
 
-                                    let default_ldtk_int_cell: Box<dyn PhantomLdtkIntCellTrait> =
-                                        Box::new(PhantomLdtkIntCell::<IntGridCellBundle>::new());
+                                let context = IntGridCellContext::new(
+                                    layer_instance,
+                                    layer_definition,
+                                    grid_coords,
+                                );
 
-                                    ldtk_map_get_or_default(
-                                        layer_instance.identifier.clone(),
-                                        *value,
-                                        &default_ldtk_int_cell,
-                                        ldtk_int_cell_map,
-                                    )
-                                    .evaluate(
-                                        &mut entity_commands,
-                                        IntGridCell { value: *value },
-                                        layer_instance,
-                                    );
-                                }
+                                let tile_pos = grid_coords.into();
+                                let tile_entity = match storage.get(&tile_pos) {
+                                    Some(tile_entity) => tile_entity,
+                                    // With layer storage, registered cells may not have a tile yet
+                                    None if layer_storage => {
+                                        let tile_entity = commands
+                                            .spawn(TileGridBundle {
+                                                tile_bundle: TileBundle {
+                                                    position: tile_pos,
+                                                    ..tile_pos_to_invisible_tile(tile_pos)
+                                                        .unwrap_or_default()
+                                                },
+                                                grid_coords,
+                                            })
+                                            .insert(TilemapId(layer_entity))
+                                            .id();
+                                        storage.set(&tile_pos, tile_entity);
+                                        tile_entity
+                                    }
+                                    None => continue,
+                                };
+
+                                ldtk_int_cell.evaluate(
+                                    &mut commands.entity(tile_entity),
+                                    IntGridCell { value: *value },
+                                    layer_instance,
+                                    &context,
+                                );
                             }
                         }
 
@@ -595,15 +1297,7 @@ pub fn spawn_level(
                             );
                         }
 
-                        TilemapBundle {
-                            grid_size: tilemap_grid_size,
-                            size,
-                            spacing,
-                            storage,
-                            texture: texture.clone(),
-                            tile_size: tilemap_tile_size,
-                            ..default()
-                        }
+                        storage
                     } else {
                         let tile_bundle_maker =
                             tile_pos_to_tile_grid_bundle_maker(tile_pos_to_transparent_tile_maker(
@@ -622,10 +1316,10 @@ pub fn spawn_level(
 
                         let mut storage = TileStorage::empty(size);
 
-                        set_all_tiles_with_func(
+                        set_tiles_with_func(
                             commands,
                             &mut storage,
-                            size,
+                            grid_tile_positions,
                             TilemapId(layer_entity),
                             tile_bundle_maker,
                         );
@@ -641,77 +1335,1017 @@ pub fn spawn_level(
                             );
                         }
 
-                        TilemapBundle {
-                            grid_size: tilemap_grid_size,
-                            size,
-                            spacing,
-                            storage,
-                            texture: texture.clone(),
-                            tile_size: tilemap_tile_size,
-                            ..default()
-                        }
+                        storage
                     };
 
                     insert_spatial_bundle_for_layer_tiles(
                         commands,
-                        &tilemap_bundle.storage,
-                        &tilemap_bundle.size,
+                        &storage,
+                        &size,
+                        rows.clone(),
                         layer_instance.grid_size,
                         TilemapId(layer_entity),
                     );
 
-                    let LayerDefinition {
-                        tile_pivot_x,
-                        tile_pivot_y,
-                        ..
-                    } = &layer_definition_map
-                        .get(&layer_instance.layer_def_uid)
-                        .expect("Encountered layer without definition");
-
-                    // The math for determining the x/y of a tilemap layer depends heavily on
-                    // both the layer's grid size and the tileset's tile size.
-                    // In particular, we care about their difference for properly reversing y
-                    // direction and for tile pivot calculations.
-                    let grid_tile_size_difference = grid_size - tile_size;
-
-                    // It is useful to determine what we should treat as the desired "origin" of
-                    // the tilemap in bevy space.
-                    // This will be the bottom left pixel of the tilemap.
-                    // The y value is affected when there is a difference between the grid size and
-                    // tile size - it sinks below 0 when the grid size is greater.
-                    let bottom_left_pixel = Vec2::new(0., grid_tile_size_difference);
-
-                    // Tiles in bevy_ecs_tilemap are anchored to the center of the tile.
-                    // We need to cancel out this anchoring so that layers of different sizes will
-                    // stack on top of eachother as they do in LDtk.
-                    let centering_adjustment = Vec2::splat(tile_size / 2.);
-
-                    // Layers in LDtk can have a pivot value that acts like an anchor.
-                    // The amount that a tile is translated by this pivot is simply the difference
-                    // between grid_size and tile_size again.
-                    let pivot_adjustment = Vec2::new(
-                        grid_tile_size_difference * tile_pivot_x,
-                        -grid_tile_size_difference * tile_pivot_y,
-                    );
+                    if resuming {
+                        // The tilemap was inserted in a previous frame, so these tiles are added
+                        // to its storage instead
+                        let tiles: Vec<(TilePos, Entity)> = rows
+                            .clone()
+                            .flat_map(|y| (0..size.x).map(move |x| TilePos { x, y }))
+                            .filter_map(|tile_pos| {
+                                storage
+                                    .get(&tile_pos)
+                                    .map(|tile_entity| (tile_pos, tile_entity))
+                            })
+                            .collect();
 
-                    commands
-                        .entity(layer_entity)
-                        .insert(tilemap_bundle)
-                        .insert(SpatialBundle::from_transform(Transform::from_translation(
-                            (bottom_left_pixel
-                                + centering_adjustment
-                                + pivot_adjustment
-                                + layer_offset)
-                                .extend(layer_z as f32),
-                        )))
-                        .insert(LayerMetadata::from(layer_instance))
-                        .insert(Name::new(layer_instance.identifier.to_owned()));
-
-                    commands.entity(ldtk_entity).add_child(layer_entity);
+                        commands.add(move |world: &mut World| {
+                            if let Some(mut tile_storage) =
+                                world.get_mut::<TileStorage>(layer_entity)
+                            {
+                                for (tile_pos, tile_entity) in tiles {
+                                    tile_storage.set(&tile_pos, tile_entity);
+                                }
+                            }
+                        });
+                    } else {
+                        let LayerDefinition {
+                            tile_pivot_x,
+                            tile_pivot_y,
+                            ..
+                        } = layer_definition;
+
+                        // The math for determining the x/y of a tilemap layer depends heavily on
+                        // both the layer's grid size and the tileset's tile size.
+                        // In particular, we care about their difference for properly reversing y
+                        // direction and for tile pivot calculations.
+                        let grid_tile_size_difference = grid_size - tile_size;
+
+                        // It is useful to determine what we should treat as the desired "origin" of
+                        // the tilemap in bevy space.
+                        // This will be the bottom left pixel of the tilemap.
+                        // The y value is affected when there is a difference between the grid size and
+                        // tile size - it sinks below 0 when the grid size is greater.
+                        let bottom_left_pixel = Vec2::new(0., grid_tile_size_difference);
+
+                        // Tiles in bevy_ecs_tilemap are anchored to the center of the tile.
+                        // We need to cancel out this anchoring so that layers of different sizes will
+                        // stack on top of eachother as they do in LDtk.
+                        let centering_adjustment = Vec2::splat(tile_size / 2.);
+
+                        // Layers in LDtk can have a pivot value that acts like an anchor.
+                        // The amount that a tile is translated by this pivot is simply the difference
+                        // between grid_size and tile_size again.
+                        let pivot_adjustment = Vec2::new(
+                            grid_tile_size_difference * tile_pivot_x,
+                            -grid_tile_size_difference * tile_pivot_y,
+                        );
+
+                        match &texture {
+                            Some(texture) => commands.entity(layer_entity).insert(TilemapBundle {
+                                grid_size: tilemap_grid_size,
+                                size,
+                                spacing,
+                                storage,
+                                texture: texture.clone(),
+                                tile_size: tilemap_tile_size,
+                                ..default()
+                            }),
+                            None => commands.entity(layer_entity).insert((
+                                tilemap_grid_size,
+                                size,
+                                storage,
+                            )),
+                        };
+
+                        let layer_translation = (bottom_left_pixel
+                            + centering_adjustment
+                            + pivot_adjustment
+                            + layer_offset)
+                            .extend(layer_z as f32);
+
+                        commands
+                            .entity(layer_entity)
+                            .insert(layer_spatial_bundle(
+                                layer_instance,
+                                layer_definition,
+                                layer_translation,
+                                ldtk_settings,
+                            ))
+                            .insert(LayerMetadata::from(layer_instance))
+                            .insert(Name::new(layer_instance.identifier.to_owned()));
+
+                        if let Some(parallax) = LdtkParallax::from_layer_definition(
+                            layer_definition,
+                            layer_translation,
+                            level_center,
+                        ) {
+                            commands.entity(layer_entity).insert(parallax);
+                        }
+
+                        if i == 0 && layer_instance.layer_instance_type == Type::IntGrid {
+                            spawn_merged_int_grid_regions(
+                                commands,
+                                layer_entity,
+                                layer_instance,
+                                &ldtk_settings.int_grid_merging,
+                                &excluded_values,
+                                bottom_left_pixel + centering_adjustment + pivot_adjustment,
+                            );
+
+                            let excluded_cells: Vec<GridCoords> = layer_instance
+                                .int_grid_csv
+                                .iter()
+                                .enumerate()
+                                .filter(|(_, value)| excluded_values.contains(value))
+                                .filter_map(|(i, _)| {
+                                    int_grid_index_to_grid_coords(
+                                        i,
+                                        layer_instance.c_wid as u32,
+                                        layer_instance.c_hei as u32,
+                                    )
+                                })
+                                .collect();
+
+                            if layer_storage {
+                                let mut int_grid_layer = IntGridLayer::from(layer_instance);
+                                for grid_coords in excluded_cells.iter() {
+                                    int_grid_layer.set(*grid_coords, 0);
+                                }
+
+                                commands.entity(layer_entity).insert(int_grid_layer);
+                            }
+
+                            if let Some(nav_grid_settings) =
+                                ldtk_settings.nav_grids.iter().find(|settings| {
+                                    settings.layer_identifier == layer_instance.identifier
+                                })
+                            {
+                                let mut nav_grid =
+                                    NavGrid::from_layer_instance(layer_instance, nav_grid_settings);
+                                for grid_coords in excluded_cells.iter() {
+                                    nav_grid.set_value(*grid_coords, 0);
+                                }
 
+                                commands.entity(layer_entity).insert(nav_grid);
+                            }
+                        }
+
+                        if let Some(ldtk_layer) = ldtk_layer {
+                            ldtk_layer.evaluate(
+                                &mut commands.entity(layer_entity),
+                                layer_instance,
+                                layer_definition,
+                                asset_server,
+                            );
+                        }
+
+                        commands.entity(ldtk_entity).add_child(layer_entity);
+                    }
+
+                    if rows.end < size.y {
+                        progress.layer_spawned = rows.end as usize;
+                        progress.layer_z = layer_z;
+                        return false;
+                    }
+
+                    progress.layer_spawned = 0;
                     layer_z += 1;
                 }
             }
         }
+
+        progress.spawned_layers += 1;
+        progress.layer_entities.clear();
+        progress.layer_spawned = 0;
+        progress.layer_tiles.clear();
+    }
+
+    progress.layer_z = layer_z;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_spawn_budget() {
+        let mut unlimited = FrameSpawnBudget::new(LevelSpawnBudget::Unlimited);
+        assert!(unlimited.try_spend(usize::MAX));
+        assert!(unlimited.try_spend(usize::MAX));
+
+        let mut budget = FrameSpawnBudget::new(LevelSpawnBudget::PerFrame(100));
+        assert!(budget.try_spend(60));
+        assert!(budget.try_spend(40));
+        assert!(!budget.try_spend(1));
+
+        // the first cost of a frame always fits
+        let mut budget = FrameSpawnBudget::new(LevelSpawnBudget::PerFrame(100));
+        assert!(budget.try_spend(1000));
+        assert!(!budget.try_spend(1));
+    }
+
+    #[test]
+    fn test_frame_spawn_budget_ranges() {
+        let mut unlimited = FrameSpawnBudget::new(LevelSpawnBudget::Unlimited);
+        assert_eq!(unlimited.try_spend_range(3, 100, 64), Some(3..100));
+
+        let mut budget = FrameSpawnBudget::new(LevelSpawnBudget::PerFrame(100));
+        assert_eq!(budget.try_spend_range(0, 10, 30), Some(0..3));
+        assert_eq!(budget.try_spend_range(3, 10, 30), None);
+
+        // the first unit of a frame always fits, and empty layers cost one unit
+        let mut budget = FrameSpawnBudget::new(LevelSpawnBudget::PerFrame(100));
+        assert_eq!(budget.try_spend_range(5, 10, 1000), Some(5..6));
+        assert_eq!(budget.try_spend_range(0, 0, 1), None);
+
+        let mut budget = FrameSpawnBudget::new(LevelSpawnBudget::PerFrame(100));
+        assert_eq!(budget.try_spend_range(0, 0, 1), Some(0..0));
+    }
+
+    #[test]
+    fn overlapping_tiles_are_layered_by_index() {
+        let tile = |x: i32, y: i32| TileInstance {
+            px: IVec2::new(x, y),
+            ..default()
+        };
+        let tiles = [
+            tile(0, 0),
+            tile(16, 0),
+            tile(0, 0),
+            tile(0, 0),
+            tile(16, 16),
+        ];
+
+        assert_eq!(
+            layer_grid_tiles(tiles.iter().enumerate()),
+            vec![vec![0, 1, 4], vec![2], vec![3]]
+        );
+        assert_eq!(layer_grid_tiles([]), vec![Vec::<usize>::new()]);
+    }
+
+    #[test]
+    #[allow(clippy::type_complexity)]
+    fn tile_layers_spawn_a_few_rows_per_frame() {
+        use crate::{
+            app::{LdtkEntityMap, LdtkEntitySystemMap, LdtkIntCellMap, LdtkLayerMap, LdtkLevelMap},
+            ldtk::Level,
+        };
+        use bevy::ecs::system::SystemState;
+        use seek_ecs_tilemap::tiles::TileTextureIndex;
+
+        let mut app = App::new();
+        app.add_plugins(AssetPlugin::default())
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_resource::<LdtkEntityMap>()
+            .init_resource::<LdtkEntitySystemMap>()
+            .init_resource::<LdtkIntCellMap>()
+            .init_resource::<LdtkLayerMap>()
+            .init_resource::<LdtkLevelMap>();
+
+        // Tiles have metadata so they're spawned in logic-only mode too
+        let tileset_definition = TilesetDefinition {
+            uid: 10,
+            tile_grid_size: 16,
+            custom_data: (0..9)
+                .map(|tile_id| TileCustomMetadata {
+                    data: tile_id.to_string(),
+                    tile_id,
+                })
+                .collect(),
+            ..default()
+        };
+        let tile_definition = LayerDefinition {
+            uid: 2,
+            identifier: "Decoration".to_string(),
+            purple_type: Type::Tiles,
+            tileset_def_uid: Some(10),
+            ..default()
+        };
+
+        // Every cell of a 2x4 layer has a tile, and the bottom-left cell has a second one on top
+        let mut grid_tiles: Vec<TileInstance> = (0..8)
+            .map(|i| TileInstance {
+                t: i,
+                px: IVec2::new(i % 2 * 16, i / 2 * 16),
+                ..default()
+            })
+            .collect();
+        grid_tiles.push(TileInstance {
+            t: 8,
+            px: IVec2::new(0, 48),
+            ..default()
+        });
+
+        let level = Level {
+            layer_instances: Some(vec![LayerInstance {
+                identifier: "Decoration".to_string(),
+                layer_instance_type: Type::Tiles,
+                layer_def_uid: 2,
+                tileset_def_uid: Some(10),
+                c_wid: 2,
+                c_hei: 4,
+                grid_size: 16,
+                grid_tiles,
+                ..default()
+            }]),
+            ..default()
+        };
+
+        let ldtk_settings = LdtkSettings {
+            level_background: LevelBackground::Nonexistent,
+            ..default()
+        };
+
+        let level_entity = app.world.spawn_empty().id();
+        let mut progress = LevelSpawnProgress::default();
+
+        let mut system_state: SystemState<(
+            Commands,
+            Res<AssetServer>,
+            Res<Assets<Image>>,
+            ResMut<Assets<TextureAtlasLayout>>,
+            LdtkRegistrations,
+        )> = SystemState::new(&mut app.world);
+
+        let mut frames = 0;
+        loop {
+            frames += 1;
+
+            let (mut commands, asset_server, images, mut texture_atlases, ldtk_registrations) =
+                system_state.get_mut(&mut app.world);
+
+            let complete = spawn_level(
+                LoadedLevel::try_from(&level).unwrap(),
+                &None,
+                &mut commands,
+                &asset_server,
+                &images,
+                &mut texture_atlases,
+                &ldtk_registrations,
+                &HashMap::new(),
+                &HashMap::from([(2, &tile_definition)]),
+                &HashMap::from([(10, Handle::default())]),
+                &HashMap::from([(10, &tileset_definition)]),
+                &None,
+                HashSet::new(),
+                level_entity,
+                &ldtk_settings,
+                &mut EntityIidMap::default(),
+                &mut progress,
+                &mut FrameSpawnBudget::new(LevelSpawnBudget::PerFrame(2)),
+            );
+
+            system_state.apply(&mut app.world);
+
+            if complete {
+                break;
+            }
+
+            // The layer's tiles are only split into tilemaps once
+            assert_eq!(progress.layer_tiles.len(), 2);
+            assert!(frames < 20, "the level should have spawned by now");
+        }
+
+        // One row of each tilemap is spawned per frame
+        assert_eq!(frames, 8);
+        assert!(progress.layer_tiles.is_empty());
+
+        let world = &mut app.world;
+
+        let mut tilemaps: Vec<Vec<i32>> = world
+            .query::<&TileStorage>()
+            .iter(world)
+            .map(|tile_storage| {
+                let mut tile_ids: Vec<i32> = tile_storage
+                    .iter()
+                    .flatten()
+                    .map(|tile_entity| {
+                        world.get::<TileTextureIndex>(*tile_entity).unwrap().0 as i32
+                    })
+                    .collect();
+                tile_ids.sort();
+                tile_ids
+            })
+            .collect();
+        tilemaps.sort_by_key(|tile_ids| tile_ids.len());
+
+        assert_eq!(tilemaps, vec![vec![8], (0..8).collect()]);
+    }
+
+    #[test]
+    fn hidden_and_tagged_layers_are_hidden_or_excluded() {
+        let debug_definition = LayerDefinition {
+            uid: 1,
+            ui_filter_tags: vec!["debug".to_string()],
+            render_in_world_view: true,
+            ..default()
+        };
+        let rendered_definition = LayerDefinition {
+            render_in_world_view: true,
+            ..default()
+        };
+        let world_hidden_definition = LayerDefinition {
+            render_in_world_view: false,
+            ..default()
+        };
+        let layer_definition_map = HashMap::from([
+            (1, &debug_definition),
+            (2, &rendered_definition),
+            (3, &rendered_definition),
+            (4, &world_hidden_definition),
+        ]);
+
+        let layer_instances = [
+            LayerInstance {
+                identifier: "Debug".to_string(),
+                layer_def_uid: 1,
+                visible: true,
+                ..default()
+            },
+            LayerInstance {
+                identifier: "Hidden".to_string(),
+                layer_def_uid: 2,
+                visible: false,
+                ..default()
+            },
+            LayerInstance {
+                identifier: "Visible".to_string(),
+                layer_def_uid: 3,
+                visible: true,
+                ..default()
+            },
+            LayerInstance {
+                identifier: "World_Hidden".to_string(),
+                layer_def_uid: 4,
+                visible: true,
+                ..default()
+            },
+        ];
+
+        let identifiers = |ldtk_settings: &LdtkSettings| {
+            layers_to_spawn(&layer_instances, &layer_definition_map, ldtk_settings)
+                .map(|layer_instance| layer_instance.identifier.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let visibility = |index: usize, ldtk_settings: &LdtkSettings| {
+            let layer_instance = &layer_instances[index];
+            let layer_definition = layer_definition_map[&layer_instance.layer_def_uid];
+            layer_spatial_bundle(layer_instance, layer_definition, Vec3::ZERO, ldtk_settings)
+                .visibility
+        };
+
+        let mut ldtk_settings = LdtkSettings::default();
+        assert_eq!(
+            identifiers(&ldtk_settings),
+            vec!["World_Hidden", "Visible", "Hidden", "Debug"]
+        );
+        assert_eq!(visibility(1, &ldtk_settings), Visibility::Hidden);
+        assert_eq!(visibility(2, &ldtk_settings), Visibility::Inherited);
+        // "Render in world view" only affects LDtk's world view by default
+        assert_eq!(visibility(3, &ldtk_settings), Visibility::Inherited);
+
+        ldtk_settings.world_view_layers = WorldViewLayers::Hidden;
+        assert_eq!(visibility(3, &ldtk_settings), Visibility::Hidden);
+
+        ldtk_settings.hidden_layers = HiddenLayers::Visible;
+        assert_eq!(visibility(1, &ldtk_settings), Visibility::Inherited);
+        assert_eq!(visibility(3, &ldtk_settings), Visibility::Inherited);
+
+        ldtk_settings.hidden_layers = HiddenLayers::Excluded;
+        ldtk_settings.exclusions.layer_tags = vec!["debug".to_string()];
+        assert_eq!(identifiers(&ldtk_settings), vec!["Visible"]);
+
+        ldtk_settings.world_view_layers = WorldViewLayers::Ignored;
+        assert_eq!(identifiers(&ldtk_settings), vec!["World_Hidden", "Visible"]);
+    }
+
+    #[test]
+    fn test_merge_int_grid_cells() {
+        #[rustfmt::skip]
+        let int_grid_csv = [
+            1, 1, 0, 3,
+            1, 1, 2, 0,
+            0, 1, 2, 2,
+        ];
+
+        let regions = merge_int_grid_cells(&int_grid_csv, 4, 3, |value| value != 0);
+
+        assert_eq!(
+            regions,
+            vec![
+                MergedIntGridRegion {
+                    value: 1,
+                    rect: IRect::new(1, 0, 2, 3),
+                },
+                MergedIntGridRegion {
+                    value: 2,
+                    rect: IRect::new(2, 0, 4, 1),
+                },
+                MergedIntGridRegion {
+                    value: 1,
+                    rect: IRect::new(0, 1, 1, 3),
+                },
+                MergedIntGridRegion {
+                    value: 2,
+                    rect: IRect::new(2, 1, 3, 2),
+                },
+                MergedIntGridRegion {
+                    value: 3,
+                    rect: IRect::new(3, 2, 4, 3),
+                },
+            ]
+        );
+
+        let regions = merge_int_grid_cells(&int_grid_csv, 4, 3, |value| value == 2);
+        assert_eq!(regions.len(), 2);
+        assert!(regions.iter().all(|region| region.value == 2));
+
+        assert!(merge_int_grid_cells(&[], 4, 3, |_| true).is_empty());
+    }
+
+    #[test]
+    fn test_trace_int_grid_outlines() {
+        #[rustfmt::skip]
+        let int_grid_csv = [
+            1, 1, 1, 0,
+            1, 0, 1, 0,
+            1, 1, 1, 2,
+        ];
+
+        let outlines = trace_int_grid_outlines(&int_grid_csv, 4, 3, |value| value != 0);
+
+        assert_eq!(
+            outlines,
+            vec![
+                (
+                    1,
+                    vec![
+                        IVec2::new(0, 0),
+                        IVec2::new(3, 0),
+                        IVec2::new(3, 3),
+                        IVec2::new(0, 3)
+                    ]
+                ),
+                (
+                    1,
+                    vec![
+                        IVec2::new(2, 1),
+                        IVec2::new(1, 1),
+                        IVec2::new(1, 2),
+                        IVec2::new(2, 2)
+                    ]
+                ),
+                (
+                    2,
+                    vec![
+                        IVec2::new(3, 0),
+                        IVec2::new(4, 0),
+                        IVec2::new(4, 1),
+                        IVec2::new(3, 1)
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trace_int_grid_outlines_separates_diagonal_cells() {
+        #[rustfmt::skip]
+        let int_grid_csv = [
+            0, 1,
+            1, 0,
+        ];
+
+        let outlines = trace_int_grid_outlines(&int_grid_csv, 2, 2, |value| value != 0);
+
+        assert_eq!(outlines.len(), 2);
+        assert!(outlines.iter().all(|(_, points)| points.len() == 4));
+    }
+
+    #[test]
+    #[allow(clippy::type_complexity)]
+    fn logic_only_levels_spawn_without_tilemaps() {
+        use crate::{
+            app::{LdtkEntityMap, LdtkEntitySystemMap, LdtkIntCellMap, LdtkLayerMap, LdtkLevelMap},
+            ldtk::Level,
+            resources::LevelSpawnMode,
+        };
+        use bevy::ecs::system::SystemState;
+
+        let mut app = App::new();
+        app.add_plugins(AssetPlugin::default())
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_resource::<LdtkEntityMap>()
+            .init_resource::<LdtkEntitySystemMap>()
+            .init_resource::<LdtkIntCellMap>()
+            .init_resource::<LdtkLayerMap>()
+            .init_resource::<LdtkLevelMap>();
+
+        let tileset_definition = TilesetDefinition {
+            uid: 10,
+            tile_grid_size: 16,
+            custom_data: vec![TileCustomMetadata {
+                data: "door".to_string(),
+                tile_id: 5,
+            }],
+            ..default()
+        };
+        let int_grid_definition = LayerDefinition {
+            uid: 1,
+            identifier: "Terrain".to_string(),
+            purple_type: Type::IntGrid,
+            ..default()
+        };
+        let tile_definition = LayerDefinition {
+            uid: 2,
+            identifier: "Decoration".to_string(),
+            purple_type: Type::Tiles,
+            tileset_def_uid: Some(10),
+            ..default()
+        };
+
+        let level = Level {
+            layer_instances: Some(vec![
+                LayerInstance {
+                    identifier: "Decoration".to_string(),
+                    layer_instance_type: Type::Tiles,
+                    layer_def_uid: 2,
+                    tileset_def_uid: Some(10),
+                    c_wid: 2,
+                    c_hei: 2,
+                    grid_size: 16,
+                    grid_tiles: vec![
+                        TileInstance {
+                            t: 5,
+                            px: IVec2::new(0, 0),
+                            ..default()
+                        },
+                        TileInstance {
+                            t: 6,
+                            px: IVec2::new(16, 0),
+                            ..default()
+                        },
+                    ],
+                    ..default()
+                },
+                LayerInstance {
+                    identifier: "Terrain".to_string(),
+                    layer_instance_type: Type::IntGrid,
+                    layer_def_uid: 1,
+                    c_wid: 2,
+                    c_hei: 2,
+                    grid_size: 16,
+                    int_grid_csv: vec![1, 0, 0, 2],
+                    ..default()
+                },
+            ]),
+            ..default()
+        };
+
+        let ldtk_settings = LdtkSettings {
+            level_spawn_mode: LevelSpawnMode::LogicOnly,
+            ..default()
+        };
+
+        let level_entity = app.world.spawn_empty().id();
+
+        let mut system_state: SystemState<(
+            Commands,
+            Res<AssetServer>,
+            Res<Assets<Image>>,
+            ResMut<Assets<TextureAtlasLayout>>,
+            LdtkRegistrations,
+        )> = SystemState::new(&mut app.world);
+
+        let (mut commands, asset_server, images, mut texture_atlases, ldtk_registrations) =
+            system_state.get_mut(&mut app.world);
+
+        let complete = spawn_level(
+            LoadedLevel::try_from(&level).unwrap(),
+            &None,
+            &mut commands,
+            &asset_server,
+            &images,
+            &mut texture_atlases,
+            &ldtk_registrations,
+            &HashMap::new(),
+            &HashMap::from([(1, &int_grid_definition), (2, &tile_definition)]),
+            &HashMap::from([(10, Handle::default())]),
+            &HashMap::from([(10, &tileset_definition)]),
+            &None,
+            HashSet::new(),
+            level_entity,
+            &ldtk_settings,
+            &mut EntityIidMap::default(),
+            &mut LevelSpawnProgress::default(),
+            &mut FrameSpawnBudget::new(LevelSpawnBudget::Unlimited),
+        );
+        assert!(complete);
+
+        system_state.apply(&mut app.world);
+
+        let world = &mut app.world;
+
+        assert_eq!(world.query::<&TilesetTexture>().iter(world).count(), 0);
+        assert_eq!(world.query::<&Sprite>().iter(world).count(), 0);
+        assert_eq!(
+            world
+                .query::<(&LayerMetadata, &TileStorage)>()
+                .iter(world)
+                .count(),
+            2
+        );
+
+        let mut int_grid_cells: Vec<_> = world
+            .query::<(&IntGridCell, &GridCoords)>()
+            .iter(world)
+            .map(|(int_grid_cell, grid_coords)| (int_grid_cell.value, *grid_coords))
+            .collect();
+        int_grid_cells.sort_by_key(|(value, _)| *value);
+        assert_eq!(
+            int_grid_cells,
+            vec![(1, GridCoords::new(0, 1)), (2, GridCoords::new(1, 0))]
+        );
+
+        // Only the tile with metadata is spawned on the tile layer
+        let tiles: Vec<_> = world
+            .query_filtered::<Option<&TileMetadata>, (With<TilePos>, Without<IntGridCell>)>()
+            .iter(world)
+            .collect();
+        assert_eq!(
+            tiles,
+            vec![Some(&TileMetadata {
+                data: "door".to_string()
+            })]
+        );
+    }
+
+    #[test]
+    #[allow(clippy::type_complexity)]
+    fn excluded_int_grid_values_spawn_like_empty_cells() {
+        use crate::{
+            app::{LdtkEntityMap, LdtkEntitySystemMap, LdtkIntCellMap, LdtkLayerMap, LdtkLevelMap},
+            ldtk::{IntGridValueDefinition, Level},
+            pathfinding::NavGridSettings,
+            resources::SpawnExclusions,
+        };
+        use bevy::ecs::system::SystemState;
+
+        let mut app = App::new();
+        app.add_plugins(AssetPlugin::default())
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_resource::<LdtkEntityMap>()
+            .init_resource::<LdtkEntitySystemMap>()
+            .init_resource::<LdtkIntCellMap>()
+            .init_resource::<LdtkLayerMap>()
+            .init_resource::<LdtkLevelMap>();
+
+        let tileset_definition = TilesetDefinition {
+            uid: 10,
+            tile_grid_size: 16,
+            ..default()
+        };
+        let int_grid_definition = LayerDefinition {
+            uid: 1,
+            identifier: "Terrain".to_string(),
+            purple_type: Type::IntGrid,
+            tileset_def_uid: Some(10),
+            int_grid_values: [1, 4]
+                .into_iter()
+                .map(|value| IntGridValueDefinition { value, ..default() })
+                .collect(),
+            ..default()
+        };
+
+        // every cell has an auto-tile
+        let auto_layer_tiles = [(0, 0), (16, 0), (0, 16), (16, 16)]
+            .into_iter()
+            .map(|(x, y)| TileInstance {
+                px: IVec2::new(x, y),
+                ..default()
+            })
+            .collect();
+
+        let level = Level {
+            layer_instances: Some(vec![LayerInstance {
+                identifier: "Terrain".to_string(),
+                layer_instance_type: Type::IntGrid,
+                layer_def_uid: 1,
+                tileset_def_uid: Some(10),
+                c_wid: 2,
+                c_hei: 2,
+                grid_size: 16,
+                int_grid_csv: vec![1, 4, 4, 1],
+                auto_layer_tiles,
+                ..default()
+            }]),
+            ..default()
+        };
+
+        let ldtk_settings = LdtkSettings {
+            exclusions: SpawnExclusions {
+                int_grid_values: vec![("Terrain".to_string(), 4), ("Water".to_string(), 1)],
+                ..default()
+            },
+            nav_grids: vec![NavGridSettings {
+                layer_identifier: "Terrain".to_string(),
+                costs: HashMap::from([(0, 1)]),
+                diagonal_movement: false,
+            }],
+            ..default()
+        };
+
+        let level_entity = app.world.spawn_empty().id();
+
+        let mut system_state: SystemState<(
+            Commands,
+            Res<AssetServer>,
+            Res<Assets<Image>>,
+            ResMut<Assets<TextureAtlasLayout>>,
+            LdtkRegistrations,
+        )> = SystemState::new(&mut app.world);
+
+        let (mut commands, asset_server, images, mut texture_atlases, ldtk_registrations) =
+            system_state.get_mut(&mut app.world);
+
+        let complete = spawn_level(
+            LoadedLevel::try_from(&level).unwrap(),
+            &None,
+            &mut commands,
+            &asset_server,
+            &images,
+            &mut texture_atlases,
+            &ldtk_registrations,
+            &HashMap::new(),
+            &HashMap::from([(1, &int_grid_definition)]),
+            &HashMap::from([(10, Handle::default())]),
+            &HashMap::from([(10, &tileset_definition)]),
+            &None,
+            HashSet::new(),
+            level_entity,
+            &ldtk_settings,
+            &mut EntityIidMap::default(),
+            &mut LevelSpawnProgress::default(),
+            &mut FrameSpawnBudget::new(LevelSpawnBudget::Unlimited),
+        );
+        assert!(complete);
+
+        system_state.apply(&mut app.world);
+
+        let world = &mut app.world;
+
+        // Only the cells with value 1 are spawned, along with their auto-tiles
+        let mut int_grid_cells: Vec<_> = world
+            .query::<(&IntGridCell, &GridCoords)>()
+            .iter(world)
+            .map(|(int_grid_cell, grid_coords)| (int_grid_cell.value, *grid_coords))
+            .collect();
+        int_grid_cells.sort_by_key(|(_, grid_coords)| grid_coords.x);
+        assert_eq!(
+            int_grid_cells,
+            vec![(1, GridCoords::new(0, 1)), (1, GridCoords::new(1, 0))]
+        );
+        assert_eq!(world.query::<&TilePos>().iter(world).count(), 2);
+
+        let nav_grid = world.query::<&NavGrid>().single(world);
+        assert_eq!(nav_grid.value(GridCoords::new(0, 0)), Some(0));
+        assert_eq!(nav_grid.value(GridCoords::new(0, 1)), Some(1));
+    }
+    #[test]
+    #[allow(clippy::type_complexity)]
+    fn logic_only_int_grid_layers_keep_metadata_tiles_over_empty_cells() {
+        use crate::{
+            app::{LdtkEntityMap, LdtkEntitySystemMap, LdtkIntCellMap, LdtkLayerMap, LdtkLevelMap},
+            ldtk::Level,
+            resources::{IntGridStorage, LevelSpawnMode},
+        };
+        use bevy::ecs::system::SystemState;
+
+        let mut app = App::new();
+        app.add_plugins(AssetPlugin::default())
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_resource::<LdtkEntityMap>()
+            .init_resource::<LdtkEntitySystemMap>()
+            .init_resource::<LdtkIntCellMap>()
+            .init_resource::<LdtkLayerMap>()
+            .init_resource::<LdtkLevelMap>();
+
+        let tileset_definition = TilesetDefinition {
+            uid: 10,
+            tile_grid_size: 16,
+            custom_data: vec![TileCustomMetadata {
+                data: "grass".to_string(),
+                tile_id: 5,
+            }],
+            ..default()
+        };
+        let int_grid_definition = LayerDefinition {
+            uid: 1,
+            identifier: "Terrain".to_string(),
+            purple_type: Type::IntGrid,
+            tileset_def_uid: Some(10),
+            ..default()
+        };
+
+        // The auto-tile with metadata is over the empty cell in the top right
+        let level = Level {
+            layer_instances: Some(vec![LayerInstance {
+                identifier: "Terrain".to_string(),
+                layer_instance_type: Type::IntGrid,
+                layer_def_uid: 1,
+                tileset_def_uid: Some(10),
+                c_wid: 2,
+                c_hei: 2,
+                grid_size: 16,
+                int_grid_csv: vec![1, 0, 0, 2],
+                auto_layer_tiles: vec![
+                    TileInstance {
+                        t: 5,
+                        px: IVec2::new(16, 0),
+                        ..default()
+                    },
+                    TileInstance {
+                        t: 6,
+                        px: IVec2::new(0, 0),
+                        ..default()
+                    },
+                ],
+                ..default()
+            }]),
+            ..default()
+        };
+
+        // Layer storage doesn't spawn unregistered cells, so the layer may have no tiles at all
+        for int_grid_storage in [IntGridStorage::Entities, IntGridStorage::Layer] {
+            let ldtk_settings = LdtkSettings {
+                level_spawn_mode: LevelSpawnMode::LogicOnly,
+                int_grid_storage,
+                ..default()
+            };
+
+            let level_entity = app.world.spawn_empty().id();
+
+            let mut system_state: SystemState<(
+                Commands,
+                Res<AssetServer>,
+                Res<Assets<Image>>,
+                ResMut<Assets<TextureAtlasLayout>>,
+                LdtkRegistrations,
+            )> = SystemState::new(&mut app.world);
+
+            let (mut commands, asset_server, images, mut texture_atlases, ldtk_registrations) =
+                system_state.get_mut(&mut app.world);
+
+            let complete = spawn_level(
+                LoadedLevel::try_from(&level).unwrap(),
+                &None,
+                &mut commands,
+                &asset_server,
+                &images,
+                &mut texture_atlases,
+                &ldtk_registrations,
+                &HashMap::new(),
+                &HashMap::from([(1, &int_grid_definition)]),
+                &HashMap::from([(10, Handle::default())]),
+                &HashMap::from([(10, &tileset_definition)]),
+                &None,
+                HashSet::new(),
+                level_entity,
+                &ldtk_settings,
+                &mut EntityIidMap::default(),
+                &mut LevelSpawnProgress::default(),
+                &mut FrameSpawnBudget::new(LevelSpawnBudget::Unlimited),
+            );
+            assert!(complete);
+
+            system_state.apply(&mut app.world);
+
+            let world = &mut app.world;
+
+            let metadata_tiles: Vec<_> = world
+                .query::<(&TileMetadata, &TilePos, Option<&IntGridCell>)>()
+                .iter(world)
+                .map(|(metadata, tile_pos, int_grid_cell)| {
+                    (metadata.data.clone(), *tile_pos, int_grid_cell.copied())
+                })
+                .collect();
+            assert_eq!(
+                metadata_tiles,
+                vec![("grass".to_string(), TilePos { x: 1, y: 1 }, None)]
+            );
+
+            let tile_storage = world.query::<&TileStorage>().single(world);
+            assert!(tile_storage.get(&TilePos { x: 1, y: 1 }).is_some());
+
+            world.clear_entities();
+        }
     }
 }
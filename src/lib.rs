@@ -21,11 +21,19 @@
 //! I.e., projects that store level data within the main project file.
 //! - `external_levels`: Enable support for projects that store levels externally.
 //! I.e., projects that store data for each level in files separate from the main project file.
-//! - `derive`: Enables the derive macros for [LdtkEntity] and [LdtkIntCell].
-//! - `render`: Enables rendering via [bevy_ecs_tilemap]'s `render` feature. Disable it if you want
-//! to run in headless mode.
-//! - `atlas`: Enables the `atlas` feature of [bevy_ecs_tilemap]. This is required for WASM support
-//! and also for tile spacing to work on Tile and AutoTile layers.
+//! - `derive`: Enables the derive macros for [LdtkEntity], [LdtkIntCell], [LdtkLayer],
+//! [LdtkLevel], and [LdtkFields], and the `ldtk_enums!` macro for generating [LdtkEnum]
+//! implementations.
+//! - `render`: Enables rendering levels with [seek_ecs_tilemap] (and its `render` feature), and
+//! updating the clear color.
+//! Disable it if you want to run in headless mode, like on a game server.
+//! Without it, levels always spawn in [LevelSpawnMode::LogicOnly], and the plugin can run with
+//! [MinimalPlugins](bevy::MinimalPlugins) and an [AssetPlugin](bevy::asset::AssetPlugin).
+//! Note that this only gates the plugin's own rendering code, not its dependencies: `bevy_sprite`
+//! is always required, since its types are part of the [LdtkEntity] API, so `bevy_render` is
+//! still compiled.
+//! - `atlas`: Enables tile spacing on Tile and AutoTile layers, using [seek_ecs_tilemap]'s `atlas`
+//! feature.
 //!
 //! The `derive`, `render`, and `internal_levels` features are enabled by default.
 //! Furthermore, one or both of `internal_levels` and `external_levels` must be enabled.
@@ -33,13 +41,21 @@
 //! [feature flags]: https://doc.rust-lang.org/cargo/reference/features.html#the-features-section
 //! [LdtkEntity]: app::LdtkEntity
 //! [LdtkIntCell]: app::LdtkEntity
-//! [bevy_ecs_tilemap]: https://docs.rs/bevy_ecs_tilemap
+//! [LdtkLayer]: app::LdtkLayer
+//! [LdtkLevel]: app::LdtkLevel
+//! [LdtkEnum]: ldtk::ldtk_enum::LdtkEnum
+//! [LdtkFields]: ldtk::ldtk_fields::LdtkFields
+//! [seek_ecs_tilemap]: https://github.com/TheSeekerGame/seek_ecs_tilemap
 
 pub mod app;
 pub mod assets;
+mod auto_layer;
 mod components;
+mod edit_sync;
+mod grid;
 pub mod ldtk;
 mod level;
+mod pathfinding;
 mod plugin;
 mod resources;
 pub mod systems;
@@ -47,6 +63,8 @@ mod tile_makers;
 pub mod utils;
 
 pub use components::*;
+pub use grid::*;
+pub use pathfinding::*;
 pub use plugin::*;
 pub use resources::*;
 
@@ -57,25 +75,34 @@ pub mod prelude {
     //! `use bevy_ecs_ldtk::prelude::*;` to import commonly used items.
 
     pub use crate::{
-        app::{LdtkEntity, LdtkEntityAppExt, LdtkIntCell, LdtkIntCellAppExt},
+        app::{
+            IntGridCellContext, LdtkEntity, LdtkEntityAppExt, LdtkIntCell, LdtkIntCellAppExt,
+            LdtkLayer, LdtkLayerAppExt, LdtkLevel, LdtkLevelAppExt,
+        },
         assets::{LdtkProject, LevelIndices, LevelMetadataAccessor},
         components::{
-            EntityIid, EntityInstance, GridCoords, IntGridCell, LayerMetadata, LdtkWorldBundle,
-            LevelIid, LevelSet, Respawn, TileEnumTags, TileMetadata, Worldly, LdtkParent,
+            EntityIid, EntityInstance, GridCoords, IntGridCell, IntGridLayer, LayerMetadata,
+            LdtkEntityRefs, LdtkParallax, LdtkParallaxCamera, LdtkParent, LdtkWorldBundle,
+            LevelIid, LevelSet, LevelSpawnProgress, MergedIntGridOutline, MergedIntGridRegion,
+            Respawn, TileEnumTags, TileMetadata, Worldly,
         },
+        grid::{LdtkGrid, LdtkGridTile},
         ldtk::{
-            self, ldtk_fields::LdtkFields, raw_level_accessor::RawLevelAccessor, FieldValue,
-            LayerInstance, TilesetDefinition,
+            self, ldtk_enum::LdtkEnum, ldtk_fields::LdtkFields,
+            raw_level_accessor::RawLevelAccessor, FieldValue, LayerInstance, TilesetDefinition,
         },
-        plugin::{LdtkPlugin, ProcessLdtkApi},
+        pathfinding::{FlowField, NavGrid, NavGridSettings},
+        plugin::{LdtkPlugin, ProcessLdtkApi, SyncLdtkEditsSet},
         resources::{
-            IntGridRendering, LdtkSettings, LevelBackground, LevelEvent, LevelSelection,
-            LevelSpawnBehavior, SetClearColor, SpawnExclusions,
+            AssetEditSync, AutoLayerRuleEvaluation, EntityIidMap, HiddenLayers, IntGridMerging,
+            IntGridRendering, IntGridStorage, LdtkSettings, LevelBackground, LevelEvent,
+            LevelSelection, LevelSpawnBehavior, LevelSpawnBudget, LevelSpawnMode, SetClearColor,
+            SpawnExclusions, WorldViewLayers,
         },
     };
 
     #[cfg(feature = "derive")]
-    pub use crate::{LdtkEntity, LdtkIntCell};
+    pub use crate::{ldtk_enums, LdtkEntity, LdtkFields, LdtkIntCell, LdtkLayer, LdtkLevel};
 
     #[cfg(feature = "external_levels")]
     pub use crate::assets::LdtkExternalLevel;
@@ -0,0 +1,662 @@
+//! Navigation grids built from IntGrid layers, with A* pathfinding and flow fields.
+
+use crate::{components::GridCoords, ldtk::LayerInstance};
+use bevy::prelude::*;
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    f32::consts::SQRT_2,
+};
+
+/// Describes how to build a [NavGrid] for an IntGrid layer.
+///
+/// Used in [LdtkSettings::nav_grids](crate::prelude::LdtkSettings::nav_grids).
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ecs_ldtk::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .insert_resource(LdtkSettings {
+///             nav_grids: vec![NavGridSettings {
+///                 layer_identifier: "Terrain".to_string(),
+///                 // Empty cells are walkable, mud (value 2) is slow
+///                 costs: [(0, 1), (2, 4)].into(),
+///                 diagonal_movement: true,
+///             }],
+///             ..default()
+///         });
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct NavGridSettings {
+    /// Identifier of the IntGrid layer to build the [NavGrid] from.
+    pub layer_identifier: String,
+    /// Walkable IntGrid values, and the cost of entering a cell with that value.
+    ///
+    /// Cells with values missing from this map aren't walkable.
+    /// Empty cells have the value 0, so include it to make them walkable.
+    /// Costs lower than 1 are treated as 1.
+    pub costs: HashMap<i32, u32>,
+    /// Whether paths can move diagonally.
+    ///
+    /// Diagonal steps cost [SQRT_2] times as much as orthogonal ones, and can't cut corners, so
+    /// both of the cells they pass between need to be walkable too.
+    pub diagonal_movement: bool,
+}
+
+/// [Component] that stores the walkability of an IntGrid layer, for finding paths across it.
+///
+/// The plugin inserts this on the layer entities described by
+/// [LdtkSettings::nav_grids](crate::prelude::LdtkSettings::nav_grids), and keeps it up to date
+/// when the layer's [IntGridCell]s, or its [IntGridLayer], change.
+/// Since it's built from the layer's `int_grid_csv`, cells don't need to be spawned for it to
+/// know about them.
+///
+/// Paths are expressed in the layer's [GridCoords].
+/// [LdtkGrid::grid_coords](crate::prelude::LdtkGrid::grid_coords) can convert world positions to
+/// them.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ecs_ldtk::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Goal(GridCoords);
+///
+/// fn follow_path(
+///     mut agent_query: Query<(&mut GridCoords, &Goal)>,
+///     nav_grid_query: Query<&NavGrid>,
+/// ) {
+///     let Ok(nav_grid) = nav_grid_query.get_single() else {
+///         return;
+///     };
+///
+///     for (mut grid_coords, goal) in agent_query.iter_mut() {
+///         if let Some(path) = nav_grid.find_path(*grid_coords, goal.0) {
+///             if let Some(next) = path.get(1) {
+///                 *grid_coords = *next;
+///             }
+///         }
+///     }
+/// }
+/// # bevy::ecs::system::assert_is_system(follow_path);
+/// ```
+///
+/// [IntGridCell]: crate::prelude::IntGridCell
+/// [IntGridLayer]: crate::prelude::IntGridLayer
+#[derive(Clone, Eq, PartialEq, Debug, Default, Component)]
+pub struct NavGrid {
+    width: i32,
+    height: i32,
+    /// IntGrid values, starting at the bottom-left cell like [GridCoords].
+    values: Vec<i32>,
+    costs: HashMap<i32, u32>,
+    diagonal_movement: bool,
+}
+
+const ORTHOGONAL_OFFSETS: [IVec2; 4] = [IVec2::Y, IVec2::X, IVec2::NEG_Y, IVec2::NEG_X];
+
+const DIAGONAL_OFFSETS: [IVec2; 4] = [
+    IVec2::new(1, 1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, -1),
+    IVec2::new(-1, 1),
+];
+
+impl NavGrid {
+    /// Builds a [NavGrid] from the `int_grid_csv` of an IntGrid layer.
+    pub fn from_layer_instance(layer_instance: &LayerInstance, settings: &NavGridSettings) -> Self {
+        let width = layer_instance.c_wid;
+        let height = layer_instance.c_hei;
+
+        // int_grid_csv starts at the top of the layer, GridCoords start at the bottom
+        let values = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                layer_instance
+                    .int_grid_csv
+                    .get(((height - 1 - y) * width + x) as usize)
+                    .copied()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        NavGrid {
+            width,
+            height,
+            values,
+            costs: settings.costs.clone(),
+            diagonal_movement: settings.diagonal_movement,
+        }
+    }
+
+    /// Width of the grid, in cells.
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// Height of the grid, in cells.
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Returns true if the given coordinates are inside of the grid.
+    pub fn contains(&self, grid_coords: GridCoords) -> bool {
+        (0..self.width).contains(&grid_coords.x) && (0..self.height).contains(&grid_coords.y)
+    }
+
+    fn index(&self, grid_coords: GridCoords) -> Option<usize> {
+        self.contains(grid_coords)
+            .then(|| (grid_coords.y * self.width + grid_coords.x) as usize)
+    }
+
+    /// Returns the IntGrid value of a cell, or [None] if it's outside of the grid.
+    pub fn value(&self, grid_coords: GridCoords) -> Option<i32> {
+        self.index(grid_coords).map(|index| self.values[index])
+    }
+
+    /// Changes the IntGrid value of a cell.
+    ///
+    /// This is done automatically when the cell's [IntGridCell](crate::prelude::IntGridCell)
+    /// changes.
+    /// Coordinates outside of the grid are ignored.
+    pub fn set_value(&mut self, grid_coords: GridCoords, value: i32) {
+        if let Some(index) = self.index(grid_coords) {
+            self.values[index] = value;
+        }
+    }
+
+    /// Returns the cost of entering a cell, or [None] if it isn't walkable.
+    pub fn cost(&self, grid_coords: GridCoords) -> Option<u32> {
+        self.costs
+            .get(&self.value(grid_coords)?)
+            .map(|cost| (*cost).max(1))
+    }
+
+    /// Returns true if the cell is inside of the grid and walkable.
+    pub fn is_walkable(&self, grid_coords: GridCoords) -> bool {
+        self.cost(grid_coords).is_some()
+    }
+
+    /// Iterates over the walkable neighbours of a cell, with the cost of stepping between them.
+    ///
+    /// Since costs are for entering a cell, stepping from the neighbour back to this cell can
+    /// cost a different amount.
+    pub fn neighbours(
+        &self,
+        grid_coords: GridCoords,
+    ) -> impl Iterator<Item = (GridCoords, f32)> + '_ {
+        let orthogonal = ORTHOGONAL_OFFSETS.into_iter().map(|offset| (offset, 1.));
+
+        let diagonal = DIAGONAL_OFFSETS
+            .into_iter()
+            .filter(|_| self.diagonal_movement)
+            .filter(move |offset| {
+                self.is_walkable(grid_coords + GridCoords::new(offset.x, 0))
+                    && self.is_walkable(grid_coords + GridCoords::new(0, offset.y))
+            })
+            .map(|offset| (offset, SQRT_2));
+
+        orthogonal
+            .chain(diagonal)
+            .filter_map(move |(offset, length)| {
+                let neighbour = grid_coords + GridCoords::from(offset);
+                self.cost(neighbour)
+                    .map(|cost| (neighbour, cost as f32 * length))
+            })
+    }
+
+    /// Lowest possible cost of moving between two cells, used as the A* heuristic.
+    fn heuristic(&self, from: GridCoords, to: GridCoords, min_cost: f32) -> f32 {
+        let delta = IVec2::from(to - from).abs();
+
+        let distance = if self.diagonal_movement {
+            let diagonal_steps = delta.min_element() as f32;
+            let orthogonal_steps = (delta.max_element() - delta.min_element()) as f32;
+            diagonal_steps * SQRT_2 + orthogonal_steps
+        } else {
+            (delta.x + delta.y) as f32
+        };
+
+        distance * min_cost
+    }
+
+    /// Finds the cheapest path between two cells with A*.
+    ///
+    /// The path includes both `from` and `to`.
+    /// Returns [None] if either cell isn't walkable, or if they aren't connected.
+    pub fn find_path(&self, from: GridCoords, to: GridCoords) -> Option<Vec<GridCoords>> {
+        if !self.is_walkable(from) || !self.is_walkable(to) {
+            return None;
+        }
+
+        let min_cost = self.costs.values().map(|cost| (*cost).max(1)).min()? as f32;
+
+        let mut costs: HashMap<GridCoords, f32> = HashMap::from([(from, 0.)]);
+        let mut came_from: HashMap<GridCoords, GridCoords> = HashMap::new();
+        let mut open = BinaryHeap::from([OpenCell {
+            priority: self.heuristic(from, to, min_cost),
+            grid_coords: from,
+        }]);
+
+        while let Some(OpenCell { grid_coords, .. }) = open.pop() {
+            if grid_coords == to {
+                let mut path = vec![to];
+                while let Some(previous) = came_from.get(path.last()?) {
+                    path.push(*previous);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            let cost = costs[&grid_coords];
+
+            for (neighbour, step_cost) in self.neighbours(grid_coords) {
+                let neighbour_cost = cost + step_cost;
+
+                if costs
+                    .get(&neighbour)
+                    .filter(|known_cost| **known_cost <= neighbour_cost)
+                    .is_none()
+                {
+                    costs.insert(neighbour, neighbour_cost);
+                    came_from.insert(neighbour, grid_coords);
+                    open.push(OpenCell {
+                        priority: neighbour_cost + self.heuristic(neighbour, to, min_cost),
+                        grid_coords: neighbour,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Builds a [FlowField] leading every connected cell to `goal`.
+    ///
+    /// This is cheaper than finding a path for each agent when many of them share a goal.
+    /// If `goal` isn't walkable, no cell leads to it.
+    pub fn flow_field(&self, goal: GridCoords) -> FlowField {
+        let cell_count = (self.width * self.height) as usize;
+        let mut flow_field = FlowField {
+            width: self.width,
+            height: self.height,
+            goal,
+            costs: vec![None; cell_count],
+            directions: vec![None; cell_count],
+        };
+
+        let Some(goal_index) = self.index(goal).filter(|_| self.is_walkable(goal)) else {
+            return flow_field;
+        };
+
+        flow_field.costs[goal_index] = Some(0.);
+        flow_field.directions[goal_index] = Some(IVec2::ZERO);
+
+        // Dijkstra's algorithm, starting from the goal.
+        // Stepping from a cell to the popped one costs the popped cell's cost, scaled by the
+        // length of the step.
+        let mut open = BinaryHeap::from([OpenCell {
+            priority: 0.,
+            grid_coords: goal,
+        }]);
+
+        while let Some(OpenCell {
+            priority,
+            grid_coords,
+        }) = open.pop()
+        {
+            let Some(cost) = self.cost(grid_coords) else {
+                continue;
+            };
+
+            // Cells are pushed again when a cheaper path to them is found, so skip outdated ones
+            if flow_field
+                .cost(grid_coords)
+                .filter(|known_cost| *known_cost < priority)
+                .is_some()
+            {
+                continue;
+            }
+
+            for (neighbour, _) in self.neighbours(grid_coords) {
+                let offset = IVec2::from(grid_coords - neighbour);
+                let length = if offset.x != 0 && offset.y != 0 {
+                    SQRT_2
+                } else {
+                    1.
+                };
+                let neighbour_cost = priority + cost as f32 * length;
+
+                let Some(neighbour_index) = flow_field.index(neighbour) else {
+                    continue;
+                };
+
+                if flow_field.costs[neighbour_index]
+                    .filter(|known_cost| *known_cost <= neighbour_cost)
+                    .is_none()
+                {
+                    flow_field.costs[neighbour_index] = Some(neighbour_cost);
+                    flow_field.directions[neighbour_index] = Some(offset);
+                    open.push(OpenCell {
+                        priority: neighbour_cost,
+                        grid_coords: neighbour,
+                    });
+                }
+            }
+        }
+
+        flow_field
+    }
+}
+
+/// Entry of the open set of [NavGrid]'s searches, ordered so the lowest priority is popped first.
+#[derive(Copy, Clone, Debug)]
+struct OpenCell {
+    priority: f32,
+    grid_coords: GridCoords,
+}
+
+impl PartialEq for OpenCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenCell {}
+
+impl PartialOrd for OpenCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.total_cmp(&self.priority)
+    }
+}
+
+/// Directions towards a goal for every cell of a [NavGrid], created by [NavGrid::flow_field].
+///
+/// A flow field isn't updated when its [NavGrid] changes, so it needs to be rebuilt.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct FlowField {
+    width: i32,
+    height: i32,
+    goal: GridCoords,
+    costs: Vec<Option<f32>>,
+    directions: Vec<Option<IVec2>>,
+}
+
+impl FlowField {
+    /// The cell this flow field leads to.
+    pub fn goal(&self) -> GridCoords {
+        self.goal
+    }
+
+    fn index(&self, grid_coords: GridCoords) -> Option<usize> {
+        ((0..self.width).contains(&grid_coords.x) && (0..self.height).contains(&grid_coords.y))
+            .then(|| (grid_coords.y * self.width + grid_coords.x) as usize)
+    }
+
+    /// Returns the cost of the cheapest path from a cell to the goal, or [None] if it can't reach
+    /// the goal.
+    pub fn cost(&self, grid_coords: GridCoords) -> Option<f32> {
+        self.costs[self.index(grid_coords)?]
+    }
+
+    /// Returns the offset of the next step from a cell towards the goal.
+    ///
+    /// This is [IVec2::ZERO] at the goal, and [None] if the cell can't reach the goal.
+    pub fn direction(&self, grid_coords: GridCoords) -> Option<IVec2> {
+        self.directions[self.index(grid_coords)?]
+    }
+
+    /// Returns the next cell on the way from a cell to the goal.
+    ///
+    /// This is the goal itself at the goal, and [None] if the cell can't reach the goal.
+    pub fn next(&self, grid_coords: GridCoords) -> Option<GridCoords> {
+        self.direction(grid_coords)
+            .map(|direction| grid_coords + GridCoords::from(direction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::{IntGridCell, IntGridLayer, LdtkParent},
+        systems::update_nav_grids,
+    };
+
+    /// Builds a grid from rows of values, written top to bottom like `int_grid_csv`.
+    fn nav_grid(rows: &[&[i32]], costs: &[(i32, u32)], diagonal_movement: bool) -> NavGrid {
+        let layer_instance = LayerInstance {
+            c_wid: rows[0].len() as i32,
+            c_hei: rows.len() as i32,
+            int_grid_csv: rows.concat(),
+            ..default()
+        };
+
+        NavGrid::from_layer_instance(
+            &layer_instance,
+            &NavGridSettings {
+                layer_identifier: "Terrain".to_string(),
+                costs: costs.iter().copied().collect(),
+                diagonal_movement,
+            },
+        )
+    }
+
+    fn path(coords: &[(i32, i32)]) -> Vec<GridCoords> {
+        coords
+            .iter()
+            .map(|(x, y)| GridCoords::new(*x, *y))
+            .collect()
+    }
+
+    #[test]
+    fn values_are_read_from_int_grid_csv() {
+        let nav_grid = nav_grid(&[&[0, 1, 2], &[3, 4, 5]], &[(0, 1), (2, 3)], false);
+
+        assert_eq!(nav_grid.width(), 3);
+        assert_eq!(nav_grid.height(), 2);
+        assert_eq!(nav_grid.value(GridCoords::new(0, 0)), Some(3));
+        assert_eq!(nav_grid.value(GridCoords::new(2, 1)), Some(2));
+        assert_eq!(nav_grid.value(GridCoords::new(3, 1)), None);
+
+        assert_eq!(nav_grid.cost(GridCoords::new(0, 1)), Some(1));
+        assert_eq!(nav_grid.cost(GridCoords::new(2, 1)), Some(3));
+        assert_eq!(nav_grid.cost(GridCoords::new(1, 1)), None);
+        assert!(!nav_grid.is_walkable(GridCoords::new(-1, 0)));
+    }
+
+    #[test]
+    fn paths_go_around_walls() {
+        let nav_grid = nav_grid(
+            &[
+                &[0, 0, 0, 0], //
+                &[0, 1, 1, 0],
+                &[0, 0, 1, 0],
+            ],
+            &[(0, 1)],
+            false,
+        );
+
+        assert_eq!(
+            nav_grid.find_path(GridCoords::new(1, 0), GridCoords::new(3, 0)),
+            Some(path(&[
+                (1, 0),
+                (0, 0),
+                (0, 1),
+                (0, 2),
+                (1, 2),
+                (2, 2),
+                (3, 2),
+                (3, 1),
+                (3, 0)
+            ]))
+        );
+
+        assert_eq!(
+            nav_grid.find_path(GridCoords::new(0, 0), GridCoords::new(0, 0)),
+            Some(path(&[(0, 0)]))
+        );
+        assert_eq!(
+            nav_grid.find_path(GridCoords::new(0, 0), GridCoords::new(1, 1)),
+            None
+        );
+    }
+
+    #[test]
+    fn paths_prefer_cheaper_cells() {
+        let nav_grid = nav_grid(
+            &[
+                &[0, 0, 0], //
+                &[0, 2, 0],
+            ],
+            &[(0, 1), (2, 5)],
+            false,
+        );
+
+        assert_eq!(
+            nav_grid.find_path(GridCoords::new(0, 0), GridCoords::new(2, 0)),
+            Some(path(&[(0, 0), (0, 1), (1, 1), (2, 1), (2, 0)]))
+        );
+    }
+
+    #[test]
+    fn diagonal_paths_dont_cut_corners() {
+        let open = nav_grid(&[&[0, 0], &[0, 0]], &[(0, 1)], true);
+
+        assert_eq!(
+            open.find_path(GridCoords::new(0, 0), GridCoords::new(1, 1)),
+            Some(path(&[(0, 0), (1, 1)]))
+        );
+
+        let walled = nav_grid(
+            &[
+                &[0, 0, 0], //
+                &[0, 1, 0],
+                &[1, 0, 1],
+            ],
+            &[(0, 1)],
+            true,
+        );
+
+        assert_eq!(
+            walled.find_path(GridCoords::new(0, 1), GridCoords::new(2, 1)),
+            Some(path(&[(0, 1), (0, 2), (1, 2), (2, 2), (2, 1)]))
+        );
+        assert_eq!(
+            walled.find_path(GridCoords::new(1, 0), GridCoords::new(0, 1)),
+            None
+        );
+    }
+
+    #[test]
+    fn flow_fields_lead_to_the_goal() {
+        let nav_grid = nav_grid(
+            &[
+                &[0, 1, 0], //
+                &[0, 1, 0],
+                &[0, 0, 0],
+            ],
+            &[(0, 1)],
+            false,
+        );
+
+        let goal = GridCoords::new(2, 2);
+        let flow_field = nav_grid.flow_field(goal);
+
+        assert_eq!(flow_field.goal(), goal);
+        assert_eq!(flow_field.direction(goal), Some(IVec2::ZERO));
+        assert_eq!(flow_field.cost(GridCoords::new(0, 2)), Some(6.));
+        assert_eq!(flow_field.cost(GridCoords::new(1, 2)), None);
+        assert_eq!(flow_field.direction(GridCoords::new(1, 1)), None);
+
+        let mut grid_coords = GridCoords::new(0, 2);
+        let mut steps = 0;
+        while grid_coords != goal {
+            grid_coords = flow_field.next(grid_coords).unwrap();
+            steps += 1;
+        }
+        assert_eq!(steps, 6);
+
+        assert_eq!(nav_grid.flow_field(GridCoords::new(1, 1)).cost(goal), None);
+    }
+
+    #[test]
+    fn nav_grids_are_updated_by_int_grid_cells() {
+        let mut app = App::new();
+        app.add_systems(Update, update_nav_grids);
+
+        let layer_entity = app
+            .world
+            .spawn(nav_grid(&[&[0, 1, 0]], &[(0, 1)], false))
+            .id();
+        let cell_entity = app
+            .world
+            .spawn((
+                IntGridCell { value: 1 },
+                GridCoords::new(1, 0),
+                LdtkParent(layer_entity),
+            ))
+            .id();
+
+        app.update();
+
+        let nav_grid = app.world.get::<NavGrid>(layer_entity).unwrap();
+        assert_eq!(
+            nav_grid.find_path(GridCoords::new(0, 0), GridCoords::new(2, 0)),
+            None
+        );
+
+        app.world.get_mut::<IntGridCell>(cell_entity).unwrap().value = 0;
+        app.update();
+
+        let nav_grid = app.world.get::<NavGrid>(layer_entity).unwrap();
+        assert_eq!(nav_grid.value(GridCoords::new(1, 0)), Some(0));
+        assert_eq!(
+            nav_grid.find_path(GridCoords::new(0, 0), GridCoords::new(2, 0)),
+            Some(path(&[(0, 0), (1, 0), (2, 0)]))
+        );
+    }
+
+    #[test]
+    fn nav_grids_are_updated_by_int_grid_layers() {
+        let mut app = App::new();
+        app.add_systems(Update, update_nav_grids);
+
+        let layer_instance = LayerInstance {
+            c_wid: 3,
+            c_hei: 1,
+            int_grid_csv: vec![0, 0, 0],
+            ..default()
+        };
+
+        let layer_entity = app
+            .world
+            .spawn((
+                nav_grid(&[&[0, 0, 0]], &[(0, 1)], false),
+                IntGridLayer::from(&layer_instance),
+            ))
+            .id();
+
+        app.update();
+
+        app.world
+            .get_mut::<IntGridLayer>(layer_entity)
+            .unwrap()
+            .set(GridCoords::new(1, 0), 1);
+        app.update();
+
+        let nav_grid = app.world.get::<NavGrid>(layer_entity).unwrap();
+        assert_eq!(nav_grid.value(GridCoords::new(1, 0)), Some(1));
+        assert_eq!(
+            nav_grid.find_path(GridCoords::new(0, 0), GridCoords::new(2, 0)),
+            None
+        );
+    }
+}
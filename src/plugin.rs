@@ -23,6 +23,14 @@ enum ProcessApiSet {
     Clean,
 }
 
+/// [SystemSet] for the systems that write edits of spawned entities back into LDtk assets.
+///
+/// Runs in [PostUpdate], only if [resources::LdtkSettings::asset_edit_sync] is
+/// [resources::AssetEditSync::Enabled].
+/// Systems that edit levels in [PostUpdate] should run before this set.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, SystemSet)]
+pub struct SyncLdtkEditsSet;
+
 /// Adds the default systems, assets, and resources used by `bevy_ecs_ldtk`.
 ///
 /// Add it to your [App] to gain LDtk functionality!
@@ -30,10 +38,11 @@ enum ProcessApiSet {
 pub struct LdtkPlugin;
 
 impl Plugin for LdtkPlugin {
-    fn build(&self, mut app: &mut App) {
+    fn build(&self, app: &mut App) {
         // Check if we have added the TileMap plugin
+        #[cfg(feature = "render")]
         if !app.is_plugin_added::<seek_ecs_tilemap::TilemapPlugin>() {
-            app = app.add_plugins(seek_ecs_tilemap::TilemapPlugin);
+            app.add_plugins(seek_ecs_tilemap::TilemapPlugin);
         }
 
         app.world
@@ -46,13 +55,28 @@ impl Plugin for LdtkPlugin {
                 ProcessLdtkApi,
                 (ProcessApiSet::PreClean, ProcessApiSet::Clean).chain(),
             )
-            .init_non_send_resource::<app::LdtkEntityMap>()
-            .init_non_send_resource::<app::LdtkIntCellMap>()
+            .init_resource::<app::LdtkEntityMap>()
+            .init_resource::<app::LdtkIntCellMap>()
+            .init_resource::<app::LdtkEntitySystemMap>()
+            .init_resource::<app::LdtkLayerMap>()
+            .init_resource::<app::LdtkLevelMap>()
             .init_resource::<resources::LdtkSettings>()
+            .init_resource::<resources::EntityIidMap>()
+            .init_resource::<resources::SyncedLdtkProjectEdits>()
             .add_event::<resources::LevelEvent>()
             .add_systems(
                 PreUpdate,
-                (systems::process_ldtk_assets, systems::process_ldtk_levels),
+                (
+                    systems::process_ldtk_assets,
+                    systems::warn_unresolved_int_cell_registrations,
+                    systems::process_ldtk_levels,
+                    (
+                        systems::clean_entity_iid_map,
+                        systems::resolve_ldtk_entity_refs,
+                    )
+                        .chain()
+                        .after(systems::process_ldtk_levels),
+                ),
             )
             .add_systems(
                 ProcessLdtkApi,
@@ -72,13 +96,47 @@ impl Plugin for LdtkPlugin {
                     systems::detect_level_spawned_events
                         .pipe(systems::fire_level_transformed_events),
                     systems::worldly_adoption.after(TransformSystem::TransformPropagate),
+                    (
+                        systems::sync_int_grid_layers,
+                        (systems::apply_auto_layer_rules, systems::update_nav_grids),
+                    )
+                        .chain(),
+                    systems::apply_parallax.before(TransformSystem::TransformPropagate),
                 ),
             )
+            .configure_sets(
+                PostUpdate,
+                SyncLdtkEditsSet.run_if(|ldtk_settings: Res<resources::LdtkSettings>| {
+                    ldtk_settings.asset_edit_sync == resources::AssetEditSync::Enabled
+                }),
+            )
+            .add_systems(
+                PostUpdate,
+                systems::sync_edits_to_ldtk_assets.in_set(SyncLdtkEditsSet),
+            )
             .register_type::<components::LevelIid>()
             .register_type::<components::EntityIid>()
+            .register_type::<components::LdtkEntityRefs>()
             .register_type::<components::GridCoords>()
+            .register_type::<components::MergedIntGridRegion>()
+            .register_type::<components::MergedIntGridOutline>()
             .register_type::<components::TileMetadata>()
             .register_type::<components::TileEnumTags>()
-            .register_type::<components::LayerMetadata>();
+            .register_type::<components::LayerMetadata>()
+            .register_type::<components::IntGridLayer>()
+            .register_type::<components::LdtkParallax>()
+            .register_type::<components::LdtkParallaxCamera>();
+    }
+
+    fn finish(&self, app: &mut App) {
+        // Levels are spawned with these assets, which are missing without rendering plugins, like
+        // when using MinimalPlugins
+        if !app.world.contains_resource::<Assets<Image>>() {
+            app.init_asset::<Image>();
+        }
+
+        if !app.world.contains_resource::<Assets<TextureAtlasLayout>>() {
+            app.init_asset::<TextureAtlasLayout>();
+        }
     }
 }
@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::EntityIid;
+
+/// [`Resource`] that maps the [`EntityIid`]s of spawned LDtk entities to their bevy [`Entity`]s.
+///
+/// Entries are added by the plugin as levels spawn, and removed as the entities despawn.
+/// So, it only ever contains entities that currently exist in the world.
+///
+/// This is also what the plugin uses to resolve [`LdtkEntityRefs`].
+///
+/// [`Resource`]: https://docs.rs/bevy/latest/bevy/ecs/prelude/trait.Resource.html
+/// [`Entity`]: https://docs.rs/bevy/latest/bevy/ecs/entity/struct.Entity.html
+/// [`LdtkEntityRefs`]: crate::LdtkEntityRefs
+#[derive(Clone, Debug, Default, PartialEq, Eq, Resource)]
+pub struct EntityIidMap {
+    map: HashMap<EntityIid, Entity>,
+}
+
+impl EntityIidMap {
+    /// Get the [`Entity`] of the spawned LDtk entity with the given iid, if it exists.
+    ///
+    /// [`Entity`]: https://docs.rs/bevy/latest/bevy/ecs/entity/struct.Entity.html
+    pub fn get(&self, iid: &EntityIid) -> Option<Entity> {
+        self.map.get(iid).copied()
+    }
+
+    /// Returns true if an LDtk entity with the given iid is currently spawned.
+    pub fn contains(&self, iid: &EntityIid) -> bool {
+        self.map.contains_key(iid)
+    }
+
+    /// Iterate through all spawned LDtk entities and their iids.
+    pub fn iter(&self) -> impl Iterator<Item = (&EntityIid, Entity)> {
+        self.map.iter().map(|(iid, entity)| (iid, *entity))
+    }
+
+    /// Number of spawned LDtk entities in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if there are no spawned LDtk entities in the map.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub(crate) fn insert(&mut self, iid: EntityIid, entity: Entity) {
+        self.map.insert(iid, entity);
+    }
+
+    pub(crate) fn retain(&mut self, func: impl FnMut(&EntityIid, &mut Entity) -> bool) {
+        self.map.retain(func);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retain_removes_entries_by_entity() {
+        let mut entity_iid_map = EntityIidMap::default();
+
+        entity_iid_map.insert(EntityIid::new("a"), Entity::from_raw(0));
+        entity_iid_map.insert(EntityIid::new("b"), Entity::from_raw(1));
+
+        entity_iid_map.retain(|_, entity| *entity != Entity::from_raw(0));
+
+        assert_eq!(entity_iid_map.get(&EntityIid::new("a")), None);
+        assert_eq!(
+            entity_iid_map.get(&EntityIid::new("b")),
+            Some(Entity::from_raw(1))
+        );
+    }
+}
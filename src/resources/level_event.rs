@@ -5,10 +5,26 @@ use crate::LevelIid;
 /// Events fired by the plugin related to level spawning/despawning.
 ///
 /// Each variant stores the level's `iid` in LDtk.
+///
+/// In order, a level will produce a [`LevelEvent::SpawnTriggered`], any number of
+/// [`LevelEvent::Progress`]es, a [`LevelEvent::Spawned`], and a [`LevelEvent::Transformed`].
 #[derive(Clone, Eq, PartialEq, Debug, Hash, Event)]
 pub enum LevelEvent {
     /// Indicates that a level has been triggered to spawn, but hasn't been spawned yet.
     SpawnTriggered(LevelIid),
+    /// Some, but not all, of the level's layers have spawned.
+    ///
+    /// Only occurs when levels are spawned across multiple frames with
+    /// [`LevelSpawnBudget::PerFrame`].
+    ///
+    /// [`LevelSpawnBudget::PerFrame`]: crate::prelude::LevelSpawnBudget::PerFrame
+    Progress {
+        level_iid: LevelIid,
+        /// Number of layers spawned so far.
+        spawned_layers: usize,
+        /// Number of layers that will be spawned in total.
+        total_layers: usize,
+    },
     /// The level, with all of its layers, entities, etc., has spawned.
     ///
     /// Note: due to the frame-delay of [`GlobalTransform`] being updated, this may not be the
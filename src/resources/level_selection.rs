@@ -1,4 +1,7 @@
-use crate::{assets::LevelIndices, ldtk::Level, LevelIid};
+use crate::{
+    assets::LevelIndices, ldtk::Level, resources::LevelSpawnBehavior, utils::level_bounds,
+    LevelIid, LevelSet,
+};
 use bevy::prelude::*;
 
 /// [`Resource`] for choosing which level(s) to spawn.
@@ -18,6 +21,29 @@ pub enum LevelSelection {
     Iid(LevelIid),
     /// Spawn level with the given level `uid`.
     Uid(i32),
+    /// Spawn all levels near the `focus` entity, and despawn them as it moves away.
+    ///
+    /// Distances are measured from the `focus` entity's [`GlobalTransform`] to the nearest edge of
+    /// each level, in the space of the [`LdtkWorldBundle`].
+    /// So, levels containing the `focus` entity are always spawned.
+    ///
+    /// Levels spawn once they are within `load_radius` pixels of the `focus`, but don't despawn
+    /// until they are further than `unload_radius` pixels.
+    /// This prevents levels from repeatedly spawning and despawning when the `focus` lingers near
+    /// the edge of the `load_radius`, as long as `unload_radius` is somewhat larger.
+    ///
+    /// This is intended for "2d free map" and "GridVania" layouts, so you should use it with
+    /// [`LevelSpawnBehavior::UseWorldTranslation`].
+    /// Otherwise, every level is considered to be at the origin.
+    /// The `load_level_neighbors` option has no effect for this variant.
+    ///
+    /// [`GlobalTransform`]: https://docs.rs/bevy/latest/bevy/transform/components/struct.GlobalTransform.html
+    /// [`LdtkWorldBundle`]: crate::components::LdtkWorldBundle
+    Streaming {
+        focus: Entity,
+        load_radius: u32,
+        unload_radius: u32,
+    },
 }
 
 impl Default for LevelSelection {
@@ -79,15 +105,158 @@ impl LevelSelection {
         LevelSelection::Indices(LevelIndices::in_world(world_index, level_index))
     }
 
+    /// Construct a [`LevelSelection::Streaming`] with the given `focus` entity and radii.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_ldtk::prelude::*;
+    ///
+    /// # let player = Entity::from_raw(0);
+    /// let level_selection = LevelSelection::streaming(player, 256, 512);
+    ///
+    /// assert_eq!(
+    ///     level_selection,
+    ///     LevelSelection::Streaming {
+    ///         focus: player,
+    ///         load_radius: 256,
+    ///         unload_radius: 512,
+    ///     }
+    /// );
+    /// ```
+    pub fn streaming(focus: Entity, load_radius: u32, unload_radius: u32) -> Self {
+        LevelSelection::Streaming {
+            focus,
+            load_radius,
+            unload_radius,
+        }
+    }
+
     /// Returns true if the given level matches this [`LevelSelection`].
     ///
     /// Since levels don't inherently store their index, it needs to be provided separately.
+    ///
+    /// [`LevelSelection::Streaming`] depends on the world, not just the level, so it never matches.
     pub fn is_match(&self, indices: &LevelIndices, level: &Level) -> bool {
         match self {
             LevelSelection::Identifier(s) => *s == level.identifier,
             LevelSelection::Indices(i) => *i == *indices,
             LevelSelection::Iid(i) => *i.get() == level.iid,
             LevelSelection::Uid(u) => *u == level.uid,
+            LevelSelection::Streaming { .. } => false,
         }
     }
 }
+
+/// Determines the [`LevelSet`] for [`LevelSelection::Streaming`].
+///
+/// `focus` should be relative to the [`LdtkWorldBundle`], and `level_set` should be the currently
+/// selected levels so that they can be kept until they are out of the `unload_radius`.
+///
+/// [`LdtkWorldBundle`]: crate::components::LdtkWorldBundle
+pub(crate) fn streamed_level_set<'a>(
+    levels: impl IntoIterator<Item = &'a Level>,
+    focus: Vec2,
+    load_radius: f32,
+    unload_radius: f32,
+    level_set: &LevelSet,
+    level_spawn_behavior: &LevelSpawnBehavior,
+) -> LevelSet {
+    let iids = levels
+        .into_iter()
+        .filter(|level| {
+            let bounds = level_bounds(level, level_spawn_behavior);
+            let distance = focus.distance(focus.clamp(bounds.min, bounds.max));
+
+            let iid = LevelIid::new(level.iid.clone());
+
+            distance <= load_radius || (level_set.iids.contains(&iid) && distance <= unload_radius)
+        })
+        .map(|level| LevelIid::new(level.iid.clone()))
+        .collect();
+
+    LevelSet { iids }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(iid: &str, world_x: i32, world_y: i32) -> Level {
+        Level {
+            iid: iid.to_string(),
+            world_x,
+            world_y,
+            px_wid: 100,
+            px_hei: 100,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn streaming_loads_levels_within_load_radius() {
+        let levels = [level("a", 0, 0), level("b", 100, 0), level("c", 300, 0)];
+        let level_spawn_behavior = LevelSpawnBehavior::UseWorldTranslation {
+            load_level_neighbors: false,
+        };
+
+        let level_set = streamed_level_set(
+            &levels,
+            Vec2::new(50., -50.),
+            60.,
+            120.,
+            &LevelSet::default(),
+            &level_spawn_behavior,
+        );
+
+        assert_eq!(level_set, LevelSet::from_iids(["a", "b"]));
+
+        let level_set = streamed_level_set(
+            &levels,
+            Vec2::new(250., -50.),
+            60.,
+            120.,
+            &LevelSet::default(),
+            &level_spawn_behavior,
+        );
+
+        assert_eq!(level_set, LevelSet::from_iids(["b", "c"]));
+    }
+
+    #[test]
+    fn streaming_keeps_levels_until_unload_radius() {
+        let levels = [level("a", 0, 0), level("b", 200, 0)];
+        let level_spawn_behavior = LevelSpawnBehavior::UseWorldTranslation {
+            load_level_neighbors: false,
+        };
+
+        let stream = |focus_x: f32, level_set: &LevelSet| {
+            streamed_level_set(
+                &levels,
+                Vec2::new(focus_x, -50.),
+                50.,
+                100.,
+                level_set,
+                &level_spawn_behavior,
+            )
+        };
+
+        let level_set = stream(90., &LevelSet::default());
+        assert_eq!(level_set, LevelSet::from_iids(["a"]));
+
+        // moving within range of b, but a is still within unload radius
+        let level_set = stream(160., &level_set);
+        assert_eq!(level_set, LevelSet::from_iids(["a", "b"]));
+
+        // moving back doesn't unload b yet
+        let level_set = stream(140., &level_set);
+        assert_eq!(level_set, LevelSet::from_iids(["a", "b"]));
+
+        let level_set = stream(210., &level_set);
+        assert_eq!(level_set, LevelSet::from_iids(["b"]));
+
+        // a isn't reloaded until it's within the load radius again
+        let level_set = stream(180., &level_set);
+        assert_eq!(level_set, LevelSet::from_iids(["b"]));
+    }
+}
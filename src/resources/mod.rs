@@ -1,17 +1,23 @@
 //! Resources and events used by the plugin.
 use bevy::prelude::*;
+use std::collections::HashMap;
 
-#[allow(unused_imports)]
 use crate::assets::LdtkProject;
 #[allow(unused_imports)]
 use crate::components::LdtkWorldBundle;
+use crate::ldtk::{EntityInstance, LayerDefinition, LayerInstance};
+use crate::pathfinding::NavGridSettings;
 
 mod level_selection;
+pub(crate) use level_selection::streamed_level_set;
 pub use level_selection::LevelSelection;
 
 mod level_event;
 pub use level_event::LevelEvent;
 
+mod entity_iid_map;
+pub use entity_iid_map::EntityIidMap;
+
 /// Option in [LdtkSettings] that determines clear color behavior.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum SetClearColor {
@@ -48,6 +54,166 @@ pub enum LevelSpawnBehavior {
     },
 }
 
+/// Option in [LdtkSettings] that determines how much of a level can spawn in a single frame.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum LevelSpawnBudget {
+    /// Levels spawn completely in the frame they're processed.
+    #[default]
+    Unlimited,
+    /// Levels spawn layer-by-layer across multiple frames.
+    ///
+    /// Each frame, layers are spawned until the given number of tiles and entities is reached,
+    /// shared between all levels that are spawning.
+    /// Layers larger than the budget are split between frames, a few rows of tiles or a few
+    /// entities at a time.
+    ///
+    /// [LevelEvent::Progress] is sent for every frame a level is incomplete, and
+    /// [LevelEvent::Spawned] is only sent once all of its layers have spawned.
+    PerFrame(usize),
+}
+
+/// Option in [LdtkSettings] that determines whether levels are spawned with their visuals.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum LevelSpawnMode {
+    /// Levels spawn with tilemaps, backgrounds, and entity sprites.
+    #[default]
+    Rendered,
+    /// Levels spawn without any texture or tilemap work, for game servers and simulations.
+    ///
+    /// Level, layer, and entity entities are spawned as usual, with their [LevelIid],
+    /// [LayerMetadata], [EntityInstance], and registered bundles.
+    /// IntGrid cells still spawn with [IntGridCell] and [GridCoords], and so do tiles with
+    /// [TileMetadata] or [TileEnumTags].
+    /// Other tiles, including auto-tiles of IntGrid layers, and level backgrounds aren't spawned.
+    ///
+    /// Layers get a [TileStorage] and [TilemapGridSize] instead of a whole tilemap, so
+    /// [LdtkGrid] works the same in both modes.
+    /// Registered [LdtkEntity] bundles are given no tileset, so sprites are spawned without a
+    /// texture.
+    ///
+    /// This is always used when the `render` feature is disabled.
+    /// In that case, tileset and background images aren't loaded either, so the plugin can run
+    /// with [MinimalPlugins] and an [AssetPlugin](bevy::asset::AssetPlugin).
+    ///
+    /// [LevelIid]: crate::prelude::LevelIid
+    /// [LayerMetadata]: crate::prelude::LayerMetadata
+    /// [EntityInstance]: crate::prelude::EntityInstance
+    /// [IntGridCell]: crate::prelude::IntGridCell
+    /// [GridCoords]: crate::prelude::GridCoords
+    /// [TileMetadata]: crate::prelude::TileMetadata
+    /// [TileEnumTags]: crate::prelude::TileEnumTags
+    /// [TileStorage]: seek_ecs_tilemap::tiles::TileStorage
+    /// [TilemapGridSize]: seek_ecs_tilemap::map::TilemapGridSize
+    /// [LdtkGrid]: crate::prelude::LdtkGrid
+    /// [LdtkEntity]: crate::prelude::LdtkEntity
+    LogicOnly,
+}
+
+impl LevelSpawnMode {
+    /// Returns true if levels spawn with [LevelSpawnMode::LogicOnly], either because it's selected
+    /// or because the `render` feature is disabled.
+    pub fn is_logic_only(&self) -> bool {
+        cfg!(not(feature = "render")) || *self == LevelSpawnMode::LogicOnly
+    }
+}
+
+/// Option in [LdtkSettings] that determines whether auto-layer rules are evaluated at runtime.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum AutoLayerRuleEvaluation {
+    /// Auto-tiles only come from the tiles exported by LDtk, and don't change after spawning.
+    #[default]
+    ExportedOnly,
+    /// When an [IntGridCell] changes, the auto-layer rules of its layer, and of any AutoLayer
+    /// layers using it as a source, are re-evaluated around it.
+    /// The affected tiles' [TileTextureIndex], [TileFlip], and [TileVisible] are updated in place.
+    ///
+    /// Rules with stamp tile mode, tile offsets, or random tile offsets are not supported, and
+    /// perlin filtering and biome requirements are ignored.
+    /// Rules place a single tile per match: if a rule's tiles are rectangles of several tiles,
+    /// only the first tile of the chosen rectangle is placed.
+    /// Tiles are only updated within the existing tilemaps of a layer, so a cell can't gain more
+    /// stacked tiles than the layer had when it spawned.
+    /// In that case, the top-most tiles are kept.
+    ///
+    /// [IntGridCell]: crate::prelude::IntGridCell
+    /// [TileTextureIndex]: seek_ecs_tilemap::tiles::TileTextureIndex
+    /// [TileFlip]: seek_ecs_tilemap::tiles::TileFlip
+    /// [TileVisible]: seek_ecs_tilemap::tiles::TileVisible
+    OnIntGridChange,
+}
+
+/// Option in [LdtkSettings] that determines whether edits to spawned entities are written back
+/// into the [`LdtkProject`] asset.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum AssetEditSync {
+    /// Changes to spawned entities only affect the ECS.
+    #[default]
+    Disabled,
+    /// Changes to the following components are mirrored into the level data of the
+    /// [`LdtkProject`], or of the [`LdtkExternalLevel`] for external levels:
+    /// - [EntityInstance] on LDtk entities, including their field instances
+    /// - [GridCoords] on LDtk entities, which moves them to the new cell
+    /// - [IntGridCell] on `IntGrid` tiles
+    ///
+    /// So, edits are kept when levels respawn, and are included when the project is saved.
+    /// Modifying the [`LdtkProject`] this way doesn't cause worlds to respawn.
+    ///
+    /// This is performed by the systems in [SyncLdtkEditsSet] in [PostUpdate].
+    /// Auto-layer tiles in the asset are not updated.
+    ///
+    /// [`LdtkExternalLevel`]: crate::assets::LdtkExternalLevel
+    /// [EntityInstance]: crate::ldtk::EntityInstance
+    /// [GridCoords]: crate::prelude::GridCoords
+    /// [IntGridCell]: crate::prelude::IntGridCell
+    /// [SyncLdtkEditsSet]: crate::SyncLdtkEditsSet
+    Enabled,
+}
+
+/// [Resource] that tracks modifications made to [`LdtkProject`] assets by [AssetEditSync], so they
+/// don't cause worlds to respawn.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Resource)]
+pub struct SyncedLdtkProjectEdits {
+    generations: HashMap<AssetId<LdtkProject>, SyncedEditGenerations>,
+}
+
+/// How many times the plugin has modified a project, and how many of those modifications have
+/// been seen as [AssetEvent::Modified] events.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+struct SyncedEditGenerations {
+    recorded: u64,
+    consumed: u64,
+}
+
+impl SyncedLdtkProjectEdits {
+    /// Record that the project has been modified by the plugin.
+    ///
+    /// Should only be called once the project has actually been borrowed mutably, since that's
+    /// what sends the [AssetEvent::Modified] event.
+    pub(crate) fn record(&mut self, id: AssetId<LdtkProject>) {
+        self.generations.entry(id).or_default().recorded += 1;
+    }
+
+    /// Consume a recorded modification of the project, returning false if every recorded
+    /// modification has already been consumed.
+    pub(crate) fn consume(&mut self, id: AssetId<LdtkProject>) -> bool {
+        let Some(generations) = self.generations.get_mut(&id) else {
+            return false;
+        };
+
+        if generations.consumed >= generations.recorded {
+            return false;
+        }
+
+        generations.consumed += 1;
+        true
+    }
+
+    /// Forget the modifications of a removed project.
+    pub(crate) fn forget(&mut self, id: AssetId<LdtkProject>) {
+        self.generations.remove(&id);
+    }
+}
+
 /// Option in [LdtkSettings] that determines the visual representation of IntGrid layers when they don't have AutoTile rules.
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
 pub enum IntGridRendering {
@@ -58,6 +224,74 @@ pub enum IntGridRendering {
     Invisible,
 }
 
+/// Option in [LdtkSettings] that determines where the values of `IntGrid` layers are stored.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum IntGridStorage {
+    /// Every non-empty cell is spawned as a tile entity with an [IntGridCell], or with the bundle
+    /// registered for it.
+    ///
+    /// [IntGridCell]: crate::prelude::IntGridCell
+    #[default]
+    Entities,
+    /// The values of each `IntGrid` layer are stored in an [IntGridLayer] on the layer entity.
+    ///
+    /// Only cells with a registered [LdtkIntCell] bundle are spawned as entities, so huge layers
+    /// spawn far fewer entities.
+    /// Tiles that are needed to render the layer are still spawned, but without an [IntGridCell].
+    /// So, layers without a tileset spawn a tile for every cell with
+    /// [IntGridRendering::Colorful], and none with [IntGridRendering::Invisible] or
+    /// [LevelSpawnMode::LogicOnly].
+    ///
+    /// Since most cells have no entity, [AutoLayerRuleEvaluation::OnIntGridChange] and
+    /// [AssetEditSync::Enabled] don't apply to them.
+    ///
+    /// [IntGridLayer]: crate::prelude::IntGridLayer
+    /// [LdtkIntCell]: crate::prelude::LdtkIntCell
+    /// [IntGridCell]: crate::prelude::IntGridCell
+    Layer,
+}
+
+/// Option in [LdtkSettings] that determines how layers that are hidden in LDtk are spawned.
+///
+/// Layers are hidden if they're hidden in their level.
+/// Layers that aren't rendered in LDtk's world view can also be treated as hidden with
+/// [WorldViewLayers::Hidden].
+///
+/// The "Hide in list" and "Hide fields when inactive" options of layer definitions are ignored.
+/// They only change how the layer is presented in LDtk's UI, not whether its contents are
+/// rendered, so there's nothing for them to hide at runtime.
+/// They're still available on the [LayerDefinition] for [LdtkLayer] registrations, and layers can
+/// be excluded by their tags with [SpawnExclusions::layer_tags].
+///
+/// [LdtkLayer]: crate::prelude::LdtkLayer
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum HiddenLayers {
+    /// Hidden layers are spawned with [Visibility::Hidden], so they and their tiles and entities
+    /// aren't rendered.
+    /// They're still spawned, so they can be made visible again.
+    #[default]
+    Hidden,
+    /// Hidden layers are spawned like any other layer.
+    Visible,
+    /// Hidden layers are not spawned at all, like the layers in [SpawnExclusions].
+    Excluded,
+}
+
+/// Option in [LdtkSettings] that determines whether layers with "Render in world view" disabled in
+/// LDtk are treated as hidden.
+///
+/// That option only affects LDtk's simplified overview of the world, and these layers are rendered
+/// normally in their levels, so it's ignored by default.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum WorldViewLayers {
+    /// Layers are spawned regardless of whether they're rendered in the world view.
+    #[default]
+    Ignored,
+    /// Layers that aren't rendered in the world view are hidden or excluded like layers hidden in
+    /// their level, according to [HiddenLayers].
+    Hidden,
+}
+
 /// Option in [LdtkSettings] that dictates how the plugin handles level backgrounds.
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
 pub enum LevelBackground {
@@ -69,12 +303,119 @@ pub enum LevelBackground {
     Nonexistent,
 }
 
+/// Option in [LdtkSettings] that determines whether `IntGrid` cells are merged into larger regions.
+///
+/// When enabled, every `IntGrid` layer gets additional children with [MergedIntGridRegion]
+/// components, each covering a rectangle of cells with the same value.
+/// This is useful for spawning a small number of physics colliders instead of one per cell, without
+/// tying the plugin to any particular physics engine.
+///
+/// [MergedIntGridRegion]: crate::prelude::MergedIntGridRegion
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub enum IntGridMerging {
+    /// No regions are spawned.
+    #[default]
+    Disabled,
+    /// Regions are spawned for the given values.
+    Enabled {
+        /// `IntGrid` values to merge into regions.
+        /// If empty, all non-zero values are merged.
+        values: Vec<i32>,
+        /// Whether to also spawn a [MergedIntGridOutline] for every connected group of cells.
+        ///
+        /// [MergedIntGridOutline]: crate::prelude::MergedIntGridOutline
+        outlines: bool,
+    },
+}
+
+impl IntGridMerging {
+    /// Returns true if cells with the given value should be merged.
+    pub fn merges_value(&self, value: i32) -> bool {
+        match self {
+            IntGridMerging::Disabled => false,
+            IntGridMerging::Enabled { values, .. } => {
+                value != 0 && (values.is_empty() || values.contains(&value))
+            }
+        }
+    }
+}
+
 /// Specifies data that should be ignored completely when spawning levels. Excluded items will still
 /// be present in the [`LdtkProject`] but will not cause any entities to be spawned in the world.
+///
+/// This is useful for keeping debug markers and designer annotations out of the game.
 #[derive(Clone, Eq, PartialEq, Debug, Default)]
 pub struct SpawnExclusions {
+    /// List of level `Identifier` names (not IIDs) to skip spawning, even if they're in a
+    /// [LevelSet](crate::prelude::LevelSet).
+    pub level_identifiers: Vec<String>,
     /// List of layer `Identifier` names (not UIDs) to skip spawning as tilemaps.
     pub layer_identifiers: Vec<String>,
+    /// List of layer definition tags (`uiFilterTags` in the LDtk json).
+    /// Layers with any of these tags are skipped.
+    pub layer_tags: Vec<String>,
+    /// List of entity `Identifier` names (not UIDs) to skip spawning.
+    pub entity_identifiers: Vec<String>,
+    /// List of entity definition tags.
+    /// Entities with any of these tags are skipped.
+    pub entity_tags: Vec<String>,
+    /// Whether to skip entities tagged with [SpawnExclusions::EDITOR_ONLY_TAG].
+    pub editor_only_entities: bool,
+    /// List of `IntGrid` values to skip spawning, as pairs of layer `Identifier` and value.
+    ///
+    /// Cells with these values are spawned like empty cells, so they also aren't rendered, merged
+    /// into regions, or part of [IntGridLayer]s and [NavGrid]s.
+    /// Tiles placed on them by auto-layer rules in LDtk are skipped too.
+    ///
+    /// [IntGridLayer]: crate::prelude::IntGridLayer
+    /// [NavGrid]: crate::prelude::NavGrid
+    pub int_grid_values: Vec<(String, i32)>,
+}
+
+impl SpawnExclusions {
+    /// Entity tag for entities that only exist to help in the editor, excluded with
+    /// [SpawnExclusions::editor_only_entities].
+    pub const EDITOR_ONLY_TAG: &'static str = "editor_only";
+
+    /// Returns true if the level with the given identifier should not be spawned.
+    pub fn excludes_level(&self, level_identifier: &str) -> bool {
+        self.level_identifiers
+            .iter()
+            .any(|identifier| identifier == level_identifier)
+    }
+
+    /// Returns true if the layer should not be spawned.
+    pub fn excludes_layer(
+        &self,
+        layer_instance: &LayerInstance,
+        layer_definition: Option<&LayerDefinition>,
+    ) -> bool {
+        self.layer_identifiers.contains(&layer_instance.identifier)
+            || layer_definition.is_some_and(|layer_definition| {
+                layer_definition
+                    .ui_filter_tags
+                    .iter()
+                    .any(|tag| self.layer_tags.contains(tag))
+            })
+    }
+
+    /// Returns true if the entity should not be spawned.
+    pub fn excludes_entity(&self, entity_instance: &EntityInstance) -> bool {
+        self.entity_identifiers
+            .contains(&entity_instance.identifier)
+            || entity_instance.tags.iter().any(|tag| {
+                self.entity_tags.contains(tag)
+                    || (self.editor_only_entities && tag == Self::EDITOR_ONLY_TAG)
+            })
+    }
+
+    /// Returns true if `IntGrid` cells with the given value, on the layer with the given
+    /// identifier, should be spawned as empty cells.
+    pub fn excludes_int_grid_value(&self, layer_identifier: &str, value: i32) -> bool {
+        self.int_grid_values
+            .iter()
+            .any(|(identifier, excluded)| identifier == layer_identifier && *excluded == value)
+    }
 }
 
 /// Settings resource for the plugin.
@@ -84,6 +425,81 @@ pub struct LdtkSettings {
     pub level_spawn_behavior: LevelSpawnBehavior,
     pub set_clear_color: SetClearColor,
     pub int_grid_rendering: IntGridRendering,
+    pub int_grid_storage: IntGridStorage,
     pub level_background: LevelBackground,
+    pub hidden_layers: HiddenLayers,
+    pub world_view_layers: WorldViewLayers,
     pub exclusions: SpawnExclusions,
+    pub int_grid_merging: IntGridMerging,
+    pub level_spawn_budget: LevelSpawnBudget,
+    pub level_spawn_mode: LevelSpawnMode,
+    pub auto_layer_rule_evaluation: AutoLayerRuleEvaluation,
+    pub asset_edit_sync: AssetEditSync,
+    /// IntGrid layers to build a [NavGrid](crate::prelude::NavGrid) for.
+    pub nav_grids: Vec<NavGridSettings>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawn_exclusions_match_identifiers_tags_and_values() {
+        let exclusions = SpawnExclusions {
+            level_identifiers: vec!["Debug_Level".to_string()],
+            layer_tags: vec!["notes".to_string()],
+            entity_identifiers: vec!["Camera_Hint".to_string()],
+            entity_tags: vec!["debug".to_string()],
+            editor_only_entities: true,
+            int_grid_values: vec![("Terrain".to_string(), 4)],
+            ..default()
+        };
+
+        assert!(exclusions.excludes_level("Debug_Level"));
+        assert!(!exclusions.excludes_level("Level_0"));
+
+        let layer_instance = LayerInstance::default();
+        let notes = LayerDefinition {
+            ui_filter_tags: vec!["notes".to_string()],
+            ..default()
+        };
+        assert!(exclusions.excludes_layer(&layer_instance, Some(&notes)));
+        assert!(!exclusions.excludes_layer(&layer_instance, Some(&LayerDefinition::default())));
+        assert!(!exclusions.excludes_layer(&layer_instance, None));
+
+        let entity = |identifier: &str, tags: &[&str]| EntityInstance {
+            identifier: identifier.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..default()
+        };
+        assert!(exclusions.excludes_entity(&entity("Camera_Hint", &[])));
+        assert!(exclusions.excludes_entity(&entity("Player", &["debug"])));
+        assert!(exclusions.excludes_entity(&entity("Player", &["editor_only"])));
+        assert!(!exclusions.excludes_entity(&entity("Player", &["actor"])));
+        assert!(!SpawnExclusions::default().excludes_entity(&entity("Player", &["editor_only"])));
+
+        assert!(exclusions.excludes_int_grid_value("Terrain", 4));
+        assert!(!exclusions.excludes_int_grid_value("Terrain", 1));
+        assert!(!exclusions.excludes_int_grid_value("Water", 4));
+    }
+
+    #[test]
+    fn synced_edits_are_consumed_once_per_recorded_modification() {
+        let mut synced_edits = SyncedLdtkProjectEdits::default();
+        let id = AssetId::<LdtkProject>::default();
+
+        assert!(!synced_edits.consume(id));
+
+        synced_edits.record(id);
+        synced_edits.record(id);
+        assert!(synced_edits.consume(id));
+        assert!(synced_edits.consume(id));
+
+        // Modifications that weren't recorded, like hot reloads, aren't consumed
+        assert!(!synced_edits.consume(id));
+
+        synced_edits.record(id);
+        synced_edits.forget(id);
+        assert!(!synced_edits.consume(id));
+    }
 }
@@ -3,19 +3,32 @@
 #[cfg(feature = "render")]
 use crate::resources::SetClearColor;
 use crate::{
-    app::{LdtkEntityMap, LdtkIntCellMap},
+    app::{LdtkIntCellMap, LdtkRegistrations},
     assets::{LdtkProject, LdtkProjectData, LevelMetadataAccessor},
+    auto_layer::{AutoLayerRuleEvaluator, AutoTile},
     components::*,
-    ldtk::{Level, TilesetDefinition},
-    level::spawn_level,
-    resources::{LdtkSettings, LevelEvent, LevelSelection, LevelSpawnBehavior},
+    edit_sync::LevelEdit,
+    ldtk::{raw_level_accessor::RawLevelAccessor, EntityInstance, Level, TilesetDefinition, Type},
+    level::{layers_to_spawn, spawn_level, FrameSpawnBudget},
+    pathfinding::NavGrid,
+    resources::{
+        streamed_level_set, AutoLayerRuleEvaluation, EntityIidMap, LdtkSettings, LevelEvent,
+        LevelSelection, LevelSpawnBehavior, SyncedLdtkProjectEdits,
+    },
     utils::*,
 };
 
 #[cfg(feature = "external_levels")]
 use crate::assets::LdtkExternalLevel;
 
-use bevy::{asset::RecursiveDependencyLoadState, ecs::system::SystemState, prelude::*};
+use bevy::{
+    asset::RecursiveDependencyLoadState, ecs::system::SystemState, prelude::*,
+    transform::helper::TransformHelper,
+};
+use seek_ecs_tilemap::{
+    map::TilemapId,
+    tiles::{TileBundle, TileColor, TileFlip, TilePos, TileStorage, TileTextureIndex, TileVisible},
+};
 use std::collections::{HashMap, HashSet};
 
 /// Detects [LdtkProject] events and spawns levels as children of the [LdtkWorldBundle].
@@ -24,6 +37,7 @@ pub fn process_ldtk_assets(
     mut commands: Commands,
     mut ldtk_project_events: EventReader<AssetEvent<LdtkProject>>,
     ldtk_world_query: Query<(Entity, &Handle<LdtkProject>)>,
+    mut synced_edits: ResMut<SyncedLdtkProjectEdits>,
     #[cfg(feature = "render")] ldtk_settings: Res<LdtkSettings>,
     #[cfg(feature = "render")] mut clear_color: ResMut<ClearColor>,
     #[cfg(feature = "render")] ldtk_project_assets: Res<Assets<LdtkProject>>,
@@ -37,6 +51,9 @@ pub fn process_ldtk_assets(
                 debug!("LDtk asset creation detected.");
                 ldtk_handles_for_clear_color.insert(id);
             }
+            AssetEvent::Modified { id } if synced_edits.consume(*id) => {
+                debug!("LDtk asset modification from synced edits detected.");
+            }
             AssetEvent::Modified { id } => {
                 info!("LDtk asset modification detected.");
                 ldtk_handles_to_respawn.insert(id);
@@ -44,6 +61,7 @@ pub fn process_ldtk_assets(
             }
             AssetEvent::Removed { id } => {
                 info!("LDtk asset removal detected.");
+                synced_edits.forget(*id);
                 // if mesh was modified and removed in the same update, ignore the modification
                 // events are ordered so future modification events are ok
                 ldtk_handles_to_respawn.retain(|changed_id| *changed_id != id);
@@ -68,18 +86,92 @@ pub fn process_ldtk_assets(
     }
 }
 
+/// Warns about IntGrid registrations by name that don't match a loaded [LdtkProject].
+pub fn warn_unresolved_int_cell_registrations(
+    mut ldtk_project_events: EventReader<AssetEvent<LdtkProject>>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    ldtk_int_cell_map: Res<LdtkIntCellMap>,
+) {
+    for event in ldtk_project_events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        else {
+            continue;
+        };
+
+        let Some(project) = ldtk_project_assets.get(*id) else {
+            continue;
+        };
+
+        for unresolved in ldtk_int_cell_map.unresolved_registrations(&project.json_data().defs) {
+            warn!("IntGrid registration will never be used: {unresolved}");
+        }
+    }
+}
+
 /// Updates all LevelSet components according to the LevelSelection
 pub fn apply_level_selection(
     level_selection: Option<Res<LevelSelection>>,
     ldtk_settings: Res<LdtkSettings>,
     ldtk_project_assets: Res<Assets<LdtkProject>>,
-    mut level_set_query: Query<(&Handle<LdtkProject>, &mut LevelSet)>,
+    mut level_set_query: Query<(
+        &Handle<LdtkProject>,
+        &mut LevelSet,
+        Option<&GlobalTransform>,
+    )>,
+    transform_query: Query<&GlobalTransform>,
     #[cfg(feature = "render")] mut clear_color: ResMut<ClearColor>,
 ) {
     if let Some(level_selection) = level_selection {
-        for (ldtk_handle, mut level_set) in level_set_query.iter_mut() {
+        for (ldtk_handle, mut level_set, world_transform) in level_set_query.iter_mut() {
             if let Some(project) = &ldtk_project_assets.get(ldtk_handle) {
-                if let Some(level) = project.find_raw_level_by_level_selection(&level_selection) {
+                if let LevelSelection::Streaming {
+                    focus,
+                    load_radius,
+                    unload_radius,
+                } = *level_selection
+                {
+                    let Ok(focus_transform) = transform_query.get(focus) else {
+                        continue;
+                    };
+
+                    // levels are positioned relative to the world entity
+                    let focus_translation = world_transform
+                        .map(|world_transform| {
+                            world_transform
+                                .affine()
+                                .inverse()
+                                .transform_point3(focus_transform.translation())
+                        })
+                        .unwrap_or(focus_transform.translation())
+                        .truncate();
+
+                    let new_level_set = streamed_level_set(
+                        project.iter_raw_levels(),
+                        focus_translation,
+                        load_radius as f32,
+                        unload_radius as f32,
+                        &level_set,
+                        &ldtk_settings.level_spawn_behavior,
+                    );
+
+                    if *level_set != new_level_set {
+                        *level_set = new_level_set;
+                    }
+
+                    #[cfg(feature = "render")]
+                    if ldtk_settings.set_clear_color == SetClearColor::FromLevelBackground {
+                        if let Some(level) = project.iter_raw_levels().find(|level| {
+                            level_bounds(level, &ldtk_settings.level_spawn_behavior)
+                                .contains(focus_translation)
+                        }) {
+                            if clear_color.0 != level.bg_color {
+                                clear_color.0 = level.bg_color;
+                            }
+                        }
+                    }
+                } else if let Some(level) =
+                    project.find_raw_level_by_level_selection(&level_selection)
+                {
                     let new_level_set = {
                         let mut iids = HashSet::new();
                         iids.insert(LevelIid::new(level.iid.clone()));
@@ -152,7 +244,18 @@ pub fn apply_level_set(
 
             let previous_iids: HashSet<&LevelIid> = previous_level_maps.keys().collect();
 
-            let level_set_as_ref = level_set.iids.iter().collect::<HashSet<_>>();
+            // Excluded levels are treated as if they weren't in the level set
+            let level_set_as_ref = level_set
+                .iids
+                .iter()
+                .filter(|iid| {
+                    !project
+                        .get_raw_level_by_iid(iid.get())
+                        .is_some_and(|level| {
+                            ldtk_settings.exclusions.excludes_level(&level.identifier)
+                        })
+                })
+                .collect::<HashSet<_>>();
 
             // Spawn levels that should be spawned but aren't
             let spawned_levels = level_set_as_ref
@@ -188,16 +291,9 @@ pub fn apply_level_set(
 }
 
 fn pre_spawn_level(commands: &mut Commands, level: &Level, ldtk_settings: &LdtkSettings) -> Entity {
-    let mut translation = Vec3::ZERO;
-
-    if let LevelSpawnBehavior::UseWorldTranslation { .. } = ldtk_settings.level_spawn_behavior {
-        let level_coords = ldtk_pixel_coords_to_translation(
-            IVec2::new(level.world_x, level.world_y + level.px_hei),
-            0,
-        );
-        translation.x = level_coords.x;
-        translation.y = level_coords.y;
-    }
+    let translation = level_bounds(level, &ldtk_settings.level_spawn_behavior)
+        .min
+        .extend(0.);
 
     commands
         .spawn(LevelIid::new(level.iid.clone()))
@@ -211,6 +307,11 @@ fn pre_spawn_level(commands: &mut Commands, level: &Level, ldtk_settings: &LdtkS
 
 /// Performs all the spawning of levels, layers, chunks, bundles, entities, tiles, etc. when a
 /// LevelIid is added or respawned.
+///
+/// With [LevelSpawnBudget::PerFrame], this also continues spawning levels that have a
+/// [LevelSpawnProgress].
+///
+/// [LevelSpawnBudget::PerFrame]: crate::prelude::LevelSpawnBudget::PerFrame
 #[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn process_ldtk_levels(
     mut commands: Commands,
@@ -219,8 +320,7 @@ pub fn process_ldtk_levels(
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
     ldtk_project_assets: Res<Assets<LdtkProject>>,
     #[cfg(feature = "external_levels")] level_assets: Res<Assets<LdtkExternalLevel>>,
-    ldtk_entity_map: NonSend<LdtkEntityMap>,
-    ldtk_int_cell_map: NonSend<LdtkIntCellMap>,
+    ldtk_registrations: LdtkRegistrations,
     ldtk_query: Query<&Handle<LdtkProject>>,
     level_query: Query<
         (
@@ -232,10 +332,14 @@ pub fn process_ldtk_levels(
         ),
         Or<(Added<LevelIid>, With<Respawn>)>,
     >,
+    spawning_level_query: Query<(Entity, &LevelIid, &Parent, &LevelSpawnProgress)>,
     worldly_query: Query<&Worldly>,
     mut level_events: EventWriter<LevelEvent>,
     ldtk_settings: Res<LdtkSettings>,
+    mut entity_iid_map: ResMut<EntityIidMap>,
 ) {
+    let mut levels_to_process = Vec::new();
+
     for (ldtk_entity, level_iid, parent, respawn, children) in level_query.iter() {
         // Checking if the level has any children is an okay method of checking whether it has
         // already been processed.
@@ -249,80 +353,122 @@ pub fn process_ldtk_levels(
         let already_processed = matches!(children, Some(children) if !children.is_empty());
 
         if !already_processed {
-            if let Ok(ldtk_handle) = ldtk_query.get(parent.get()) {
-                if let Some(ldtk_project) = ldtk_project_assets.get(ldtk_handle) {
-                    // Commence the spawning
-                    let tileset_definition_map: HashMap<i32, &TilesetDefinition> = ldtk_project
-                        .json_data()
-                        .defs
-                        .tilesets
-                        .iter()
-                        .map(|t| (t.uid, t))
-                        .collect();
-
-                    let entity_definition_map =
-                        create_entity_definition_map(&ldtk_project.json_data().defs.entities);
-
-                    let layer_definition_map =
-                        create_layer_definition_map(&ldtk_project.json_data().defs.layers);
-
-                    let int_grid_image_handle = &ldtk_project.int_grid_image_handle();
-
-                    let worldly_set = worldly_query.iter().cloned().collect();
-
-                    let maybe_level_data = match ldtk_project.data() {
-                        #[cfg(feature = "internal_levels")]
-                        LdtkProjectData::Standalone(project) => project
-                            .level_map()
-                            .get(level_iid.get())
-                            .and_then(|level_metadata| {
-                                let loaded_level = project
-                                    .get_loaded_level_at_indices(level_metadata.indices())?;
-
-                                Some((level_metadata, loaded_level))
-                            }),
-                        #[cfg(feature = "external_levels")]
-                        LdtkProjectData::Parent(project) => project
-                            .level_map()
-                            .get(level_iid.get())
-                            .and_then(|level_metadata| {
-                                let loaded_level = project.get_external_level_at_indices(
-                                    &level_assets,
-                                    level_metadata.metadata().indices(),
-                                )?;
-
-                                Some((level_metadata.metadata(), loaded_level))
-                            }),
-                    };
+            levels_to_process.push((
+                ldtk_entity,
+                level_iid,
+                parent,
+                respawn.is_some(),
+                LevelSpawnProgress::default(),
+            ));
+        }
+    }
+
+    // Partially-spawned levels that are restarting have already been collected
+    for (ldtk_entity, level_iid, parent, progress) in spawning_level_query.iter() {
+        if !levels_to_process
+            .iter()
+            .any(|(entity, ..)| *entity == ldtk_entity)
+        {
+            levels_to_process.push((ldtk_entity, level_iid, parent, false, progress.clone()));
+        }
+    }
+
+    let mut budget = FrameSpawnBudget::new(ldtk_settings.level_spawn_budget);
+
+    for (ldtk_entity, level_iid, parent, respawn, mut progress) in levels_to_process {
+        if let Ok(ldtk_handle) = ldtk_query.get(parent.get()) {
+            if let Some(ldtk_project) = ldtk_project_assets.get(ldtk_handle) {
+                // Commence the spawning
+                let tileset_definition_map: HashMap<i32, &TilesetDefinition> = ldtk_project
+                    .json_data()
+                    .defs
+                    .tilesets
+                    .iter()
+                    .map(|t| (t.uid, t))
+                    .collect();
+
+                let entity_definition_map =
+                    create_entity_definition_map(&ldtk_project.json_data().defs.entities);
+
+                let layer_definition_map =
+                    create_layer_definition_map(&ldtk_project.json_data().defs.layers);
+
+                let int_grid_image_handle = &ldtk_project.int_grid_image_handle();
+
+                let worldly_set = worldly_query.iter().cloned().collect();
+
+                let maybe_level_data = match ldtk_project.data() {
+                    #[cfg(feature = "internal_levels")]
+                    LdtkProjectData::Standalone(project) => project
+                        .level_map()
+                        .get(level_iid.get())
+                        .and_then(|level_metadata| {
+                            let loaded_level =
+                                project.get_loaded_level_at_indices(level_metadata.indices())?;
+
+                            Some((level_metadata, loaded_level))
+                        }),
+                    #[cfg(feature = "external_levels")]
+                    LdtkProjectData::Parent(project) => project
+                        .level_map()
+                        .get(level_iid.get())
+                        .and_then(|level_metadata| {
+                            let loaded_level = project.get_external_level_at_indices(
+                                &level_assets,
+                                level_metadata.metadata().indices(),
+                            )?;
+
+                            Some((level_metadata.metadata(), loaded_level))
+                        }),
+                };
+
+                if let Some((level_metadata, loaded_level)) = maybe_level_data {
+                    let complete = spawn_level(
+                        loaded_level,
+                        level_metadata.bg_image(),
+                        &mut commands,
+                        &asset_server,
+                        &images,
+                        &mut texture_atlases,
+                        &ldtk_registrations,
+                        &entity_definition_map,
+                        &layer_definition_map,
+                        ldtk_project.tileset_map(),
+                        &tileset_definition_map,
+                        int_grid_image_handle,
+                        worldly_set,
+                        ldtk_entity,
+                        &ldtk_settings,
+                        &mut entity_iid_map,
+                        &mut progress,
+                        &mut budget,
+                    );
+
+                    if complete {
+                        commands.entity(ldtk_entity).remove::<LevelSpawnProgress>();
 
-                    if let Some((level_metadata, loaded_level)) = maybe_level_data {
-                        spawn_level(
-                            loaded_level,
-                            level_metadata.bg_image(),
-                            &mut commands,
-                            &asset_server,
-                            &images,
-                            &mut texture_atlases,
-                            &ldtk_entity_map,
-                            &ldtk_int_cell_map,
-                            &entity_definition_map,
-                            &layer_definition_map,
-                            ldtk_project.tileset_map(),
-                            &tileset_definition_map,
-                            int_grid_image_handle,
-                            worldly_set,
-                            ldtk_entity,
-                            &ldtk_settings,
-                        );
                         level_events.send(LevelEvent::Spawned(LevelIid::new(
                             loaded_level.iid().clone(),
                         )));
-                    }
+                    } else {
+                        level_events.send(LevelEvent::Progress {
+                            level_iid: LevelIid::new(loaded_level.iid().clone()),
+                            spawned_layers: progress.spawned_layers(),
+                            total_layers: layers_to_spawn(
+                                loaded_level.layer_instances(),
+                                &layer_definition_map,
+                                &ldtk_settings,
+                            )
+                            .count(),
+                        });
 
-                    if respawn.is_some() {
-                        commands.entity(ldtk_entity).remove::<Respawn>();
+                        commands.entity(ldtk_entity).insert(progress);
                     }
                 }
+
+                if respawn {
+                    commands.entity(ldtk_entity).remove::<Respawn>();
+                }
             }
         }
     }
@@ -384,6 +530,532 @@ pub fn clean_respawn_entities(world: &mut World) {
     }
 }
 
+/// Removes entries from the [EntityIidMap] whose entities have despawned.
+pub fn clean_entity_iid_map(
+    mut entity_iid_map: ResMut<EntityIidMap>,
+    mut removed_entity_iids: RemovedComponents<EntityIid>,
+) {
+    let removed_entities: HashSet<Entity> = removed_entity_iids.read().collect();
+
+    if !removed_entities.is_empty() {
+        entity_iid_map.retain(|_, entity| !removed_entities.contains(entity));
+    }
+}
+
+/// Resolves [LdtkEntityRefs] components using the [EntityIidMap].
+///
+/// Newly added components are always resolved.
+/// All other components are only re-resolved when the [EntityIidMap] changes.
+pub fn resolve_ldtk_entity_refs(
+    entity_iid_map: Res<EntityIidMap>,
+    mut entity_refs_query: Query<&mut LdtkEntityRefs>,
+) {
+    for mut entity_refs in entity_refs_query.iter_mut() {
+        if entity_iid_map.is_changed() || entity_refs.is_added() {
+            // Only trigger change detection if a target actually changed
+            if entity_refs
+                .bypass_change_detection()
+                .resolve(&entity_iid_map)
+            {
+                entity_refs.set_changed();
+            }
+        }
+    }
+}
+
+/// Mirrors changes to [IntGridCell]s into the [IntGridLayer] of their layer, if it has one.
+///
+/// Runs before [update_nav_grids] and [apply_auto_layer_rules], so they see the edited values.
+pub fn sync_int_grid_layers(
+    changed_cell_query: Query<(Ref<IntGridCell>, &GridCoords, &LdtkParent), Changed<IntGridCell>>,
+    mut int_grid_layer_query: Query<&mut IntGridLayer>,
+) {
+    for (int_grid_cell, grid_coords, ldtk_parent) in changed_cell_query.iter() {
+        // Spawned cells are already in the layer
+        if int_grid_cell.is_added() {
+            continue;
+        }
+
+        if let Ok(mut int_grid_layer) = int_grid_layer_query.get_mut(ldtk_parent.0) {
+            if int_grid_layer.get(*grid_coords) != Some(int_grid_cell.value) {
+                int_grid_layer.set(*grid_coords, int_grid_cell.value);
+            }
+        }
+    }
+}
+
+/// Updates [NavGrid]s when [IntGridCell]s on their layers, or their layer's [IntGridLayer], change.
+///
+/// Cells are not tracked when they're despawned, so set their value to 0 to empty them instead.
+pub fn update_nav_grids(
+    changed_cell_query: Query<(Ref<IntGridCell>, &GridCoords, &LdtkParent), Changed<IntGridCell>>,
+    changed_layer_query: Query<(Entity, Ref<IntGridLayer>), Changed<IntGridLayer>>,
+    mut nav_grid_query: Query<&mut NavGrid>,
+) {
+    for (int_grid_cell, grid_coords, ldtk_parent) in changed_cell_query.iter() {
+        // Spawned cells are already in the layer's int_grid_csv
+        if int_grid_cell.is_added() {
+            continue;
+        }
+
+        if let Ok(mut nav_grid) = nav_grid_query.get_mut(ldtk_parent.0) {
+            if nav_grid.value(*grid_coords) != Some(int_grid_cell.value) {
+                nav_grid.set_value(*grid_coords, int_grid_cell.value);
+            }
+        }
+    }
+
+    for (layer_entity, int_grid_layer) in changed_layer_query.iter() {
+        if int_grid_layer.is_added() {
+            continue;
+        }
+
+        if let Ok(mut nav_grid) = nav_grid_query.get_mut(layer_entity) {
+            for y in 0..int_grid_layer.height() {
+                for x in 0..int_grid_layer.width() {
+                    let grid_coords = GridCoords::new(x, y);
+                    let value = int_grid_layer.get(grid_coords);
+
+                    if let Some(value) =
+                        value.filter(|value| nav_grid.value(grid_coords) != Some(*value))
+                    {
+                        nav_grid.set_value(grid_coords, value);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Offsets and scales [LdtkParallax] layers relative to the [LdtkParallaxCamera].
+///
+/// Runs before transform propagation, so the layers follow the camera without a frame of delay.
+pub fn apply_parallax(
+    camera_query: Query<Entity, With<LdtkParallaxCamera>>,
+    layer_query: Query<(Entity, &LdtkParallax, &Parent)>,
+    mut transforms: ParamSet<(TransformHelper, Query<&mut Transform, With<LdtkParallax>>)>,
+) {
+    let Ok(camera_entity) = camera_query.get_single() else {
+        return;
+    };
+
+    let layer_transforms: Vec<_> = {
+        let transform_helper = transforms.p0();
+
+        let Ok(camera_transform) = transform_helper.compute_global_transform(camera_entity) else {
+            return;
+        };
+
+        layer_query
+            .iter()
+            .filter_map(|(layer_entity, parallax, level_entity)| {
+                let level_transform = transform_helper
+                    .compute_global_transform(level_entity.get())
+                    .ok()?;
+
+                let camera_translation = level_transform
+                    .affine()
+                    .inverse()
+                    .transform_point3(camera_transform.translation())
+                    .truncate();
+
+                Some((layer_entity, parallax.transform(camera_translation)))
+            })
+            .collect()
+    };
+
+    let mut layer_transform_query = transforms.p1();
+    for (layer_entity, transform) in layer_transforms {
+        if let Ok(mut layer_transform) = layer_transform_query.get_mut(layer_entity) {
+            layer_transform.set_if_neq(transform);
+        }
+    }
+}
+
+/// Re-evaluates auto-layer rules around [IntGridCell]s that have changed since they spawned.
+///
+/// Only runs when [LdtkSettings::auto_layer_rule_evaluation] is
+/// [AutoLayerRuleEvaluation::OnIntGridChange].
+#[allow(clippy::too_many_arguments)]
+pub fn apply_auto_layer_rules(
+    mut commands: Commands,
+    ldtk_settings: Res<LdtkSettings>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    changed_cell_query: Query<(Ref<IntGridCell>, &GridCoords, &LdtkParent), Changed<IntGridCell>>,
+    int_grid_cell_query: Query<&IntGridCell>,
+    int_grid_layer_query: Query<&IntGridLayer>,
+    mut layer_query: Query<(&LayerMetadata, &Parent, &mut TileStorage, &Transform)>,
+    mut tile_query: Query<(&mut TileTextureIndex, &mut TileFlip, &mut TileVisible)>,
+    level_query: Query<(&Parent, &Children), With<LevelIid>>,
+    world_query: Query<&Handle<LdtkProject>>,
+) {
+    if ldtk_settings.auto_layer_rule_evaluation != AutoLayerRuleEvaluation::OnIntGridChange {
+        return;
+    }
+
+    let mut changed_cells: HashMap<Entity, Vec<GridCoords>> = HashMap::new();
+
+    for (int_grid_cell, grid_coords, ldtk_parent) in changed_cell_query.iter() {
+        if !int_grid_cell.is_added() {
+            changed_cells
+                .entry(ldtk_parent.0)
+                .or_default()
+                .push(*grid_coords);
+        }
+    }
+
+    // Tiles are determined for every affected cell before any of them are updated, so the
+    // storages of the IntGrid layers can be read while evaluating rules.
+    let mut tile_updates: Vec<(Entity, TilePos, Option<AutoTile>)> = Vec::new();
+
+    for (int_grid_layer, changed_cells) in changed_cells {
+        let Ok((source_metadata, level, source_storage, _)) = layer_query.get(int_grid_layer)
+        else {
+            continue;
+        };
+
+        let Some((ldtk_project, layer_entities)) =
+            level_query
+                .get(level.get())
+                .ok()
+                .and_then(|(world, layer_entities)| {
+                    let ldtk_project =
+                        ldtk_project_assets.get(world_query.get(world.get()).ok()?)?;
+                    Some((ldtk_project, layer_entities))
+                })
+        else {
+            continue;
+        };
+
+        let layer_definitions = &ldtk_project.json_data().defs.layers;
+
+        let Some(source_definition) = layer_definitions
+            .iter()
+            .find(|definition| definition.uid == source_metadata.layer_def_uid)
+        else {
+            continue;
+        };
+
+        let (width, height) = (source_metadata.c_wid, source_metadata.c_hei);
+
+        let int_grid = |coords: IVec2| -> Option<i32> {
+            if coords.x < 0 || coords.y < 0 || coords.x >= width || coords.y >= height {
+                return None;
+            }
+
+            let tile_pos = TilePos::new(coords.x as u32, (height - 1 - coords.y) as u32);
+
+            // With layer storage, most cells only have a value in the layer's IntGridLayer
+            if let Ok(int_grid_layer) = int_grid_layer_query.get(int_grid_layer) {
+                return int_grid_layer.get(tile_pos.into());
+            }
+
+            Some(
+                source_storage
+                    .get(&tile_pos)
+                    .and_then(|tile_entity| int_grid_cell_query.get(tile_entity).ok())
+                    .map(|int_grid_cell| int_grid_cell.value)
+                    .unwrap_or_default(),
+            )
+        };
+
+        // Layers may be split into multiple tilemaps to stack tiles, which share a layer iid
+        let mut sublayers: HashMap<&str, Vec<(f32, Entity)>> = HashMap::new();
+
+        for layer_entity in layer_entities.iter() {
+            if let Ok((metadata, _, _, transform)) = layer_query.get(*layer_entity) {
+                sublayers
+                    .entry(metadata.iid.as_str())
+                    .or_default()
+                    .push((transform.translation.z, *layer_entity));
+            }
+        }
+
+        for sublayers in sublayers.values_mut() {
+            sublayers.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+            let Ok((metadata, ..)) = layer_query.get(sublayers[0].1) else {
+                continue;
+            };
+
+            let Some(layer_definition) = layer_definitions
+                .iter()
+                .find(|definition| definition.uid == metadata.layer_def_uid)
+            else {
+                continue;
+            };
+
+            let uses_source = if metadata.iid == source_metadata.iid {
+                true
+            } else {
+                metadata.layer_instance_type == Type::AutoLayer
+                    && layer_definition.auto_source_layer_def_uid == Some(source_definition.uid)
+            };
+
+            if !uses_source {
+                continue;
+            }
+
+            let evaluator = AutoLayerRuleEvaluator::new(
+                layer_definition,
+                source_definition,
+                metadata.seed,
+                &metadata.optional_rules,
+            );
+
+            if evaluator.is_empty() {
+                continue;
+            }
+
+            let radius = evaluator.pattern_radius();
+
+            let affected_cells: HashSet<IVec2> = changed_cells
+                .iter()
+                .flat_map(|grid_coords| {
+                    let cell = IVec2::new(grid_coords.x, height - 1 - grid_coords.y);
+
+                    (-radius..=radius)
+                        .flat_map(move |y| (-radius..=radius).map(move |x| cell + IVec2::new(x, y)))
+                })
+                .filter(|cell| cell.x >= 0 && cell.y >= 0 && cell.x < width && cell.y < height)
+                .collect();
+
+            for cell in affected_cells {
+                let tile_pos = TilePos::new(cell.x as u32, (height - 1 - cell.y) as u32);
+                let tiles = evaluator.evaluate_cell(int_grid, cell);
+
+                // If there are more tiles than sublayers, only the top-most tiles are kept
+                let mut tiles = tiles
+                    .iter()
+                    .skip(tiles.len().saturating_sub(sublayers.len()));
+
+                for (_, sublayer) in sublayers.iter() {
+                    tile_updates.push((*sublayer, tile_pos, tiles.next().copied()));
+                }
+            }
+        }
+    }
+
+    for (sublayer, tile_pos, auto_tile) in tile_updates {
+        let Ok((metadata, _, mut storage, _)) = layer_query.get_mut(sublayer) else {
+            continue;
+        };
+
+        match (storage.get(&tile_pos), auto_tile) {
+            (Some(tile_entity), auto_tile) => {
+                if let Ok((mut texture_index, mut flip, mut visible)) =
+                    tile_query.get_mut(tile_entity)
+                {
+                    match auto_tile {
+                        Some(auto_tile) => {
+                            texture_index.0 = auto_tile.tile_id as u32;
+                            flip.x = auto_tile.flip_x;
+                            flip.y = auto_tile.flip_y;
+                            visible.0 = true;
+                        }
+                        None => visible.0 = false,
+                    }
+                }
+            }
+            (None, Some(auto_tile)) => {
+                let mut color = TileColor::default();
+                color.0.set_a(metadata.opacity);
+
+                let tile_entity = commands
+                    .spawn(TileGridBundle {
+                        tile_bundle: TileBundle {
+                            position: tile_pos,
+                            texture_index: TileTextureIndex(auto_tile.tile_id as u32),
+                            flip: TileFlip {
+                                x: auto_tile.flip_x,
+                                y: auto_tile.flip_y,
+                                ..default()
+                            },
+                            color,
+                            ..default()
+                        },
+                        grid_coords: tile_pos.into(),
+                    })
+                    .insert((TilemapId(sublayer), LdtkParent(sublayer)))
+                    .id();
+
+                storage.set(&tile_pos, tile_entity);
+            }
+            (None, None) => (),
+        }
+    }
+}
+
+/// Writes changes to the [EntityInstance], [GridCoords], and [IntGridCell] components of spawned
+/// entities back into the level data of their [LdtkProject] asset.
+///
+/// Only runs when [LdtkSettings::asset_edit_sync] is [AssetEditSync::Enabled].
+///
+/// [AssetEditSync::Enabled]: crate::resources::AssetEditSync::Enabled
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn sync_edits_to_ldtk_assets(
+    mut ldtk_project_assets: ResMut<Assets<LdtkProject>>,
+    #[cfg(feature = "external_levels")] mut external_level_assets: ResMut<
+        Assets<LdtkExternalLevel>,
+    >,
+    mut synced_edits: ResMut<SyncedLdtkProjectEdits>,
+    mut entity_query: Query<
+        (Entity, &mut EntityInstance, Option<Ref<GridCoords>>),
+        (
+            With<EntityIid>,
+            Or<(Changed<EntityInstance>, Changed<GridCoords>)>,
+        ),
+    >,
+    int_grid_cell_query: Query<(Ref<IntGridCell>, &GridCoords, &LdtkParent), Changed<IntGridCell>>,
+    layer_metadata_query: Query<&LayerMetadata>,
+    level_query: Query<&LevelIid>,
+    ancestor_query: Query<&Parent>,
+    world_query: Query<&Handle<LdtkProject>>,
+) {
+    // Edits are grouped by project, and by level if it's known
+    let mut project_edits: HashMap<
+        AssetId<LdtkProject>,
+        Vec<(Option<Entity>, Option<String>, LevelEdit)>,
+    > = HashMap::new();
+
+    let find_targets = |entity: Entity| {
+        let project_id = ancestor_query
+            .iter_ancestors(entity)
+            .find_map(|ancestor| world_query.get(ancestor).ok())?
+            .id();
+        let level_iid = ancestor_query
+            .iter_ancestors(entity)
+            .find_map(|ancestor| level_query.get(ancestor).ok())
+            .map(|level_iid| level_iid.get().clone());
+
+        Some((project_id, level_iid))
+    };
+
+    for (entity, entity_instance, grid_coords) in entity_query.iter_mut() {
+        if entity_instance.is_added() {
+            continue;
+        }
+
+        let grid_coords = grid_coords
+            .filter(|grid_coords| grid_coords.is_changed() && !grid_coords.is_added())
+            .map(|grid_coords| *grid_coords);
+
+        if !entity_instance.is_changed() && grid_coords.is_none() {
+            continue;
+        }
+
+        if let Some((project_id, level_iid)) = find_targets(entity) {
+            project_edits.entry(project_id).or_default().push((
+                Some(entity),
+                level_iid,
+                LevelEdit::Entity {
+                    instance: entity_instance.clone(),
+                    grid_coords,
+                },
+            ));
+        }
+    }
+
+    for (int_grid_cell, grid_coords, ldtk_parent) in int_grid_cell_query.iter() {
+        if int_grid_cell.is_added() {
+            continue;
+        }
+
+        let Ok(layer_metadata) = layer_metadata_query.get(ldtk_parent.0) else {
+            continue;
+        };
+
+        if let Some((project_id, level_iid)) = find_targets(ldtk_parent.0) {
+            project_edits.entry(project_id).or_default().push((
+                None,
+                level_iid,
+                LevelEdit::IntGrid {
+                    layer_iid: layer_metadata.iid.clone(),
+                    grid_coords: *grid_coords,
+                    value: int_grid_cell.value,
+                },
+            ));
+        }
+    }
+
+    let mut applied_entity_edits = Vec::new();
+
+    for (project_id, edits) in project_edits {
+        let Some(ldtk_project) = ldtk_project_assets.get(project_id) else {
+            continue;
+        };
+
+        if ldtk_project.json_data().external_levels {
+            #[cfg(feature = "external_levels")]
+            {
+                let level_handles: Vec<(String, Handle<LdtkExternalLevel>)> = ldtk_project
+                    .as_parent()
+                    .level_map()
+                    .iter()
+                    .map(|(iid, metadata)| (iid.clone(), metadata.external_handle().clone()))
+                    .collect();
+
+                for (entity, level_iid, mut edit) in edits {
+                    let target = level_handles.iter().find(|(iid, handle)| {
+                        level_iid.as_ref().map_or_else(
+                            || {
+                                external_level_assets
+                                    .get(handle)
+                                    .is_some_and(|level| edit.applies_to(level.data().raw()))
+                            },
+                            |level_iid| iid == level_iid,
+                        )
+                    });
+
+                    if let Some(external_level) =
+                        target.and_then(|(_, handle)| external_level_assets.get_mut(handle))
+                    {
+                        if edit.apply(external_level.data_mut()) {
+                            applied_entity_edits.extend(entity.map(|entity| (entity, edit)));
+                        }
+                    }
+                }
+            }
+        } else {
+            let Some(ldtk_project) = ldtk_project_assets.get_mut(project_id) else {
+                continue;
+            };
+
+            // Borrowing the project mutably sends a modification event, which mustn't respawn it
+            synced_edits.record(project_id);
+
+            let ldtk_json = ldtk_project.json_data_mut();
+
+            for (entity, level_iid, mut edit) in edits {
+                let applied = ldtk_json
+                    .levels
+                    .iter_mut()
+                    .chain(
+                        ldtk_json
+                            .worlds
+                            .iter_mut()
+                            .flat_map(|world| world.levels.iter_mut()),
+                    )
+                    .filter(|level| level_iid.iter().all(|level_iid| level.iid == *level_iid))
+                    .any(|level| edit.apply(level));
+
+                if applied {
+                    applied_entity_edits.extend(entity.map(|entity| (entity, edit)));
+                }
+            }
+        }
+    }
+
+    // Moving an entity changes its position in LDtk, which is reflected in its EntityInstance
+    for (entity, edit) in applied_entity_edits {
+        if let (Ok((_, mut entity_instance, _)), LevelEdit::Entity { instance, .. }) =
+            (entity_query.get_mut(entity), edit)
+        {
+            *entity_instance.bypass_change_detection() = instance;
+        }
+    }
+}
+
 /// Implements the functionality for `Worldly` components.
 pub fn worldly_adoption(
     mut commands: Commands,
@@ -430,3 +1102,117 @@ pub fn fire_level_transformed_events(
         writer.send(LevelEvent::Transformed(id));
     }
 }
+
+#[cfg(all(test, feature = "internal_levels"))]
+mod tests {
+    use super::*;
+    use crate::{
+        app::{LdtkEntityMap, LdtkEntitySystemMap, LdtkLayerMap, LdtkLevelMap},
+        assets::{LdtkJsonWithMetadata, LevelIndices, LevelMetadata},
+        ldtk::{LayerDefinition, LayerInstance, LdtkJson},
+        resources::{LevelSpawnBudget, LevelSpawnMode},
+    };
+
+    #[test]
+    fn layers_larger_than_the_budget_spawn_across_frames() {
+        let mut app = App::new();
+        app.add_plugins(AssetPlugin::default())
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_asset::<LdtkProject>()
+            .init_resource::<LdtkEntityMap>()
+            .init_resource::<LdtkEntitySystemMap>()
+            .init_resource::<LdtkIntCellMap>()
+            .init_resource::<LdtkLayerMap>()
+            .init_resource::<LdtkLevelMap>()
+            .init_resource::<EntityIidMap>()
+            .insert_resource(LdtkSettings {
+                level_spawn_mode: LevelSpawnMode::LogicOnly,
+                level_spawn_budget: LevelSpawnBudget::PerFrame(4),
+                ..default()
+            })
+            .add_event::<LevelEvent>()
+            .add_systems(Update, process_ldtk_levels);
+
+        #[cfg(feature = "external_levels")]
+        app.init_asset::<LdtkExternalLevel>();
+
+        let mut data = LdtkJson::default();
+        data.defs.layers.push(LayerDefinition {
+            uid: 1,
+            identifier: "Terrain".to_string(),
+            purple_type: Type::IntGrid,
+            ..default()
+        });
+        data.levels.push(Level {
+            iid: "level".to_string(),
+            layer_instances: Some(vec![LayerInstance {
+                identifier: "Terrain".to_string(),
+                layer_instance_type: Type::IntGrid,
+                layer_def_uid: 1,
+                c_wid: 4,
+                c_hei: 4,
+                grid_size: 16,
+                int_grid_csv: vec![1; 16],
+                ..default()
+            }]),
+            ..default()
+        });
+
+        let level_map = HashMap::from([(
+            "level".to_string(),
+            LevelMetadata::new(None, LevelIndices::in_root(0)),
+        )]);
+
+        let project = app
+            .world
+            .resource_mut::<Assets<LdtkProject>>()
+            .add(LdtkProject::new(
+                LdtkProjectData::Standalone(LdtkJsonWithMetadata::new(data, level_map)),
+                HashMap::new(),
+                None,
+            ));
+
+        let world_entity = app.world.spawn(project).id();
+        app.world
+            .spawn(LevelIid::new("level"))
+            .set_parent(world_entity);
+
+        // the layer costs 16 tiles, so one row of 4 tiles spawns each frame
+        for frame in 1..=4 {
+            app.update();
+
+            let events: Vec<_> = app
+                .world
+                .resource_mut::<Events<LevelEvent>>()
+                .drain()
+                .collect();
+
+            let cells = app.world.query::<&IntGridCell>().iter(&app.world).count();
+            assert_eq!(cells, frame * 4);
+
+            if frame < 4 {
+                assert!(matches!(
+                    events.as_slice(),
+                    [LevelEvent::Progress {
+                        spawned_layers: 0,
+                        total_layers: 1,
+                        ..
+                    }]
+                ));
+            } else {
+                assert_eq!(events, vec![LevelEvent::Spawned(LevelIid::new("level"))]);
+            }
+        }
+
+        let storage = app.world.query::<&TileStorage>().single(&app.world).clone();
+        for x in 0..4 {
+            for y in 0..4 {
+                assert!(storage.get(&TilePos { x, y }).is_some());
+            }
+        }
+
+        app.update();
+        assert!(app.world.resource::<Events<LevelEvent>>().is_empty());
+    }
+}
@@ -5,7 +5,7 @@
 //! impl FnMut(TilePos) -> Option<TileBundle>
 //! ```
 //!
-//! Tile makers can be used with [set_all_tiles_with_func] to spawn many tiles at once.
+//! Tile makers can be used with [set_tile_rows_with_func] to spawn many tiles at once.
 
 use crate::{
     components::TileGridBundle,
@@ -152,6 +152,23 @@ pub(crate) fn tile_pos_to_tile_if_int_grid_nonzero_maker(
     }
 }
 
+/// Creates a tile maker that returns None where `excludes` is true, and the result of the provided
+/// tile maker elsewhere.
+///
+/// Used for spawning IntGrid layers without the cells of excluded values.
+pub(crate) fn tile_pos_to_tile_unless_excluded_maker(
+    mut tile_maker: impl FnMut(TilePos) -> Option<TileBundle>,
+    excludes: impl Fn(TilePos) -> bool,
+) -> impl FnMut(TilePos) -> Option<TileBundle> {
+    move |tile_pos: TilePos| -> Option<TileBundle> {
+        if excludes(tile_pos) {
+            None
+        } else {
+            tile_maker(tile_pos)
+        }
+    }
+}
+
 /// Creates a tile maker that returns one of the following:
 /// 1. Returns a tile that matches the tileset visual of the ldtk layer, if it exists
 /// 2. Returns an invisible tile, if the corresponding intgrid position is nonzero and the sublayer index is 0,
@@ -3,17 +3,17 @@
 #[allow(unused_imports)]
 use crate::{
     app::LdtkEntity,
-    components::{GridCoords, IntGridCell},
+    components::{GridCoords, IntGridCell, LdtkWorldBundle},
 };
 
-use crate::{components::TileGridBundle, ldtk::*};
+use crate::{components::TileGridBundle, ldtk::*, resources::LevelSpawnBehavior};
 use bevy::prelude::*;
 use seek_ecs_tilemap::{
     map::{TilemapId, TilemapSize},
     tiles::{TilePos, TileStorage},
 };
 
-use std::{collections::HashMap, hash::Hash};
+use std::{collections::HashMap, ops::Range};
 
 /// The `int_grid_csv` field of a [LayerInstance] is a 1-dimensional [`Vec<i32>`].
 /// This function can map the indices of this [Vec] to a corresponding [GridCoords].
@@ -236,26 +236,60 @@ pub fn ldtk_pixel_coords_to_translation_pivoted(
     pivot_point + offset
 }
 
+/// Calculates the bounds of a level relative to the [LdtkWorldBundle], according to where it
+/// would be spawned with the given [LevelSpawnBehavior].
+///
+/// The `min` of the resulting [Rect] is the translation of the level entity.
+pub fn level_bounds(level: &Level, level_spawn_behavior: &LevelSpawnBehavior) -> Rect {
+    let min = match level_spawn_behavior {
+        LevelSpawnBehavior::UseWorldTranslation { .. } => ldtk_pixel_coords_to_translation(
+            IVec2::new(level.world_x, level.world_y + level.px_hei),
+            0,
+        ),
+        LevelSpawnBehavior::UseZeroTranslation => Vec2::ZERO,
+    };
+
+    Rect {
+        min,
+        max: min + IVec2::new(level.px_wid, level.px_hei).as_vec2(),
+    }
+}
+
 /// Similar to [LayerBuilder::new_batch], except it doesn't consume the [LayerBuilder]
 ///
 /// This allows for more methods to be performed on the [LayerBuilder] before building it.
 /// However, the performance cons of using non-batch methods still apply here.
-pub(crate) fn set_all_tiles_with_func(
+///
+/// Only the given rows are visited, so that large layers can be spawned a few rows at a time.
+pub(crate) fn set_tile_rows_with_func(
     commands: &mut Commands,
     storage: &mut TileStorage,
     size: TilemapSize,
+    rows: Range<u32>,
+    tilemap_id: TilemapId,
+    func: impl FnMut(TilePos) -> Option<TileGridBundle>,
+) {
+    let tile_positions = rows.flat_map(|y| (0..size.x).map(move |x| TilePos { x, y }));
+
+    set_tiles_with_func(commands, storage, tile_positions, tilemap_id, func);
+}
+
+/// Similar to [set_tile_rows_with_func], except only the given positions are visited.
+///
+/// Useful when tiles are sparse, since the rest of the layer doesn't need to be iterated.
+pub(crate) fn set_tiles_with_func(
+    commands: &mut Commands,
+    storage: &mut TileStorage,
+    tile_positions: impl IntoIterator<Item = TilePos>,
     tilemap_id: TilemapId,
     mut func: impl FnMut(TilePos) -> Option<TileGridBundle>,
 ) {
-    for x in 0..size.x {
-        for y in 0..size.y {
-            let tile_pos = TilePos { x, y };
-            let tile_entity = func(tile_pos)
-                .map(|tile_bundle| commands.spawn(tile_bundle).insert(tilemap_id).id());
-            match tile_entity {
-                Some(tile_entity) => storage.set(&tile_pos, tile_entity),
-                None => storage.remove(&tile_pos),
-            }
+    for tile_pos in tile_positions {
+        let tile_entity =
+            func(tile_pos).map(|tile_bundle| commands.spawn(tile_bundle).insert(tilemap_id).id());
+        match tile_entity {
+            Some(tile_entity) => storage.set(&tile_pos, tile_entity),
+            None => storage.remove(&tile_pos),
         }
     }
 }
@@ -285,26 +319,6 @@ where
         .or_else(|| func(None, None))
 }
 
-/// The "get" function used on [bevy_ecs_ldtk::app::LdtkEntityMap] and
-/// [bevy_ecs_ldtk::app::LdtkIntCellMap].
-///
-/// Due to the defaulting functionality of the `AppExt` traits in [bevy_ecs_ldtk::app], a single
-/// instance of an LDtk entity or int grid tile may match multiple registrations.
-/// This function is responsible for picking the correct registration while spawning these
-/// entities/tiles.
-pub(crate) fn ldtk_map_get_or_default<'a, A, B, L>(
-    a: A,
-    b: B,
-    default: &'a L,
-    map: &'a HashMap<(Option<A>, Option<B>), L>,
-) -> &'a L
-where
-    A: Hash + Eq + Clone,
-    B: Hash + Eq + Clone,
-{
-    try_each_optional_permutation(a, b, |x, y| map.get(&(x, y))).unwrap_or(default)
-}
-
 /// Creates a [SpriteSheetBundle] from the entity information available to the
 /// [LdtkEntity::bundle_entity] method.
 ///
@@ -385,6 +399,32 @@ pub fn sprite_bundle_from_entity_info(tileset: Option<&Handle<Image>>) -> Sprite
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_level_bounds() {
+        let level = Level {
+            world_x: 32,
+            world_y: 64,
+            px_wid: 100,
+            px_hei: 50,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            level_bounds(
+                &level,
+                &LevelSpawnBehavior::UseWorldTranslation {
+                    load_level_neighbors: false
+                }
+            ),
+            Rect::new(32., -114., 132., -64.)
+        );
+
+        assert_eq!(
+            level_bounds(&level, &LevelSpawnBehavior::UseZeroTranslation),
+            Rect::new(0., 0., 100., 50.)
+        );
+    }
+
     #[test]
     fn test_int_grid_index_to_tile_pos() {
         assert_eq!(